@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
 
 #[derive(Parser)]
@@ -6,6 +6,11 @@ use clap_complete::Shell;
 #[command(about = "Worktree Task Manager - manage multi-agent parallel development tasks")]
 #[command(version)]
 pub struct Cli {
+    /// Number tasks by alphabetical position instead of stable ID (overrides
+    /// config legacy_index; --legacy-index=false turns it off)
+    #[arg(long, global = true, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    pub legacy_index: Option<bool>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -28,6 +33,16 @@ pub enum Commands {
         name: Option<String>,
     },
 
+    /// Show a task and the settings it will be started with
+    Show {
+        /// Task name or index
+        name: String,
+
+        /// Output as JSON for programmatic use
+        #[arg(long)]
+        json: bool,
+    },
+
     /// List all tasks (grouped by status)
     List {
         /// Show tree view instead of grouped view
@@ -37,6 +52,14 @@ pub enum Commands {
         /// Output as JSON for programmatic use
         #[arg(long)]
         json: bool,
+
+        /// Only show tasks with this tag
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Only show tasks owned by this owner
+        #[arg(long)]
+        owner: Option<String>,
     },
 
     /// Start a task (creates worktree and tmux window)
@@ -49,10 +72,61 @@ pub enum Commands {
         all: bool,
     },
 
+    /// Keep up to max_parallel tasks running, starting tasks as they become ready
+    Run {
+        /// Seconds between scheduler checks
+        #[arg(long, default_value = "10")]
+        interval: u64,
+    },
+
+    /// Notify when running agents finish or go idle (see the config's notify section)
+    Watch {
+        /// Seconds between checks
+        #[arg(long, default_value = "10")]
+        interval: u64,
+
+        /// Check once and exit instead of watching
+        #[arg(long)]
+        once: bool,
+    },
+
+    /// Send a follow-up message to a running task's agent
+    Send {
+        /// Task name or index
+        name: String,
+
+        /// Text to send
+        message: String,
+    },
+
+    /// Start a fresh agent session on a task's existing worktree and branch
+    Restart {
+        /// Task name or index
+        name: String,
+
+        /// Give the new session the previous session's last messages
+        #[arg(long)]
+        keep_history: bool,
+    },
+
+    /// Resume a done task's agent session in a new tmux window (back to running)
+    Resume {
+        /// Task name or index
+        name: String,
+
+        /// Message to continue the session with
+        #[arg(long)]
+        prompt: Option<String>,
+    },
+
     /// Mark a task as done (ready for review)
     Done {
         /// Task name to mark as done
         name: String,
+
+        /// Mark as done even if acceptance checks fail
+        #[arg(long)]
+        force: bool,
     },
 
     /// Mark a task as merged (keeps worktree/branch for review)
@@ -61,6 +135,25 @@ pub enum Commands {
         name: String,
     },
 
+    /// Merge a task branch into the base branch and mark it as merged
+    Merge {
+        /// Task name to merge
+        name: String,
+
+        /// How to integrate the branch
+        #[arg(long, value_enum, default_value_t = MergeStrategy::Merge)]
+        strategy: MergeStrategy,
+    },
+
+    /// Rebase a stacked task (and tasks stacked on it) onto its updated upstream branches
+    Restack {
+        /// Task name to restack
+        name: String,
+    },
+
+    /// Detect task branches merged into the base branch and mark them as merged
+    Sync,
+
     /// Archive a merged task (cleanup worktree and branch)
     Archive {
         /// Task name to archive
@@ -86,13 +179,21 @@ pub enum Commands {
         #[arg(long)]
         json: bool,
 
-        /// Execute action on task (list, done, merged, archive, enter, tail)
+        /// Execute action on task (list, done, merged, archive, enter, tail, send)
         #[arg(long, value_name = "ACTION")]
         action: Option<String>,
 
         /// Target task name (required with --action)
         #[arg(long, value_name = "NAME")]
         task: Option<String>,
+
+        /// With --action done: mark as done even if acceptance checks fail
+        #[arg(long)]
+        force: bool,
+
+        /// With --action send: text to send to the agent
+        #[arg(long, value_name = "TEXT")]
+        message: Option<String>,
     },
 
     /// View last assistant messages from task transcript (JSON output)
@@ -100,14 +201,48 @@ pub enum Commands {
         /// Task name
         name: String,
 
-        /// Number of turns to show (default: 1)
-        #[arg(short = 'n', default_value = "1")]
-        count: usize,
+        /// Number of turns to show (default: 1; with --types/--since/--format,
+        /// number of events, default: 20, or all with --since)
+        #[arg(short = 'n')]
+        count: Option<usize>,
+
+        /// Show these kinds of events (comma-separated)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        types: Vec<TailType>,
+
+        /// Only show events after this time: a duration ago (30s, 10m, 2h, 1d)
+        /// or an RFC 3339 timestamp
+        #[arg(long, value_name = "WHEN")]
+        since: Option<String>,
+
+        /// Show the event stream in this format
+        #[arg(long, value_enum)]
+        format: Option<TailFormat>,
     },
 
     /// Generate filtered logs for all tasks
     Logs,
 
+    /// Show token usage and estimated cost per task, across all of its sessions
+    Cost {
+        /// Only show this task (name or index)
+        name: Option<String>,
+
+        /// Output as JSON for programmatic use
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show the journal of task state transitions
+    History {
+        /// Only show events for this task (name or index)
+        name: Option<String>,
+
+        /// Output as JSON for programmatic use
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Create a scratch environment (quick worktree without task file)
     New {
         /// Optional name (defaults to s1, s2, ...)
@@ -125,6 +260,44 @@ pub enum Commands {
     },
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+pub enum MergeStrategy {
+    /// Merge commit (--no-ff)
+    Merge,
+    /// Single squashed commit
+    Squash,
+    /// Replay branch commits onto the base branch (linear history)
+    Rebase,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum TailType {
+    /// Assistant text
+    Text,
+    /// Assistant thinking
+    Thinking,
+    /// Tool calls with their inputs
+    #[value(name = "tool_use")]
+    ToolUse,
+    /// Tool output (truncated)
+    #[value(name = "tool_result")]
+    ToolResult,
+    /// Prompts sent to the agent
+    User,
+    /// Session events (init, compaction, summaries)
+    System,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum TailFormat {
+    /// JSON array of events
+    Json,
+    /// One line per event
+    Text,
+    /// Markdown document
+    Markdown,
+}
+
 #[derive(Subcommand)]
 pub enum CompletionsAction {
     /// Generate completions script to stdout
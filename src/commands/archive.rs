@@ -1,7 +1,9 @@
 use std::path::Path;
 
 use crate::error::{Result, WtError};
-use crate::models::{TaskStatus, TaskStore, WtConfig};
+use crate::models::{Actor, Hook, TaskStatus, TaskStore, WtConfig};
+use crate::services::hooks;
+use crate::services::template::TemplateContext;
 use crate::services::{git, tmux, workspace::WorkspaceInitializer};
 
 pub fn execute(task_ref: String, silent: bool) -> Result<()> {
@@ -32,26 +34,36 @@ pub fn execute(task_ref: String, silent: bool) -> Result<()> {
     let instance = store.get_instance(&name).cloned();
     let repo_root = git::get_repo_root()?;
 
+    // pre_archive can veto the archive
+    hooks::run_for_task(&store, Hook::PreArchive, &name)?;
+
     // Run archive script if configured (before any cleanup)
     if let Some(ref script) = config.archive_script {
         if let Some(ref inst) = instance {
             if !silent {
                 println!("Running archive script...");
             }
+            let vars = TemplateContext::for_task(&config, &repo_root, &name, store.get(&name), Some(inst));
             let source_dir = Path::new(".");
             let initializer = WorkspaceInitializer::new(&inst.worktree_path, source_dir);
-            initializer.run_init_script(script)?;
+            initializer.run_init_script(&vars.render(script)?)?;
         }
     }
 
     // Update status BEFORE deleting worktree (symlink would be deleted with worktree)
-    if is_scratch {
-        store.status.tasks.remove(&name);
-    } else {
-        store.set_status(&name, TaskStatus::Archived);
-        store.set_instance(&name, None);
-    }
-    store.save_status()?;
+    // silent is only used by the TUI
+    let actor = if silent { Actor::Tui } else { Actor::Cli };
+    store.update_status(|status| {
+        if is_scratch {
+            status.transition(&name, TaskStatus::Archived, actor);
+            status.tasks.remove(&name);
+        } else {
+            status.validate_transition(&name, TaskStatus::Archived)?;
+            status.transition(&name, TaskStatus::Archived, actor);
+            status.set_instance(&name, None);
+        }
+        Ok(())
+    })?;
 
     // Cleanup all resources (after status is saved)
     if let Some(inst) = instance {
@@ -0,0 +1,143 @@
+//! Cost command - token usage and estimated cost per task, across all of its sessions.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::display::{GRAY, RESET};
+use crate::error::{Result, WtError};
+use crate::models::{TaskStore, WtConfig};
+use crate::services::cost::{self, CostEstimate, TaskUsage};
+use crate::services::transcript::TokenUsage;
+
+#[derive(Serialize)]
+struct TaskCost {
+    name: String,
+    sessions: usize,
+    usage: TokenUsage,
+    by_model: BTreeMap<String, TokenUsage>,
+    cost: CostEstimate,
+}
+
+#[derive(Serialize)]
+struct CostTotal {
+    sessions: usize,
+    usage: TokenUsage,
+    cost: CostEstimate,
+}
+
+#[derive(Serialize)]
+struct CostOutput {
+    tasks: Vec<TaskCost>,
+    total: CostTotal,
+}
+
+pub fn execute(task_ref: Option<String>, json: bool) -> Result<()> {
+    let store = TaskStore::load()?;
+    let config = WtConfig::load()?;
+
+    // Every task that has been started, or just the one asked for
+    let names: Vec<String> = match task_ref {
+        Some(r) => {
+            let name = store.resolve_task_ref(&r)?;
+            if store.get_instance(&name).is_none() {
+                return Err(WtError::TaskNotStarted(name));
+            }
+            vec![name]
+        }
+        None => store
+            .list()
+            .iter()
+            .map(|t| t.name().to_string())
+            .filter(|name| store.get_instance(name).is_some())
+            .collect(),
+    };
+
+    let mut tasks = Vec::new();
+    let mut all = TaskUsage::default();
+    for name in names {
+        let Some(instance) = store.get_instance(&name) else {
+            continue;
+        };
+        let usage = cost::task_usage(&name, instance);
+        all.add(&usage);
+        tasks.push(TaskCost {
+            sessions: usage.sessions,
+            usage: usage.total(),
+            cost: cost::estimate(&usage, &config.prices),
+            by_model: usage.by_model,
+            name,
+        });
+    }
+
+    let output = CostOutput {
+        tasks,
+        total: CostTotal {
+            sessions: all.sessions,
+            usage: all.total(),
+            cost: cost::estimate(&all, &config.prices),
+        },
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        print_human(&output);
+    }
+    Ok(())
+}
+
+fn print_human(output: &CostOutput) {
+    if output.tasks.is_empty() {
+        println!("No started tasks.");
+        return;
+    }
+
+    let name_width = output
+        .tasks
+        .iter()
+        .map(|t| t.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("Total".len());
+    let row = |name: &str, sessions: usize, usage: &TokenUsage, cost: &CostEstimate| {
+        format!(
+            "{:<width$}  {:>8}  {:>12}  {:>12}  {:>12}  {:>12}  {:>9}",
+            name,
+            sessions,
+            usage.input,
+            usage.cache_creation,
+            usage.cache_read,
+            usage.output,
+            cost.display(),
+            width = name_width
+        )
+    };
+
+    println!(
+        "{}{:<width$}  {:>8}  {:>12}  {:>12}  {:>12}  {:>12}  {:>9}{}",
+        GRAY,
+        "Task",
+        "Sessions",
+        "Input",
+        "Cache write",
+        "Cache read",
+        "Output",
+        "Cost",
+        RESET,
+        width = name_width
+    );
+    for task in &output.tasks {
+        println!("{}", row(&task.name, task.sessions, &task.usage, &task.cost));
+    }
+    let total = &output.total;
+    println!("{}", row("Total", total.sessions, &total.usage, &total.cost));
+
+    if !total.cost.unpriced_models.is_empty() {
+        println!();
+        println!(
+            "No price for {} (add it under `prices:` in .wt/config.yaml)",
+            total.cost.unpriced_models.join(", ")
+        );
+    }
+}
@@ -5,9 +5,10 @@ pub fn execute(json: String) -> Result<()> {
     let input: TaskInput = serde_json::from_str(&json)
         .map_err(|e| WtError::InvalidJson(e.to_string()))?;
 
-    let file_path = TaskStore::create(&input)?;
+    let (file_path, id) = TaskStore::create(&input)?;
 
     println!("Task '{}' created.", input.name);
+    println!("  ID:   {}", id);
     println!("  File: {}", file_path.display());
 
     if !input.depends.is_empty() {
@@ -1,8 +1,18 @@
+use std::path::Path;
+
+use crate::display::{GRAY, GREEN, RED, RESET};
 use crate::error::{Result, WtError};
-use crate::models::{TaskStatus, TaskStore};
+use crate::models::{Actor, CheckReport, Hook, TaskStatus, TaskStore, WtConfig};
+use crate::services::hooks;
 use crate::services::tmux;
+use crate::services::workspace::WorkspaceInitializer;
+
+pub fn execute(task_ref: String, force: bool) -> Result<()> {
+    execute_as(task_ref, force, Actor::Cli)
+}
 
-pub fn execute(task_ref: String) -> Result<()> {
+/// `wt done` on behalf of `actor` (the TUI's `d` runs it after leaving the TUI)
+pub fn execute_as(task_ref: String, force: bool, actor: Actor) -> Result<()> {
     let mut store = TaskStore::load()?;
 
     // Resolve task reference (name or index) to actual name
@@ -20,17 +30,108 @@ pub fn execute(task_ref: String) -> Result<()> {
     store.ensure_exists(&name)?;
     store.validate_transition(&name, TaskStatus::Done)?;
 
+    mark_done(&mut store, &name, force, actor, false)?;
+
+    println!("Task '{}' marked as done.", name);
+    println!("After PR is merged, run: wt merged {}", name);
+    Ok(())
+}
+
+/// Run the task's acceptance checks, then close its tmux window and mark it done.
+///
+/// If a check fails the task stays Running (the results are still recorded)
+/// and `ChecksFailed` is returned, unless `force` is set.
+/// `silent` suppresses progress output (TUI).
+pub fn mark_done(store: &mut TaskStore, name: &str, force: bool, actor: Actor, silent: bool) -> Result<()> {
+    let mut report = run_checks(store, name, force, silent)?;
+
+    if let Some(report) = report.as_mut().filter(|r| !r.passed) {
+        if !force {
+            let failed = report.failed().iter().map(|c| c.to_string()).collect();
+            let report = report.clone();
+            store.update_status(|status| {
+                status.set_checks(name, report);
+                Ok(())
+            })?;
+            return Err(WtError::ChecksFailed {
+                task: name.to_string(),
+                failed,
+            });
+        }
+        report.forced = true;
+    }
+
+    // on_done can veto the transition too, unless forced
+    if let Err(e) = hooks::run_for_task(store, Hook::OnDone, name) {
+        if !force {
+            return Err(e);
+        }
+        if !silent {
+            eprintln!("  Warning: {}", e);
+        }
+    }
+
     // Close tmux window if still alive
-    if let Some(instance) = store.get_instance(&name) {
-        if tmux::kill_window_if_exists(&instance.tmux_session, &instance.tmux_window)? {
+    if let Some(instance) = store.get_instance(name) {
+        if tmux::kill_window_if_exists(&instance.tmux_session, &instance.tmux_window)? && !silent {
             println!("Closed tmux window {}:{}", instance.tmux_session, instance.tmux_window);
         }
     }
 
-    store.set_status(&name, TaskStatus::Done);
-    store.save_status()?;
+    store.update_status(|status| {
+        status.validate_transition(name, TaskStatus::Done)?;
+        status.transition(name, TaskStatus::Done, actor);
+        if let Some(report) = report {
+            status.set_checks(name, report);
+        }
+        Ok(())
+    })
+}
 
-    println!("Task '{}' marked as done.", name);
-    println!("After PR is merged, run: wt merged {}", name);
-    Ok(())
+/// Run the task's acceptance checks (its `acceptance` list, else config
+/// `checks`) in its worktree. Returns `None` if there is nothing to run.
+fn run_checks(store: &TaskStore, name: &str, force: bool, silent: bool) -> Result<Option<CheckReport>> {
+    let config_checks = WtConfig::load().map(|c| c.checks).unwrap_or_default();
+    let checks = store.ensure_exists(name)?.checks(&config_checks).to_vec();
+    if checks.is_empty() {
+        return Ok(None);
+    }
+
+    let worktree = store
+        .get_instance(name)
+        .map(|i| i.worktree_path.clone())
+        .filter(|p| Path::new(p).exists());
+    let Some(worktree) = worktree else {
+        if force {
+            if !silent {
+                println!("Skipping acceptance checks: worktree not found.");
+            }
+            return Ok(None);
+        }
+        return Err(WtError::WorktreeNotFound(name.to_string()));
+    };
+
+    if !silent {
+        println!("Running {} acceptance check(s)...", checks.len());
+    }
+    let initializer = WorkspaceInitializer::new(&worktree, Path::new("."));
+    let mut results = Vec::new();
+    for check in &checks {
+        let result = initializer.run_check(check);
+        if !silent {
+            let secs = result.duration_ms as f64 / 1000.0;
+            if result.passed {
+                println!("  {}✓{} {} {}({:.1}s){}", GREEN, RESET, check, GRAY, secs, RESET);
+            } else {
+                let code = result.exit_code.map(|c| c.to_string()).unwrap_or_else(|| "-".to_string());
+                println!("  {}✗{} {} {}(exit {}, {:.1}s){}", RED, RESET, check, GRAY, code, secs, RESET);
+                for line in result.output.lines() {
+                    println!("    {}{}{}", GRAY, line, RESET);
+                }
+            }
+        }
+        results.push(result);
+    }
+
+    Ok(Some(CheckReport::new(results)))
 }
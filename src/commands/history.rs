@@ -0,0 +1,87 @@
+//! History command - query the journal of task state transitions.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
+
+use crate::display::{format_duration, GRAY, RESET};
+use crate::error::Result;
+use crate::models::{StatusStore, TaskEvent, TaskStore};
+
+#[derive(Serialize)]
+struct HistoryOutput {
+    events: Vec<TaskEvent>,
+}
+
+pub fn execute(task_ref: Option<String>, json: bool) -> Result<()> {
+    let store = TaskStore::load()?;
+
+    // Archived scratch environments no longer exist anywhere but the journal,
+    // so fall back to the literal name when it can't be resolved
+    let name = task_ref.map(|r| store.resolve_task_ref(&r).unwrap_or(r));
+
+    let events: Vec<TaskEvent> = StatusStore::load_events()?
+        .into_iter()
+        .filter(|e| name.as_ref().is_none_or(|n| &e.task == n))
+        .collect();
+
+    if json {
+        println!("{}", serde_json::to_string(&HistoryOutput { events })?);
+    } else {
+        print_human(&events);
+    }
+
+    Ok(())
+}
+
+fn print_human(events: &[TaskEvent]) {
+    if events.is_empty() {
+        println!("No events recorded.");
+        return;
+    }
+
+    let name_width = events.iter().map(|e| e.task.len()).max().unwrap_or(0);
+
+    // Time of the previous transition per task, to show how long it sat in a state
+    let mut last_seen: HashMap<&str, DateTime<Utc>> = HashMap::new();
+
+    for event in events {
+        let local = event.timestamp.with_timezone(&Local);
+        let transition = format!(
+            "{} {} → {} {}",
+            event.from.colored_icon(),
+            event.from.display_name(),
+            event.to.colored_icon(),
+            event.to.display_name()
+        );
+
+        let elapsed = last_seen
+            .get(event.task.as_str())
+            .map(|prev| {
+                let secs = event.timestamp.signed_duration_since(*prev).num_seconds();
+                format!(
+                    " {}after {} {}{}",
+                    GRAY,
+                    format_duration(secs.max(0)),
+                    event.from.display_name(),
+                    RESET
+                )
+            })
+            .unwrap_or_default();
+
+        println!(
+            "{}{}{}  {:<width$}  {}  ({}){}",
+            GRAY,
+            local.format("%Y-%m-%d %H:%M:%S"),
+            RESET,
+            event.task,
+            transition,
+            event.actor.display_name(),
+            elapsed,
+            width = name_width
+        );
+
+        last_seen.insert(event.task.as_str(), event.timestamp);
+    }
+}
@@ -31,7 +31,9 @@ fn generate_config(project_name: &str) -> String {
 # ============================================
 
 # wt start 执行的参数
-# 支持模板变量: ${{task}}, ${{branch}}, ${{worktree}}
+# 支持模板变量: ${{task}} ${{branch}} ${{worktree}} ${{repo_root}} ${{session_id}}
+# ${{base_branch}} ${{depends}} ${{task_file}} ${{env.NAME}}（init_script/archive_script 同样适用）
+# $${{ 表示字面量 ${{，未知变量会报错（wt validate 可检查）
 #
 # 交互模式（默认）- 启动 REPL 带初始 prompt
 start_args: '"@.wt/tasks/${{task}}.md 请完成这个任务"'
@@ -49,6 +51,18 @@ start_args: '"@.wt/tasks/${{task}}.md 请完成这个任务"'
 # 示例: ccc, claude --yolo, /path/to/claude
 # claude_command: claude
 
+# Agent 后端
+# 默认: claude
+# shell: 执行 shell_command，输出写入 .wt/logs/<task>/agent.log 作为 transcript（wt tail 可查看）
+# 单个任务可在 frontmatter 中用 agent: shell 覆盖
+# agent: shell
+# shell_command: ./scripts/agent.sh ${{task}}
+
+# 任务 tmux 窗口中 export 的环境变量
+# 单个任务可在 frontmatter 中用 env/agent_command/start_args/model/init_script 覆盖，wt show <task> 查看生效值
+# env:
+#   NODE_ENV: development
+
 # tmux session 名称
 # 默认: 项目目录名
 tmux_session: {}
@@ -58,6 +72,30 @@ tmux_session: {}
 # 支持相对路径（相对于项目根目录）和绝对路径
 # worktree_dir: .wt/worktrees
 
+# 基准分支 (任务从此分支创建，diff/提交统计、合并检测和 wt merge 都以它为准)
+# 默认: 不设置时从当前 HEAD 创建，统计对比 main/master
+# 单个任务可在 frontmatter 中用 base: release/2.0 覆盖
+# base_branch: develop
+
+# 同时运行的任务上限 (wt start --all 和 wt run 调度器)
+# 默认: 不限制
+# max_parallel: 3
+
+# 验收检查 (wt done 前在 worktree 中执行，任一失败则拒绝标记完成，--force 跳过)
+# 单个任务可在 frontmatter 中用 acceptance: 覆盖
+# checks:
+#   - cargo test
+#   - npm run lint
+
+# 堆叠分支：依赖为 Done (尚未合并) 即可启动，worktree 从依赖的分支创建
+# 上游分支更新后用 wt restack <task> 重新 rebase
+# 单个任务可在 frontmatter 中用 stack: true/false 覆盖
+# stack: true
+
+# 任务编号：默认每个任务在创建时分配固定 ID，新增任务不会改变已有编号
+# 设为 true 恢复旧行为：按任务名字母序编号（新增任务后会重新编号）
+# legacy_index: true
+
 # 初始化脚本 (在每个新 worktree 中执行)
 # 例如安装依赖、设置环境等
 # init_script: |
@@ -77,6 +115,39 @@ tmux_session: {}
 #   rm -rf .next/
 #   rm -rf target/
 
+# 生命周期钩子 (bash 执行，任务上下文通过 WT_TASK、WT_BRANCH、WT_WORKTREE 等环境变量传入)
+# pre_start/on_done/pre_archive 在状态变更前执行，失败则中止 (on_done 可用 --force 跳过)
+# post_start/on_merged 在状态变更后执行，失败只警告
+# 输出追加到 .wt/logs/<task>/hooks.log
+# hooks:
+#   pre_start: ./scripts/check-ticket.sh ${{task}}
+#   on_done: notify-send "wt" "$WT_TASK done"
+
+# wt watch 的通知方式 (都未配置时响终端铃)
+# command 通过 WT_EVENT (finished/idle/waiting)、WT_TASK、WT_MESSAGE 环境变量获取通知内容
+# webhook 用 curl POST JSON
+# notify:
+#   bell: true
+#   notify_send: true
+#   command: ./scripts/notify.sh
+#   webhook: https://hooks.example.com/wt
+#   idle_secs: 300          # 无活动多少秒算空闲 (默认 120)
+
+# wt cost 的模型价格 (美元/百万 token)，键为模型 ID 的子串，覆盖内置的 opus/sonnet/haiku 价格
+# prices:
+#   my-model:
+#     input: 2
+#     output: 8
+#     cache_read: 0.2         # 默认 input 的 1/10
+#     cache_write: 2.5        # 默认 input 的 1.25 倍
+
+# 上下文窗口大小 (token)，键为模型 ID 的子串，覆盖内置表 (默认 200k，带 [1m] 的模型为 1M)
+# context_windows:
+#   sonnet-4-5: 1000000
+
+# TUI 刷新时自动检测已合并到主分支的任务 (同 wt sync)
+# auto_sync: true
+
 # ============================================
 # 日志配置 (wt logs)
 # ============================================
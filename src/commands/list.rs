@@ -5,7 +5,7 @@ use serde::Serialize;
 use crate::constants::TASKS_DIR;
 use crate::display::colored_index;
 use crate::error::Result;
-use crate::models::{Task, TaskStatus, TaskStore};
+use crate::models::{Task, TaskMetadata, TaskStatus, TaskStore};
 
 #[derive(Serialize)]
 struct TaskJson {
@@ -13,6 +13,9 @@ struct TaskJson {
     name: String,
     status: TaskStatus,
     depends: Vec<String>,
+    priority: i32,
+    #[serde(flatten)]
+    metadata: TaskMetadata,
 }
 
 #[derive(Serialize)]
@@ -20,11 +23,19 @@ struct ListOutput {
     tasks: Vec<TaskJson>,
 }
 
-pub fn execute(tree: bool, json: bool) -> Result<()> {
+pub fn execute(tree: bool, json: bool, tag: Option<String>, owner: Option<String>) -> Result<()> {
     let store = TaskStore::load()?;
-    let tasks = store.list();
+    let filtered = tag.is_some() || owner.is_some();
+    let tasks: Vec<&Task> = store
+        .list()
+        .into_iter()
+        .filter(|t| tag.as_deref().is_none_or(|tag| t.metadata().has_tag(tag)))
+        .filter(|t| owner.is_none() || t.metadata().owner == owner)
+        .collect();
 
-    if json {
+    if filtered && tasks.is_empty() && !json {
+        println!("No tasks match the given filters.");
+    } else if json {
         print_json(&tasks, &store);
     } else if tree {
         print_tree(&tasks, &store);
@@ -36,15 +47,17 @@ pub fn execute(tree: bool, json: bool) -> Result<()> {
 }
 
 fn print_json(tasks: &[&Task], store: &TaskStore) {
+    let index_map = store.index_map();
     let output = ListOutput {
         tasks: tasks
             .iter()
-            .enumerate()
-            .map(|(i, t)| TaskJson {
-                index: i + 1,
+            .map(|t| TaskJson {
+                index: index_map[t.name()],
                 name: t.name().to_string(),
                 status: store.get_status(t.name()),
                 depends: t.depends().to_vec(),
+                priority: t.priority(),
+                metadata: t.metadata().clone(),
             })
             .collect(),
     };
@@ -57,12 +70,7 @@ fn print_grouped(tasks: &[&Task], store: &TaskStore) {
         return;
     }
 
-    // Build name -> index mapping (1-based)
-    let index_map: HashMap<&str, usize> = tasks
-        .iter()
-        .enumerate()
-        .map(|(i, t)| (t.name(), i + 1))
-        .collect();
+    let index_map = store.index_map();
 
     // Group tasks by status (with index)
     let mut archived: Vec<(usize, &Task)> = Vec::new();
@@ -170,7 +178,7 @@ fn print_grouped(tasks: &[&Task], store: &TaskStore) {
     }
 }
 
-fn print_task_with_deps_indexed(idx: usize, task: &Task, store: &TaskStore, index_map: &HashMap<&str, usize>) {
+fn print_task_with_deps_indexed(idx: usize, task: &Task, store: &TaskStore, index_map: &HashMap<String, usize>) {
     let status = store.get_status(task.name());
     print!("  {} {} {}", colored_index(idx), status.colored_icon(), task.name());
     if !task.depends().is_empty() {
@@ -226,6 +234,7 @@ fn build_children_map<'a>(tasks: &'a [&Task]) -> HashMap<&'a str, Vec<&'a Task>>
     map
 }
 
+#[allow(clippy::too_many_arguments)]
 fn print_tree_node<'a>(
     task: &'a Task,
     children: &HashMap<&str, Vec<&'a Task>>,
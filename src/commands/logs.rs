@@ -2,7 +2,7 @@
 
 use crate::error::Result;
 use crate::models::{TaskStatus, TaskStore, WtConfig};
-use crate::services::transcript;
+use crate::services::{agent, transcript};
 
 pub fn execute() -> Result<()> {
     let config = WtConfig::load()?;
@@ -28,49 +28,50 @@ pub fn execute() -> Result<()> {
             }
         };
 
-        // Find transcript file - try session_id first, fall back to latest
-        let transcript_path = instance
-            .session_id
-            .as_ref()
-            .and_then(|sid| transcript::transcript_path(&instance.worktree_path, sid))
-            .filter(|p| p.exists())
-            .or_else(|| transcript::find_latest_transcript(&instance.worktree_path));
-
-        let transcript_path = match transcript_path {
-            Some(p) => p,
-            None => {
-                skipped += 1;
-                continue;
-            }
-        };
+        // Find the transcripts of all sessions through the instance's agent backend
+        let transcripts = agent::for_instance(instance).session_transcripts(task.name(), instance);
+        if transcripts.is_empty() {
+            skipped += 1;
+            continue;
+        }
 
-        // Extract session_id from transcript path for log naming
-        let session_id = transcript_path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("unknown");
+        // One log per session
+        let mut total = 0;
+        let mut written = 0;
+        for transcript_path in &transcripts {
+            // Extract session_id from transcript path for log naming
+            let session_id = transcript_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unknown");
 
-        // Generate log file
-        let log_path = transcript::log_path(task.name(), session_id);
+            // Generate log file
+            let log_path = transcript::log_path(task.name(), session_id);
 
-        match transcript::extract_to_log(
-            &transcript_path,
-            &log_path,
-            &config.logs.exclude_types,
-            &config.logs.exclude_fields,
-        ) {
-            Some(count) => {
+            if let Some(count) = transcript::extract_to_log(
+                transcript_path,
+                &log_path,
+                &config.logs.exclude_types,
+                &config.logs.exclude_fields,
+            ) {
                 println!(
                     "  {} -> {} ({} entries)",
                     task.name(),
                     log_path.display(),
                     count
                 );
-                generated += 1;
+                total += count;
+                written += 1;
             }
-            None => {
-                skipped += 1;
+        }
+
+        if written == 0 {
+            skipped += 1;
+        } else {
+            if written > 1 {
+                println!("  {}: {} sessions, {} entries", task.name(), written, total);
             }
+            generated += 1;
         }
     }
 
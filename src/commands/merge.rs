@@ -0,0 +1,153 @@
+//! Merge command - integrate a task branch into the base branch.
+
+use std::path::Path;
+
+use crate::cli::MergeStrategy;
+use crate::error::{Result, WtError};
+use crate::models::{Actor, Instance, Task, TaskStatus, TaskStore, WtConfig};
+use crate::services::{agent, git};
+
+use super::merged::mark_merged;
+
+/// Longest commit subject taken from the task description
+const MAX_SUBJECT_LEN: usize = 72;
+
+pub fn execute(task_ref: String, strategy: MergeStrategy) -> Result<()> {
+    let config = WtConfig::load()?;
+    let mut store = TaskStore::load()?;
+
+    // Resolve task reference (name or index) to actual name
+    let name = store.resolve_task_ref(&task_ref)?;
+
+    if store.is_scratch(&name) {
+        return Err(WtError::InvalidInput(format!(
+            "Scratch environment '{}' cannot be merged. Merge its branch manually, then 'wt archive {}'.",
+            name, name
+        )));
+    }
+
+    let task = store.ensure_exists(&name)?.clone();
+    store.validate_transition(&name, TaskStatus::Merged)?;
+    let instance = store
+        .get_instance(&name)
+        .cloned()
+        .ok_or_else(|| WtError::TaskNotStarted(name.clone()))?;
+
+    // A stacked branch carries its upstreams' commits; those must land first
+    if let Some(parent) = instance.stack_parents.iter().find(|p| {
+        !matches!(store.get_status(p), TaskStatus::Merged | TaskStatus::Archived)
+    }) {
+        return Err(WtError::InvalidInput(format!(
+            "Task '{}' is stacked on '{}', which is not merged yet. Merge it first, then 'wt restack {}'.",
+            name, parent, name
+        )));
+    }
+
+    // All git operations run in the main worktree, where base is checked out
+    let repo_root = git::get_repo_root()?;
+    let base = store.base_branch(&name, config.base_branch.as_deref());
+
+    if git::current_branch(&repo_root).as_deref() != Some(base.as_str()) {
+        return Err(WtError::InvalidInput(format!(
+            "Main worktree '{}' must have '{}' checked out to merge into it",
+            repo_root, base
+        )));
+    }
+    if git::is_dirty(&repo_root) {
+        return Err(WtError::InvalidInput(format!(
+            "Main worktree '{}' has uncommitted changes. Commit or stash them first.",
+            repo_root
+        )));
+    }
+    match git::commits_ahead(&instance.branch, &repo_root) {
+        None => {
+            return Err(WtError::Git(format!("Branch '{}' not found", instance.branch)));
+        }
+        Some(0) => {
+            return Err(WtError::InvalidInput(format!(
+                "Branch '{}' has no commits to merge into {}",
+                instance.branch, base
+            )));
+        }
+        Some(_) => {}
+    }
+
+    // Uncommitted work in the task worktree is not part of the branch
+    if Path::new(&instance.worktree_path).exists() && git::is_dirty(&instance.worktree_path) {
+        eprintln!(
+            "Warning: worktree '{}' has uncommitted changes that will not be merged.",
+            instance.worktree_path
+        );
+    }
+
+    let message = commit_message(&task, &instance);
+    let result = match strategy {
+        MergeStrategy::Merge => git::merge_branch(&instance.branch, &message, &repo_root),
+        MergeStrategy::Squash => git::squash_merge_branch(&instance.branch, &message, &repo_root),
+        MergeStrategy::Rebase => git::cherry_pick_branch(&instance.branch, &repo_root),
+    };
+    result.map_err(|e| WtError::MergeFailed {
+        branch: instance.branch.clone(),
+        base: base.clone(),
+        message: match e {
+            WtError::Git(msg) => msg.trim().to_string(),
+            other => other.to_string(),
+        },
+    })?;
+
+    println!(
+        "Merged '{}' into {} ({}).",
+        instance.branch,
+        base,
+        strategy_name(strategy)
+    );
+
+    mark_merged(&mut store, &name, Actor::Cli, false)?;
+
+    println!("Task '{}' marked as merged.", name);
+    println!("Run 'wt archive {}' to cleanup resources.", name);
+    Ok(())
+}
+
+fn strategy_name(strategy: MergeStrategy) -> &'static str {
+    match strategy {
+        MergeStrategy::Merge => "merge",
+        MergeStrategy::Squash => "squash",
+        MergeStrategy::Rebase => "rebase",
+    }
+}
+
+/// Build a commit message from the task description and the agent's final summary.
+fn commit_message(task: &Task, instance: &Instance) -> String {
+    let title = task
+        .content
+        .lines()
+        .map(|l| l.trim().trim_start_matches('#').trim())
+        .find(|l| !l.is_empty());
+
+    let mut message = match title {
+        Some(title) => {
+            let subject = format!("{}: {}", task.name(), title);
+            if subject.chars().count() > MAX_SUBJECT_LEN {
+                let truncated: String = subject.chars().take(MAX_SUBJECT_LEN - 3).collect();
+                format!("{}...", truncated)
+            } else {
+                subject
+            }
+        }
+        None => format!("Merge task '{}'", task.name()),
+    };
+
+    let backend = agent::for_instance(instance);
+    let summary = backend
+        .find_transcript(task.name(), instance)
+        .and_then(|path| backend.parse_transcript(&path))
+        .and_then(|metrics| metrics.summary);
+    if let Some(summary) = summary.filter(|s| !s.trim().is_empty()) {
+        message.push_str("\n\n");
+        message.push_str(summary.trim());
+    }
+
+    message.push_str(&format!("\n\nTask: {}\nBranch: {}", task.name(), instance.branch));
+    message
+}
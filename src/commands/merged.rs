@@ -1,5 +1,6 @@
 use crate::error::{Result, WtError};
-use crate::models::{TaskStatus, TaskStore};
+use crate::models::{Actor, Hook, TaskStatus, TaskStore};
+use crate::services::hooks;
 use crate::services::tmux;
 
 pub fn execute(task_ref: String, silent: bool) -> Result<()> {
@@ -28,8 +29,23 @@ pub fn execute(task_ref: String, silent: bool) -> Result<()> {
         );
     }
 
+    // silent is only used by the TUI
+    let actor = if silent { Actor::Tui } else { Actor::Cli };
+    mark_merged(&mut store, &name, actor, silent)?;
+
+    if !silent {
+        println!("Task '{}' marked as merged.", name);
+        println!("Worktree and branch preserved for review.");
+        println!("Run 'wt archive {}' to cleanup resources.", name);
+    }
+    Ok(())
+}
+
+/// Close the task's tmux window and transition it to Merged.
+/// Worktree, branch and instance are kept for review and `wt archive`.
+pub fn mark_merged(store: &mut TaskStore, name: &str, actor: Actor, silent: bool) -> Result<()> {
     // Only close tmux window, keep worktree and branch for review
-    if let Some(instance) = store.get_instance(&name) {
+    if let Some(instance) = store.get_instance(name) {
         if let Err(e) = tmux::kill_window(&instance.tmux_session, &instance.tmux_window) {
             if !silent {
                 eprintln!("  Warning: Failed to kill tmux window: {}", e);
@@ -40,14 +56,17 @@ pub fn execute(task_ref: String, silent: bool) -> Result<()> {
         // Keep instance data for archive command
     }
 
-    store.set_status(&name, TaskStatus::Merged);
     // Keep instance (worktree_path, branch) for archive
-    store.save_status()?;
+    store.update_status(|status| {
+        status.transition(name, TaskStatus::Merged, actor);
+        Ok(())
+    })?;
 
-    if !silent {
-        println!("Task '{}' marked as merged.", name);
-        println!("Worktree and branch preserved for review.");
-        println!("Run 'wt archive {}' to cleanup resources.", name);
+    // The branch has already landed, so a failing on_merged hook only warns
+    if let Err(e) = hooks::run_for_task(store, Hook::OnMerged, name) {
+        if !silent {
+            eprintln!("  Warning: {}", e);
+        }
     }
     Ok(())
 }
@@ -1,15 +1,26 @@
 pub mod archive;
 pub mod completions;
+pub mod cost;
 pub mod create;
 pub mod done;
+pub mod history;
 pub mod init;
 pub mod list;
 pub mod logs;
+pub mod merge;
 pub mod merged;
 pub mod new;
 pub mod next;
 pub mod reset;
+pub mod restack;
+pub mod restart;
+pub mod resume;
+pub mod run;
+pub mod send;
+pub mod show;
 pub mod start;
 pub mod status;
+pub mod sync;
 pub mod tail;
 pub mod validate;
+pub mod watch;
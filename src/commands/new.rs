@@ -3,7 +3,7 @@ use std::path::Path;
 
 use crate::constants::{BRANCH_PREFIX, TASKS_DIR};
 use crate::error::{Result, WtError};
-use crate::models::{Instance, TaskStatus, TaskStore, WtConfig};
+use crate::models::{Actor, Instance, TaskStatus, TaskStore, WtConfig};
 use crate::services::{git, tmux, workspace::WorkspaceInitializer};
 
 pub fn execute(name: Option<String>, print_path: bool) -> Result<()> {
@@ -49,7 +49,13 @@ pub fn execute(name: Option<String>, print_path: bool) -> Result<()> {
         .to_string();
 
     // Create worktree and branch
-    git::create_worktree(&branch, &worktree_path)?;
+    let start_point = config.base_branch.clone();
+    let base_commit = git::rev_parse(start_point.as_deref().unwrap_or("HEAD"), &cwd.to_string_lossy());
+    if let (Some(base), None) = (&start_point, &base_commit) {
+        return Err(WtError::Git(format!("Base branch '{}' not found", base)));
+    }
+    let base_branch = git::resolve_base_branch(start_point.as_deref());
+    git::create_worktree(&branch, &worktree_path, start_point.as_deref())?;
 
     // Initialize workspace
     let initializer = WorkspaceInitializer::new(&worktree_path, &cwd);
@@ -79,19 +85,32 @@ pub fn execute(name: Option<String>, print_path: bool) -> Result<()> {
     tmux::create_window(&config.tmux_session, &name, &worktree_path, &cmd)?;
 
     // Update status.json with scratch=true
-    store.set_status(&name, TaskStatus::Running);
-    store.set_scratch(&name, true);
-    store.set_instance(
-        &name,
-        Some(Instance {
-            branch: branch.clone(),
-            worktree_path: worktree_path.clone(),
-            tmux_session: config.tmux_session.clone(),
-            tmux_window: name.clone(),
-            session_id: None, // No Claude session
-        }),
-    );
-    store.save_status()?;
+    store.update_status(|status| {
+        if status.tasks.contains_key(&name) {
+            return Err(WtError::InvalidInput(format!(
+                "Name '{}' already exists in status.json",
+                name
+            )));
+        }
+        status.transition(&name, TaskStatus::Running, Actor::Cli);
+        status.set_scratch(&name, true);
+        status.set_instance(
+            &name,
+            Some(Instance {
+                branch: branch.clone(),
+                worktree_path: worktree_path.clone(),
+                tmux_session: config.tmux_session.clone(),
+                tmux_window: name.clone(),
+                session_id: None, // No Claude session
+                base_commit: base_commit.clone(),
+                base_branch: Some(base_branch.clone()),
+                stack_parents: Vec::new(),
+                agent: None,
+                previous_sessions: Vec::new(),
+            }),
+        );
+        Ok(())
+    })?;
 
     let relative_path = format!("{}/{}", config.worktree_dir, name);
 
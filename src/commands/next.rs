@@ -4,7 +4,8 @@ use serde::Serialize;
 
 use crate::display::colored_index;
 use crate::error::Result;
-use crate::models::{Task, TaskStatus, TaskStore};
+use crate::models::{Task, TaskMetadata, TaskStatus, TaskStore, WtConfig};
+use crate::services::dependency;
 
 #[derive(Serialize)]
 struct NextOutput {
@@ -16,27 +17,27 @@ struct NextOutput {
 struct TaskWithIndex {
     index: usize,
     name: String,
+    priority: i32,
+    #[serde(flatten)]
+    metadata: TaskMetadata,
 }
 
 #[derive(Serialize)]
 struct BlockedTask {
     index: usize,
     name: String,
+    priority: i32,
     waiting_for: Vec<String>,
+    #[serde(flatten)]
+    metadata: TaskMetadata,
 }
 
 pub fn execute(json: bool) -> Result<()> {
     let store = TaskStore::load()?;
-    let tasks = store.list();
+    let index_map = store.index_map();
 
-    // Build name -> index mapping (1-based)
-    let index_map: HashMap<&str, usize> = tasks
-        .iter()
-        .enumerate()
-        .map(|(i, t)| (t.name(), i + 1))
-        .collect();
-
-    let (ready, blocked) = classify_tasks(&tasks, &store);
+    let config_stack = WtConfig::load().map(|c| c.stack).unwrap_or(false);
+    let (ready, blocked) = classify_tasks(&store.list_by_priority(), &store, config_stack);
 
     if json {
         print_json(&ready, &blocked, &index_map);
@@ -50,6 +51,7 @@ pub fn execute(json: bool) -> Result<()> {
 fn classify_tasks<'a>(
     tasks: &[&'a Task],
     store: &TaskStore,
+    config_stack: bool,
 ) -> (Vec<&'a Task>, Vec<(&'a Task, Vec<String>)>) {
     let mut ready = Vec::new();
     let mut blocked = Vec::new();
@@ -60,12 +62,7 @@ fn classify_tasks<'a>(
             continue;
         }
 
-        let unmerged_deps: Vec<String> = task
-            .depends()
-            .iter()
-            .filter(|dep_name| store.get_status(dep_name) != TaskStatus::Merged)
-            .cloned()
-            .collect();
+        let unmerged_deps = dependency::blocking_dependencies(store, task, config_stack);
 
         if unmerged_deps.is_empty() {
             ready.push(*task);
@@ -77,13 +74,15 @@ fn classify_tasks<'a>(
     (ready, blocked)
 }
 
-fn print_json(ready: &[&Task], blocked: &[(&Task, Vec<String>)], index_map: &HashMap<&str, usize>) {
+fn print_json(ready: &[&Task], blocked: &[(&Task, Vec<String>)], index_map: &HashMap<String, usize>) {
     let output = NextOutput {
         ready: ready
             .iter()
             .map(|t| TaskWithIndex {
                 index: index_map[t.name()],
                 name: t.name().to_string(),
+                priority: t.priority(),
+                metadata: t.metadata().clone(),
             })
             .collect(),
         blocked: blocked
@@ -91,14 +90,16 @@ fn print_json(ready: &[&Task], blocked: &[(&Task, Vec<String>)], index_map: &Has
             .map(|(t, deps)| BlockedTask {
                 index: index_map[t.name()],
                 name: t.name().to_string(),
+                priority: t.priority(),
                 waiting_for: deps.clone(),
+                metadata: t.metadata().clone(),
             })
             .collect(),
     };
     println!("{}", serde_json::to_string(&output).unwrap_or_default());
 }
 
-fn print_human(ready: &[&Task], blocked: &[(&Task, Vec<String>)], index_map: &HashMap<&str, usize>) {
+fn print_human(ready: &[&Task], blocked: &[(&Task, Vec<String>)], index_map: &HashMap<String, usize>) {
     if ready.is_empty() && blocked.is_empty() {
         println!("No pending tasks.");
         return;
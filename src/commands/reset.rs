@@ -6,7 +6,8 @@ use chrono::Utc;
 
 use crate::constants::{branch_pattern, BACKUPS_DIR};
 use crate::error::{Result, WtError};
-use crate::models::{TaskStatus, TaskStore, WtConfig};
+use crate::models::{Actor, TaskStatus, TaskStore, WtConfig};
+use crate::services::template::TemplateContext;
 use crate::services::{dependency, git, tmux, workspace::WorkspaceInitializer};
 
 pub fn execute(task_ref: String) -> Result<()> {
@@ -54,7 +55,8 @@ pub fn execute(task_ref: String) -> Result<()> {
                 println!("Running archive script...");
                 let source_dir = Path::new(".");
                 let initializer = WorkspaceInitializer::new(&instance.worktree_path, source_dir);
-                if let Err(e) = initializer.run_init_script(script) {
+                let vars = TemplateContext::for_task(&config, &repo_root, &name, store.get(&name), Some(&instance));
+                if let Err(e) = vars.render(script).and_then(|s| initializer.run_init_script(&s)) {
                     eprintln!("  Warning: Archive script failed: {}", e);
                 }
             }
@@ -100,16 +102,21 @@ pub fn execute(task_ref: String) -> Result<()> {
     }
 
     // Update status
+    store.update_status(|status| {
+        status.transition(&name, TaskStatus::Pending, Actor::Cli);
+        if is_scratch {
+            // Scratch: remove entry from status.json entirely
+            status.tasks.remove(&name);
+        } else {
+            // Normal task: reset to Pending and clear instance
+            status.set_instance(&name, None);
+        }
+        Ok(())
+    })?;
+
     if is_scratch {
-        // Scratch: remove entry from status.json entirely
-        store.status.tasks.remove(&name);
-        store.save_status()?;
         println!("Scratch environment '{}' cleaned up.", name);
     } else {
-        // Normal task: reset to Pending and clear instance
-        store.set_status(&name, TaskStatus::Pending);
-        store.set_instance(&name, None);
-        store.save_status()?;
         println!("Task '{}' reset to pending.", name);
     }
     Ok(())
@@ -0,0 +1,176 @@
+//! Restack command - rebase stacked task branches onto their updated upstreams.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::error::{Result, WtError};
+use crate::models::{TaskStatus, TaskStore, WtConfig};
+use crate::services::git;
+
+pub fn execute(task_ref: String) -> Result<()> {
+    let config = WtConfig::load()?;
+    let mut store = TaskStore::load()?;
+
+    // Resolve task reference (name or index) to actual name
+    let name = store.resolve_task_ref(&task_ref)?;
+    store.ensure_exists(&name)?;
+
+    let status = store.get_status(&name);
+    if status != TaskStatus::Running && status != TaskStatus::Done {
+        return Err(WtError::InvalidInput(format!(
+            "Task '{}' is {}; only running or done tasks can be restacked",
+            name,
+            status.display_name()
+        )));
+    }
+    if store
+        .get_instance(&name)
+        .is_none_or(|i| i.stack_parents.is_empty())
+    {
+        return Err(WtError::InvalidInput(format!(
+            "Task '{}' is not stacked on another task",
+            name
+        )));
+    }
+
+    for task in stack_order(&store, &name) {
+        restack_one(&mut store, &task, config.base_branch.as_deref())?;
+    }
+    Ok(())
+}
+
+/// The task followed by every active task stacked on it (transitively),
+/// ordered so that upstreams are restacked before their dependents.
+fn stack_order(store: &TaskStore, root: &str) -> Vec<String> {
+    let stacked_on = |name: &str| -> Vec<String> {
+        store
+            .get_instance(name)
+            .map(|i| i.stack_parents.clone())
+            .unwrap_or_default()
+    };
+    let active = |name: &str| {
+        matches!(store.get_status(name), TaskStatus::Running | TaskStatus::Done)
+    };
+
+    // Collect the stack above root
+    let mut members: HashSet<String> = HashSet::from([root.to_string()]);
+    let mut frontier = vec![root.to_string()];
+    while let Some(current) = frontier.pop() {
+        for task in store.list() {
+            let name = task.name();
+            if !members.contains(name) && active(name) && stacked_on(name).contains(&current) {
+                members.insert(name.to_string());
+                frontier.push(name.to_string());
+            }
+        }
+    }
+
+    // Emit a member once all its upstreams inside the stack have been emitted
+    let mut order: Vec<String> = Vec::new();
+    while order.len() < members.len() {
+        let next = store
+            .list()
+            .iter()
+            .map(|t| t.name().to_string())
+            .find(|name| {
+                members.contains(name)
+                    && !order.contains(name)
+                    && (name == root
+                        || stacked_on(name)
+                            .iter()
+                            .all(|p| !members.contains(p) || order.contains(p)))
+            });
+        match next {
+            Some(name) => order.push(name),
+            None => break,
+        }
+    }
+    order
+}
+
+/// Rebase one stacked task onto the current tips of its upstreams.
+/// Upstreams that have since been merged are replaced by the base branch.
+fn restack_one(store: &mut TaskStore, name: &str, config_base: Option<&str>) -> Result<()> {
+    let instance = store
+        .get_instance(name)
+        .cloned()
+        .ok_or_else(|| WtError::TaskNotStarted(name.to_string()))?;
+    if !Path::new(&instance.worktree_path).exists() {
+        return Err(WtError::WorktreeNotFound(name.to_string()));
+    }
+    let old_base = instance.base_commit.clone().ok_or_else(|| {
+        WtError::InvalidInput(format!("Task '{}' has no recorded fork point to restack from", name))
+    })?;
+    if git::is_dirty(&instance.worktree_path) {
+        return Err(WtError::InvalidInput(format!(
+            "Worktree '{}' has uncommitted changes. Commit or stash them first.",
+            instance.worktree_path
+        )));
+    }
+
+    // Upstreams still in flight keep their branch; merged ones collapse into base
+    let mut parents = Vec::new();
+    let mut upstreams: Vec<String> = Vec::new();
+    for parent in &instance.stack_parents {
+        let upstream = match (store.get_status(parent), store.get_instance(parent)) {
+            (TaskStatus::Running | TaskStatus::Done, Some(inst)) => {
+                parents.push(parent.clone());
+                inst.branch.clone()
+            }
+            _ => store.base_branch(name, config_base),
+        };
+        if !upstreams.contains(&upstream) {
+            upstreams.push(upstream);
+        }
+    }
+
+    let new_base = stack_base(&upstreams, name, &instance.branch, &instance.worktree_path)?;
+
+    if new_base == old_base {
+        println!("  {}: already up to date", name);
+    } else {
+        git::rebase_onto(&new_base, &old_base, &instance.worktree_path).map_err(|e| {
+            WtError::RestackFailed {
+                task: name.to_string(),
+                message: match e {
+                    WtError::Git(msg) => msg.trim().to_string(),
+                    other => other.to_string(),
+                },
+            }
+        })?;
+        println!("  {}: rebased onto {}", name, upstreams.join(", "));
+    }
+
+    store.update_status(|status| {
+        if let Some(inst) = status.tasks.get_mut(name).and_then(|s| s.instance.as_mut()) {
+            inst.base_commit = Some(new_base.clone());
+            inst.stack_parents = parents;
+        }
+        Ok(())
+    })
+}
+
+/// Commit to rebase onto: the single upstream's tip, or a fresh octopus merge
+/// of all of them (built detached in the task worktree).
+fn stack_base(upstreams: &[String], name: &str, branch: &str, worktree: &str) -> Result<String> {
+    let first = upstreams
+        .first()
+        .ok_or_else(|| WtError::InvalidInput(format!("Task '{}' has no upstream", name)))?;
+    if upstreams.len() == 1 {
+        return git::rev_parse(first, worktree)
+            .ok_or_else(|| WtError::Git(format!("Upstream '{}' not found", first)));
+    }
+
+    git::checkout(&format!("{}^{{commit}}", first), worktree)?;
+    let others: Vec<&str> = upstreams[1..].iter().map(|u| u.as_str()).collect();
+    let merged = git::merge_branches(&others, &format!("Stack {} on {}", name, upstreams.join(", ")), worktree)
+        .and_then(|_| {
+            git::rev_parse("HEAD", worktree)
+                .ok_or_else(|| WtError::Git("Failed to resolve stack base".to_string()))
+        });
+    git::checkout(branch, worktree)?;
+    merged.map_err(|e| WtError::RestackFailed {
+        task: name.to_string(),
+        message: e.to_string(),
+    })
+}
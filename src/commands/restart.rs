@@ -0,0 +1,164 @@
+//! Restart command - fresh agent session on a task's existing worktree and branch.
+
+use std::path::Path;
+
+use uuid::Uuid;
+
+use crate::constants::HANDOFF_MESSAGES;
+use crate::error::{Result, WtError};
+use crate::models::{Actor, AgentKind, Instance, TaskStatus, TaskStore, WtConfig};
+use crate::services::agent::{self, LaunchSpec};
+use crate::services::template::TemplateContext;
+use crate::services::{git, tmux};
+
+use super::start::export_env;
+
+pub fn execute(task_ref: String, keep_history: bool) -> Result<()> {
+    let config = WtConfig::load()?;
+    let mut store = TaskStore::load()?;
+    let name = store.resolve_task_ref(&task_ref)?;
+    store.ensure_exists(&name)?;
+
+    let status = store.get_status(&name);
+    if !matches!(status, TaskStatus::Running | TaskStatus::Done) {
+        return Err(WtError::InvalidInput(format!(
+            "Cannot restart '{}': task is {} (need running or done)",
+            name,
+            status.display_name()
+        )));
+    }
+    let old = store
+        .get_instance(&name)
+        .cloned()
+        .ok_or_else(|| WtError::TaskNotStarted(name.clone()))?;
+    if !Path::new(&old.worktree_path).exists() {
+        return Err(WtError::WorktreeNotFound(name.clone()));
+    }
+
+    let handoff = if keep_history {
+        let text = handoff_prompt(&name, &old);
+        if text.is_none() {
+            eprintln!("  Warning: no messages in the previous transcript, starting without a handoff");
+        }
+        text
+    } else {
+        None
+    };
+
+    let session_id = Uuid::new_v4().to_string();
+    let settings = config.launch_settings(store.get(&name));
+    let instance = restarted_instance(&old, &session_id, settings.agent);
+
+    let repo_root = git::get_repo_root()?;
+    let vars = TemplateContext::for_task(&config, &repo_root, &name, store.get(&name), Some(&instance));
+    let spec = LaunchSpec {
+        task: &name,
+        session_id: &session_id,
+        root: Path::new(&repo_root),
+        vars: &vars,
+        handoff: handoff.as_deref(),
+    };
+    let agent_cmd = agent::backend(settings.agent).launch_command(&settings, &spec)?;
+    let full_cmd = match export_env(&settings.env) {
+        Some(exports) => format!("{} && {}", exports, agent_cmd),
+        None => agent_cmd,
+    };
+
+    if tmux::kill_window_if_exists(&old.tmux_session, &old.tmux_window)? {
+        println!("  Killed tmux window: {}:{}", old.tmux_session, old.tmux_window);
+    }
+    if !tmux::session_exists(&instance.tmux_session) {
+        tmux::create_session(&instance.tmux_session)?;
+    }
+    tmux::create_window(&instance.tmux_session, &instance.tmux_window, &instance.worktree_path, &full_cmd)?;
+
+    store.update_status(|status| {
+        status.transition(&name, TaskStatus::Running, Actor::Cli);
+        status.set_instance(&name, Some(instance.clone()));
+        Ok(())
+    })?;
+
+    println!("Task '{}' restarted with a new agent session.", name);
+    println!("  Session:  {}", session_id);
+    if handoff.is_some() {
+        println!("  Handoff:  last {} message(s) of the previous session", HANDOFF_MESSAGES);
+    }
+    Ok(())
+}
+
+/// New session on the same worktree and branch; the old one joins the chain
+fn restarted_instance(old: &Instance, session_id: &str, agent: AgentKind) -> Instance {
+    let mut instance = Instance {
+        session_id: Some(session_id.to_string()),
+        agent: Some(agent),
+        ..old.clone()
+    };
+    instance.previous_sessions.extend(old.session_id.clone());
+    instance
+}
+
+/// Prompt that hands the previous session's last messages to the new one
+fn handoff_prompt(name: &str, previous: &Instance) -> Option<String> {
+    let backend = agent::for_instance(previous);
+    let path = backend.find_transcript(name, previous)?;
+    handoff_text(&backend.last_messages(&path, HANDOFF_MESSAGES)?)
+}
+
+fn handoff_text(messages: &[String]) -> Option<String> {
+    if messages.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "This task was restarted in a fresh session. The worktree already contains the previous session's changes. \
+         Its last messages were:\n\n{}\n\nContinue from where it left off.",
+        messages.join("\n\n---\n\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(session_id: &str, previous: &[&str]) -> Instance {
+        Instance {
+            branch: "wt/auth".to_string(),
+            worktree_path: "/repo/.wt/worktrees/auth".to_string(),
+            tmux_session: "wt".to_string(),
+            tmux_window: "auth".to_string(),
+            session_id: Some(session_id.to_string()),
+            base_commit: None,
+            base_branch: None,
+            stack_parents: Vec::new(),
+            agent: None,
+            previous_sessions: previous.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_restarted_instance_chains_sessions() {
+        let old = instance("s2", &["s1"]);
+        let new = restarted_instance(&old, "s3", AgentKind::Claude);
+
+        assert_eq!(new.session_id.as_deref(), Some("s3"));
+        assert_eq!(new.previous_sessions, vec!["s1", "s2"]);
+        assert_eq!(new.agent, Some(AgentKind::Claude));
+        assert_eq!(new.worktree_path, old.worktree_path);
+        assert_eq!(new.branch, old.branch);
+    }
+
+    #[test]
+    fn test_restart_of_never_started_session() {
+        let old = Instance { session_id: None, ..instance("s1", &[]) };
+        assert!(restarted_instance(&old, "s2", AgentKind::Claude).previous_sessions.is_empty());
+    }
+
+    #[test]
+    fn test_handoff_text() {
+        assert_eq!(handoff_text(&[]), None);
+
+        let text = handoff_text(&["tests pass".to_string(), "next: docs".to_string()]).unwrap();
+        assert!(text.starts_with("This task was restarted in a fresh session."));
+        assert!(text.contains("Its last messages were:\n\ntests pass\n\n---\n\nnext: docs\n\n"));
+        assert!(text.ends_with("Continue from where it left off."));
+    }
+}
@@ -0,0 +1,83 @@
+//! Resume command - reopen a Done task's agent session in a new tmux window.
+
+use std::path::Path;
+
+use crate::error::{Result, WtError};
+use crate::models::{Actor, TaskStatus, TaskStore, WtConfig};
+use crate::services::command::shell_quote;
+use crate::services::template::TemplateContext;
+use crate::services::{agent, git, tmux};
+
+use super::start::export_env;
+
+pub fn execute(task_ref: String, prompt: Option<String>) -> Result<()> {
+    let mut store = TaskStore::load()?;
+    let name = store.resolve_task_ref(&task_ref)?;
+
+    let target = resume_task(&mut store, &name, prompt.as_deref(), Actor::Cli)?;
+
+    println!("Task '{}' resumed in tmux window {}.", name, target);
+    println!("Attach with: tmux attach -t {}", target);
+    Ok(())
+}
+
+/// Run the agent's resume command for a Done task in a new tmux window of
+/// its worktree and move it back to Running. `prompt` is passed to the agent
+/// as its first message. Returns the `session:window` target.
+pub fn resume_task(store: &mut TaskStore, name: &str, prompt: Option<&str>, actor: Actor) -> Result<String> {
+    store.ensure_exists(name)?;
+    let status = store.get_status(name);
+    if status != TaskStatus::Done {
+        return Err(WtError::InvalidInput(format!(
+            "Cannot resume '{}': task is {} (need done)",
+            name,
+            status.display_name()
+        )));
+    }
+
+    let instance = store
+        .get_instance(name)
+        .cloned()
+        .ok_or_else(|| WtError::TaskNotStarted(name.to_string()))?;
+    if !Path::new(&instance.worktree_path).exists() {
+        return Err(WtError::WorktreeNotFound(name.to_string()));
+    }
+    let target = format!("{}:{}", instance.tmux_session, instance.tmux_window);
+    if tmux::window_exists(&instance.tmux_session, &instance.tmux_window) {
+        return Err(WtError::Tmux(format!("window {} of '{}' is still open", target, name)));
+    }
+
+    let config = WtConfig::load()?;
+    let settings = config.launch_settings(store.get(name));
+    let repo_root = git::get_repo_root()?;
+    let vars = TemplateContext::for_task(&config, &repo_root, name, store.get(name), Some(&instance));
+    let command = agent::for_instance(&instance)
+        .resume_command(&settings, &vars, &instance)?
+        .ok_or_else(|| {
+            WtError::InvalidInput(format!(
+                "Cannot resume '{}': the {} agent has no session to resume",
+                name,
+                instance.agent_kind().display_name()
+            ))
+        })?;
+    let command = match prompt {
+        Some(prompt) => format!("{} {}", command, shell_quote(prompt)),
+        None => command,
+    };
+    let command = match export_env(&settings.env) {
+        Some(exports) => format!("{} && {}", exports, command),
+        None => command,
+    };
+
+    if !tmux::session_exists(&instance.tmux_session) {
+        tmux::create_session(&instance.tmux_session)?;
+    }
+    tmux::create_window(&instance.tmux_session, &instance.tmux_window, &instance.worktree_path, &command)?;
+
+    store.update_status(|status| {
+        status.validate_transition(name, TaskStatus::Running)?;
+        status.transition(name, TaskStatus::Running, actor);
+        Ok(())
+    })?;
+    Ok(target)
+}
@@ -0,0 +1,96 @@
+//! Run command - scheduler that keeps up to max_parallel tasks running.
+
+use std::thread;
+use std::time::Duration;
+
+use crate::display::log;
+use crate::error::{Result, WtError};
+use crate::models::{Actor, TaskStatus, TaskStore, WtConfig};
+use crate::services::dependency;
+
+use super::done::mark_done;
+use super::start::execute_single;
+use super::sync::sync_merged;
+
+pub fn execute(interval: u64) -> Result<()> {
+    let config = WtConfig::load()?;
+
+    match config.max_parallel.filter(|m| *m > 0) {
+        Some(max) => println!("Scheduling tasks (max_parallel: {}). Press Ctrl+C to stop.", max),
+        None => println!("Scheduling tasks (no max_parallel limit). Press Ctrl+C to stop."),
+    }
+
+    let mut waiting_on: Vec<String> = Vec::new();
+
+    loop {
+        let mut store = TaskStore::load()?;
+        let task_names: Vec<String> = store.list().iter().map(|t| t.name().to_string()).collect();
+
+        // Completion: tmux window exited (and acceptance checks pass), or the
+        // branch landed in the base branch. A task that fails its checks
+        // stops the scheduler, so its dependents don't start.
+        for name in &task_names {
+            if !store.window_exited(name) {
+                continue;
+            }
+            mark_done(&mut store, name, false, Actor::Auto, true)
+                .map_err(|e| WtError::Scheduler(format!("'{}' failed: {}", name, e)))?;
+            log(&format!("'{}' finished (done)", name));
+        }
+        for (name, kind) in sync_merged(&mut store, config.base_branch.as_deref(), true)? {
+            log(&format!("'{}' merged ({})", name, kind.display_name()));
+        }
+
+        // Fill free slots from the same readiness logic as `wt next`
+        let running = store.count_status(TaskStatus::Running);
+        let ready = dependency::ready_tasks(&store, config.stack);
+        for name in ready.into_iter().take(config.free_slots(running)) {
+            log(&format!("Starting '{}'", name));
+            execute_single(name.clone()).map_err(|e| {
+                WtError::Scheduler(format!("failed to start '{}': {}", name, e))
+            })?;
+        }
+
+        let store = TaskStore::load()?;
+        let pending = store.count_status(TaskStatus::Pending);
+        let running = store.count_status(TaskStatus::Running);
+
+        if pending == 0 && running == 0 {
+            log("All tasks finished.");
+            return Ok(());
+        }
+
+        if running == 0 && dependency::ready_tasks(&store, config.stack).is_empty() {
+            let done: Vec<String> = task_names
+                .iter()
+                .filter(|n| store.get_status(n) == TaskStatus::Done)
+                .cloned()
+                .collect();
+
+            // Nothing left that could unblock the remaining tasks
+            if done.is_empty() {
+                let blocked: Vec<String> = store
+                    .list()
+                    .iter()
+                    .filter(|t| store.get_status(t.name()) == TaskStatus::Pending)
+                    .map(|t| {
+                        let deps = dependency::blocking_dependencies(&store, t, config.stack);
+                        format!("{} (waiting for {})", t.name(), deps.join(", "))
+                    })
+                    .collect();
+                return Err(WtError::Scheduler(format!(
+                    "{} pending task(s) can never start: {}",
+                    pending,
+                    blocked.join("; ")
+                )));
+            }
+
+            if done != waiting_on {
+                log(&format!("Waiting for done task(s) to be merged: {}", done.join(", ")));
+                waiting_on = done;
+            }
+        }
+
+        thread::sleep(Duration::from_secs(interval));
+    }
+}
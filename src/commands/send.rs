@@ -0,0 +1,55 @@
+//! Send command - deliver a follow-up prompt to a running agent.
+
+use crate::error::{Result, WtError};
+use crate::models::{Actor, SentMessage, TaskStatus, TaskStore, WtConfig};
+use crate::services::{agent, tmux};
+
+pub fn execute(task_ref: String, message: String) -> Result<()> {
+    let mut store = TaskStore::load()?;
+    let name = store.resolve_task_ref(&task_ref)?;
+
+    let sent = send_message(&mut store, &name, &message, Actor::Cli)?;
+
+    println!("Sent to '{}' ({}).", name, sent.format.display_name());
+    Ok(())
+}
+
+/// Type a message into a Running task's tmux window, framed for the agent's
+/// input format, and record it in status.json
+pub fn send_message(store: &mut TaskStore, name: &str, text: &str, actor: Actor) -> Result<SentMessage> {
+    store.ensure_exists(name)?;
+    if text.trim().is_empty() {
+        return Err(WtError::InvalidInput("Message is empty".to_string()));
+    }
+
+    let status = store.get_status(name);
+    if status != TaskStatus::Running {
+        return Err(WtError::InvalidInput(format!(
+            "Cannot send to '{}': task is {} (need running)",
+            name,
+            status.display_name()
+        )));
+    }
+    let instance = store
+        .get_instance(name)
+        .cloned()
+        .ok_or_else(|| WtError::TaskNotStarted(name.to_string()))?;
+    if !tmux::window_exists(&instance.tmux_session, &instance.tmux_window) {
+        return Err(WtError::Tmux(format!(
+            "window {}:{} of '{}' is closed",
+            instance.tmux_session, instance.tmux_window, name
+        )));
+    }
+
+    let config = WtConfig::load()?;
+    let settings = config.launch_settings(store.get(name));
+    let (format, line) = agent::for_instance(&instance).frame_message(&settings, text);
+    tmux::send_text(&instance.tmux_session, &instance.tmux_window, &line)?;
+
+    let sent = SentMessage::new(actor, format, text);
+    store.update_status(|status| {
+        status.record_message(name, sent.clone());
+        Ok(())
+    })?;
+    Ok(sent)
+}
@@ -0,0 +1,101 @@
+//! Show command - a task's definition and the settings `wt start` will launch it with.
+
+use serde::Serialize;
+
+use crate::display::{colored_index, GRAY, RESET};
+use crate::error::Result;
+use crate::models::{LaunchSettings, TaskMetadata, TaskStatus, TaskStore, WtConfig};
+
+#[derive(Serialize)]
+struct ShowOutput<'a> {
+    index: usize,
+    name: &'a str,
+    status: TaskStatus,
+    file: &'a str,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    depends: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base: Option<&'a str>,
+    priority: i32,
+    #[serde(flatten)]
+    metadata: &'a TaskMetadata,
+    launch: LaunchSettings,
+    /// Env keys set by the task itself
+    #[serde(skip)]
+    task_env: Vec<&'a str>,
+}
+
+pub fn execute(task_ref: String, json: bool) -> Result<()> {
+    let config = WtConfig::load()?;
+    let store = TaskStore::load()?;
+
+    // Resolve task reference (name or index) to actual name
+    let name = store.resolve_task_ref(&task_ref)?;
+    store.ensure_exists(&name)?;
+    let task = store.get(&name).expect("task exists");
+
+    let output = ShowOutput {
+        index: store.index_map()[&name],
+        name: task.name(),
+        status: store.get_status(&name),
+        file: &task.file_path,
+        depends: task.depends(),
+        base: task.base(),
+        priority: task.priority(),
+        metadata: task.metadata(),
+        launch: config.launch_settings(Some(task)),
+        task_env: task.overrides().env.keys().map(|k| k.as_str()).collect(),
+    };
+
+    if json {
+        println!("{}", serde_json::to_string(&output)?);
+    } else {
+        print_human(&output);
+    }
+
+    Ok(())
+}
+
+fn print_human(output: &ShowOutput) {
+    println!("{} {} {}", colored_index(output.index), output.status.colored_icon(), output.name);
+    println!("  Status:     {}", output.status.display_name());
+    println!("  File:       {}", output.file);
+    if !output.depends.is_empty() {
+        println!("  Depends:    {}", output.depends.join(", "));
+    }
+    if let Some(base) = output.base {
+        println!("  Base:       {}", base);
+    }
+    if output.priority != 0 {
+        println!("  Priority:   {}", output.priority);
+    }
+
+    let launch = &output.launch;
+    let source = |from_task: bool| {
+        if from_task {
+            format!(" {}(task){}", GRAY, RESET)
+        } else {
+            String::new()
+        }
+    };
+    let overridden = |field: &str| source(launch.overridden.contains(&field));
+
+    println!();
+    println!("Launch:");
+    println!("  Agent:      {}{}", launch.agent.display_name(), overridden("agent"));
+    println!(
+        "  Command:    {}{}",
+        launch.command.as_deref().unwrap_or("(not set)"),
+        overridden("command")
+    );
+    println!("  Start args: {}{}", launch.start_args, overridden("start_args"));
+    if let Some(model) = &launch.model {
+        println!("  Model:      {}{}", model, overridden("model"));
+    }
+    if let Some(script) = &launch.init_script {
+        println!("  Init:       {}{}", script.trim(), overridden("init_script"));
+    }
+    for (key, value) in &launch.env {
+        println!("  Env:        {}={}{}", key, value, source(output.task_env.contains(&key.as_str())));
+    }
+}
@@ -1,10 +1,16 @@
+use std::collections::BTreeMap;
 use std::env;
+use std::path::Path;
 
 use uuid::Uuid;
 
 use crate::constants::branch_name;
 use crate::error::{Result, WtError};
-use crate::models::{Instance, TaskStatus, TaskStore, WtConfig};
+use crate::models::{Actor, AgentKind, Hook, Instance, TaskStatus, TaskStore, WtConfig};
+use crate::services::agent::{self, LaunchSpec};
+use crate::services::command::shell_quote;
+use crate::services::hooks;
+use crate::services::template::TemplateContext;
 use crate::services::{dependency, git, tmux, workspace::WorkspaceInitializer};
 
 pub fn execute(task_ref: Option<String>, all: bool) -> Result<()> {
@@ -28,25 +34,12 @@ pub fn execute(task_ref: Option<String>, all: bool) -> Result<()> {
     }
 }
 
-/// Start all tasks that are ready (pending with all dependencies merged)
+/// Start all tasks that are ready (pending with all dependencies merged, or done when stacking)
 fn execute_all() -> Result<()> {
+    let config = WtConfig::load()?;
     let store = TaskStore::load()?;
-    let tasks = store.list();
 
-    // Find all ready tasks (pending with all deps merged/archived)
-    let ready_tasks: Vec<String> = tasks
-        .iter()
-        .filter(|task| {
-            if store.get_status(task.name()) != TaskStatus::Pending {
-                return false;
-            }
-            task.depends().iter().all(|dep| {
-                let status = store.get_status(dep);
-                status == TaskStatus::Merged || status == TaskStatus::Archived
-            })
-        })
-        .map(|task| task.name().to_string())
-        .collect();
+    let ready_tasks = dependency::ready_tasks(&store, config.stack);
 
     if ready_tasks.is_empty() {
         println!("No tasks ready to start.");
@@ -54,6 +47,22 @@ fn execute_all() -> Result<()> {
         return Ok(());
     }
 
+    // Respect max_parallel: only fill the free slots
+    let running = store.count_status(TaskStatus::Running);
+    let slots = config.free_slots(running);
+    if slots == 0 {
+        println!(
+            "{} task(s) ready, but {} already running (max_parallel: {}).",
+            ready_tasks.len(),
+            running,
+            config.max_parallel.unwrap_or_default()
+        );
+        println!("Use 'wt run' to start them as slots free up.");
+        return Ok(());
+    }
+    let deferred = ready_tasks.len().saturating_sub(slots);
+    let ready_tasks: Vec<String> = ready_tasks.into_iter().take(slots).collect();
+
     println!("Starting {} task(s)...\n", ready_tasks.len());
 
     let mut started = 0;
@@ -73,6 +82,12 @@ fn execute_all() -> Result<()> {
     }
 
     println!("\nSummary: {} started, {} failed", started, failed);
+    if deferred > 0 {
+        println!(
+            "{} more task(s) ready but deferred by max_parallel. Use 'wt run' to schedule them.",
+            deferred
+        );
+    }
 
     if started > 0 {
         println!("\nUse 'wt status' to monitor tasks.");
@@ -82,7 +97,7 @@ fn execute_all() -> Result<()> {
 }
 
 /// Start a single task
-fn execute_single(name: String) -> Result<()> {
+pub fn execute_single(name: String) -> Result<()> {
     let config = WtConfig::load()?;
     let mut store = TaskStore::load()?;
 
@@ -94,7 +109,22 @@ fn execute_single(name: String) -> Result<()> {
         return Err(WtError::AlreadyRunning(name.clone()));
     }
 
-    dependency::check_dependencies_merged(&store, &name)?;
+    dependency::check_dependencies_merged(&store, &name, config.stack)?;
+
+    // Stacked tasks start on top of their Done dependencies' branches
+    let stack_parents = dependency::stack_parents(&store, &name, config.stack);
+    let parent_branches = stack_parents
+        .iter()
+        .map(|parent| {
+            store
+                .get_instance(parent)
+                .map(|i| i.branch.clone())
+                .ok_or_else(|| WtError::InvalidInput(format!(
+                    "Dependency '{}' is done but has no branch to stack on",
+                    parent
+                )))
+        })
+        .collect::<Result<Vec<String>>>()?;
 
     // Generate session ID for branch naming and Claude Code tracking
     let session_id = Uuid::new_v4().to_string();
@@ -111,7 +141,63 @@ fn execute_single(name: String) -> Result<()> {
         return Err(WtError::BranchExists(branch));
     }
 
-    git::create_worktree(&branch, &worktree_path)?;
+    // Fork from the task's or configured base branch, or the current HEAD if neither is set
+    let explicit_base = store
+        .get(&name)
+        .and_then(|t| t.base())
+        .or(config.base_branch.as_deref())
+        .map(|b| b.to_string());
+    let base_branch = git::resolve_base_branch(explicit_base.as_deref());
+    let start_point = parent_branches.first().cloned().or(explicit_base);
+    let cwd_str = cwd.to_string_lossy().to_string();
+    if let Some(ref start) = start_point {
+        if git::rev_parse(start, &cwd_str).is_none() {
+            return Err(WtError::Git(format!("Base branch '{}' not found", start)));
+        }
+    }
+
+    // Build agent command from the config with the task's overrides applied
+    let settings = config.launch_settings(store.get(&name));
+    let agent = settings.agent;
+    let mut vars = TemplateContext::new(&name, &cwd_str)
+        .set("branch", branch.clone())
+        .set("worktree", worktree_path.clone())
+        .set("session_id", session_id.clone())
+        .set("base_branch", base_branch.clone())
+        .with_env(&settings.env);
+    if let Some(task) = store.get(&name) {
+        vars = vars.with_task(task);
+    }
+    let spec = LaunchSpec {
+        task: &name,
+        session_id: &session_id,
+        root: &cwd,
+        vars: &vars,
+        handoff: None,
+    };
+    let agent_cmd = agent::backend(agent).launch_command(&settings, &spec)?;
+    let init_script = settings
+        .init_script
+        .as_deref()
+        .map(|script| vars.render(script))
+        .transpose()?;
+
+    // pre_start can veto the start; there is no worktree yet, so it runs in the repo
+    hooks::run(&config, Hook::PreStart, &vars, &cwd)?;
+
+    git::create_worktree(&branch, &worktree_path, start_point.as_deref())?;
+
+    // Several stacked dependencies: merge the remaining ones into the new branch
+    if parent_branches.len() > 1 {
+        let others: Vec<&str> = parent_branches[1..].iter().map(|b| b.as_str()).collect();
+        let message = format!("Stack {} on {}", name, stack_parents.join(", "));
+        if let Err(e) = git::merge_branches(&others, &message, &worktree_path) {
+            let _ = git::remove_worktree(&worktree_path);
+            let _ = git::delete_branch_in(&branch, &cwd_str);
+            return Err(e);
+        }
+    }
+    let base_commit = git::rev_parse("HEAD", &worktree_path);
 
     // Initialize workspace
     let initializer = WorkspaceInitializer::new(&worktree_path, &cwd);
@@ -126,50 +212,93 @@ fn execute_single(name: String) -> Result<()> {
         tmux::create_session(&config.tmux_session)?;
     }
 
-    // Build agent command: claude_command + start_args
-    let expanded_args = config
-        .start_args
-        .replace("${task}", &name)
-        .replace("${branch}", &branch)
-        .replace("${worktree}", &worktree_path);
-
-    // Add --session-id to the command for session tracking
-    let agent_cmd = format!(
-        "{} {} --session-id {}",
-        config.claude_command, expanded_args, session_id
-    );
-
     // Build full command: init_script && agent_cmd (if init_script configured)
-    let full_cmd = match &config.init_script {
+    let full_cmd = match &init_script {
         Some(script) => format!("({}) && {}", script, agent_cmd),
         None => agent_cmd,
     };
+    let full_cmd = match export_env(&settings.env) {
+        Some(exports) => format!("{} && {}", exports, full_cmd),
+        None => full_cmd,
+    };
 
     tmux::create_window(&config.tmux_session, &name, &worktree_path, &full_cmd)?;
 
-    if config.init_script.is_some() {
+    if init_script.is_some() {
         println!("  Init script will run in tmux window");
     }
 
     // Update status in StatusStore
-    store.set_status(&name, TaskStatus::Running);
-    store.set_instance(
-        &name,
-        Some(Instance {
-            branch: branch.clone(),
-            worktree_path: worktree_path.clone(),
-            tmux_session: config.tmux_session.clone(),
-            tmux_window: name.clone(),
-            session_id: Some(session_id),
-        }),
-    );
-    store.save_status()?;
+    store.update_status(|status| {
+        if status.get_status(&name) == TaskStatus::Running {
+            return Err(WtError::AlreadyRunning(name.clone()));
+        }
+        status.transition(&name, TaskStatus::Running, Actor::Cli);
+        status.set_instance(
+            &name,
+            Some(Instance {
+                branch: branch.clone(),
+                worktree_path: worktree_path.clone(),
+                tmux_session: config.tmux_session.clone(),
+                tmux_window: name.clone(),
+                session_id: Some(session_id),
+                base_commit: base_commit.clone(),
+                base_branch: Some(base_branch.clone()),
+                stack_parents: stack_parents.clone(),
+                agent: Some(agent),
+                previous_sessions: Vec::new(),
+            }),
+        );
+        Ok(())
+    })?;
+
+    if let Err(e) = hooks::run(&config, Hook::PostStart, &vars, Path::new(&worktree_path)) {
+        eprintln!("  Warning: {}", e);
+    }
 
     let relative_path = format!("{}/{}", config.worktree_dir, name);
 
     println!("OK");
     println!("  Worktree: {}", relative_path);
     println!("  Branch:   {}", branch);
+    println!("  Base:     {}", base_branch);
+    if !stack_parents.is_empty() {
+        println!("  Stacked:  on {}", stack_parents.join(", "));
+    }
+    if agent != AgentKind::Claude {
+        println!("  Agent:    {}", agent.display_name());
+    }
 
     Ok(())
 }
+
+/// `export` statement for the window's environment variables, values single-quoted
+pub fn export_env(env: &BTreeMap<String, String>) -> Option<String> {
+    if env.is_empty() {
+        return None;
+    }
+    let assignments: Vec<String> = env
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, shell_quote(value)))
+        .collect();
+    Some(format!("export {}", assignments.join(" ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_env() {
+        assert_eq!(export_env(&BTreeMap::new()), None);
+
+        let env = BTreeMap::from([
+            ("PORT".to_string(), "3000".to_string()),
+            ("GREETING".to_string(), "it's here".to_string()),
+        ]);
+        assert_eq!(
+            export_env(&env).unwrap(),
+            r"export GREETING='it'\''s here' PORT='3000'"
+        );
+    }
+}
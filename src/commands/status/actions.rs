@@ -25,6 +25,7 @@ fn success_response(action: &str, task_name: &str, before: TaskStatus, after: Ta
         available_actions: None,
         unavailable_actions: None,
         command: None,
+        sent: None,
     }
 }
 
@@ -44,6 +45,7 @@ fn error_response(action: &str, error: &str, task_name: &str, status: Option<Tas
         available_actions: None,
         unavailable_actions: None,
         command: None,
+        sent: None,
     }
 }
 
@@ -57,6 +59,7 @@ fn error_response_no_task(action: &str, error: &str) -> ActionResponse {
         available_actions: None,
         unavailable_actions: None,
         command: None,
+        sent: None,
     }
 }
 
@@ -79,6 +82,7 @@ fn task_not_found_response(action: &str, task_name: &str) -> ActionResponse {
         available_actions: None,
         unavailable_actions: None,
         command: None,
+        sent: None,
     }
 }
 
@@ -96,7 +100,7 @@ fn respond_and_exit(response: ActionResponse) -> ! {
 // ============================================================================
 
 /// Execute an action via the --action API
-pub fn execute_action(action: &str, task_ref: Option<String>) {
+pub fn execute_action(action: &str, task_ref: Option<String>, force: bool, message: Option<String>) {
     let task_ref = match task_ref {
         Some(r) => r,
         None => respond_and_exit(error_response_no_task(action, "--task is required with --action")),
@@ -127,11 +131,12 @@ pub fn execute_action(action: &str, task_ref: Option<String>) {
 
     let response = match action {
         "list" => handle_list_action(&app, &task_name),
-        "done" => handle_done_action(&mut app, &task_name),
+        "done" => handle_done_action(&mut app, &task_name, force),
         "merged" => handle_merged_action(&mut app, &task_name),
         "archive" => handle_archive_action(&mut app, &task_name),
         "enter" => handle_enter_action(&app, &task_name),
         "tail" => handle_tail_action(&task_name),
+        "send" => handle_send_action(&mut app, &task_name, message.as_deref()),
         _ => ActionResponse {
             action: action.to_string(),
             success: false,
@@ -146,6 +151,7 @@ pub fn execute_action(action: &str, task_ref: Option<String>) {
             available_actions: None,
             unavailable_actions: None,
             command: None,
+            sent: None,
         },
     };
 
@@ -180,6 +186,16 @@ fn handle_list_action(app: &App, task_name: &str) -> ActionResponse {
         );
     }
 
+    // send check
+    if app.can_send() {
+        available.push("send".to_string());
+    } else {
+        unavailable.insert(
+            "send".to_string(),
+            format!("task is {} (need running with a live tmux window)", task.status.display_name()),
+        );
+    }
+
     // done check
     if app.can_mark_done() {
         available.push("done".to_string());
@@ -224,10 +240,11 @@ fn handle_list_action(app: &App, task_name: &str) -> ActionResponse {
         available_actions: Some(available),
         unavailable_actions: Some(unavailable),
         command: None,
+        sent: None,
     }
 }
 
-fn handle_done_action(app: &mut App, task_name: &str) -> ActionResponse {
+fn handle_done_action(app: &mut App, task_name: &str, force: bool) -> ActionResponse {
     let task = app.selected_task().unwrap();
     let status_before = task.status.clone();
     let tmux_alive = task.tmux_alive;
@@ -236,7 +253,7 @@ fn handle_done_action(app: &mut App, task_name: &str) -> ActionResponse {
         return error_response("done", "Cannot mark as done: task is not running", task_name, Some(status_before), Some(tmux_alive));
     }
 
-    if let Err(e) = app.mark_done() {
+    if let Err(e) = app.mark_done(force) {
         return error_response("done", &format!("Failed to mark as done: {}", e), task_name, Some(status_before), None);
     }
 
@@ -309,11 +326,12 @@ fn handle_enter_action(app: &App, task_name: &str) -> ActionResponse {
                 window: Some(window),
                 ..Default::default()
             }),
+            sent: None,
         },
         Some(TuiAction::ShowResume {
             worktree,
             session_id,
-            claude_command,
+            command,
         }) => ActionResponse {
             action: "enter".to_string(),
             success: true,
@@ -331,12 +349,32 @@ fn handle_enter_action(app: &App, task_name: &str) -> ActionResponse {
                 cmd_type: "resume".to_string(),
                 worktree: Some(worktree.clone()),
                 session_id: Some(session_id.clone()),
-                shell_command: Some(format!(
-                    "cd {} && {} -r {}",
-                    worktree, claude_command, session_id
-                )),
+                shell_command: Some(format!("cd {} && {}", worktree, command)),
                 ..Default::default()
             }),
+            sent: None,
+        },
+        Some(TuiAction::Resume { name }) => ActionResponse {
+            action: "enter".to_string(),
+            success: true,
+            error: None,
+            task: Some(TaskInfo {
+                name: task_name.to_string(),
+                status: None,
+                status_before: None,
+                status_after: None,
+                tmux_alive: None,
+            }),
+            available_actions: None,
+            unavailable_actions: None,
+            command: Some(CommandInfo {
+                cmd_type: "resume".to_string(),
+                worktree: task.worktree_path.clone(),
+                session_id: task.session_id.clone(),
+                shell_command: Some(format!("wt resume {}", name)),
+                ..Default::default()
+            }),
+            sent: None,
         },
         _ => ActionResponse {
             action: "enter".to_string(),
@@ -352,13 +390,53 @@ fn handle_enter_action(app: &App, task_name: &str) -> ActionResponse {
             available_actions: None,
             unavailable_actions: None,
             command: None,
+            sent: None,
+        },
+    }
+}
+
+fn handle_send_action(app: &mut App, task_name: &str, message: Option<&str>) -> ActionResponse {
+    let task = app.selected_task().unwrap();
+    let status = task.status.clone();
+    let tmux_alive = task.tmux_alive;
+
+    let Some(message) = message else {
+        return error_response("send", "--message is required with --action send", task_name, Some(status), None);
+    };
+    if !app.can_send() {
+        return error_response(
+            "send",
+            &format!("Cannot send: task is {} (need running with a live tmux window)", status.display_name()),
+            task_name,
+            Some(status),
+            Some(tmux_alive),
+        );
+    }
+
+    match app.send_message(message) {
+        Ok(sent) => ActionResponse {
+            action: "send".to_string(),
+            success: true,
+            error: None,
+            task: Some(TaskInfo {
+                name: task_name.to_string(),
+                status: Some(status),
+                status_before: None,
+                status_after: None,
+                tmux_alive: Some(tmux_alive),
+            }),
+            available_actions: None,
+            unavailable_actions: None,
+            command: None,
+            sent: Some(sent),
         },
+        Err(e) => error_response("send", &format!("Failed to send: {}", e), task_name, Some(status), None),
     }
 }
 
 fn handle_tail_action(task_name: &str) -> ActionResponse {
     // Execute tail command directly - it outputs JSON
-    match crate::commands::tail::execute(task_name.to_string(), 1) {
+    match crate::commands::tail::execute(task_name.to_string(), Some(1), Vec::new(), None, None) {
         Ok(_) => {
             // tail::execute already printed output, exit without additional JSON
             std::process::exit(0);
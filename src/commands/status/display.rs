@@ -1,16 +1,21 @@
-use std::collections::HashMap;
 use std::time::SystemTime;
 
 use crate::constants::IDLE_THRESHOLD_SECS;
 use crate::display::{colored_index, format_duration, running_icon, RESET};
 use crate::error::Result;
-use crate::models::{TaskStatus, TaskStore};
-use crate::services::{git, tmux, transcript};
+use crate::models::{TaskStatus, TaskStore, WtConfig};
+use crate::services::cost::{self, TaskUsage};
+use crate::services::{agent, git, tmux, transcript};
 
 use super::types::{StatusOutput, StatusSummary, TaskMetrics};
 
 /// Display status in JSON or human-readable format
 pub fn display_status(json: bool) -> Result<()> {
+    let config = WtConfig::load().ok();
+    let config_base = config.as_ref().and_then(|c| c.base_branch.clone());
+    let context_windows = config.as_ref().map(|c| c.context_windows.clone()).unwrap_or_default();
+    let checks = config.as_ref().map(|c| c.checks.clone()).unwrap_or_default();
+    let prices = config.map(|c| c.prices).unwrap_or_default();
     let mut store = TaskStore::load()?;
 
     let mut metrics_list = Vec::new();
@@ -18,23 +23,16 @@ pub fn display_status(json: bool) -> Result<()> {
     let mut done_count = 0;
     let mut total_additions = 0;
     let mut total_deletions = 0;
-    let mut status_changed = false;
+    let mut total_usage = TaskUsage::default();
 
     // Collect task names first to avoid borrow conflict
     let task_names: Vec<String> = store.list().iter().map(|t| t.name().to_string()).collect();
 
-    // Build name -> index mapping (1-based)
-    let index_map: HashMap<String, usize> = task_names
-        .iter()
-        .enumerate()
-        .map(|(i, name)| (name.clone(), i + 1))
-        .collect();
+    let index_map = store.index_map();
 
     for task_name in &task_names {
         // Auto-mark as Done if Running but tmux window is closed
-        if store.auto_mark_done_if_needed(task_name)? {
-            status_changed = true;
-        }
+        store.auto_mark_done_if_needed(task_name, &checks)?;
 
         let status = store.get_status(task_name);
 
@@ -64,14 +62,26 @@ pub fn display_status(json: bool) -> Result<()> {
         // Get session_id and transcript path info
         let session_id = instance.and_then(|i| i.session_id.clone());
 
-        // Find transcript file for this instance
-        let transcript_path_opt = instance.and_then(transcript::find_transcript_for_instance);
-        let transcript_exists = transcript_path_opt.as_ref().map(|p| p.exists());
-
-        // Parse transcript for metrics
-        let transcript_metrics = transcript_path_opt
-            .as_ref()
-            .and_then(|path| transcript::parse_transcript(path));
+        // Find the transcripts of every session of this instance
+        let backend = instance.map(agent::for_instance);
+        let transcripts = instance
+            .zip(backend)
+            .map(|(inst, backend)| backend.session_transcripts(task_name, inst))
+            .unwrap_or_default();
+        let transcript_exists = (!transcripts.is_empty()).then_some(true);
+        let sessions: Vec<String> = transcripts
+            .iter()
+            .filter_map(|p| p.file_stem())
+            .map(|s| s.to_string_lossy().into_owned())
+            .collect();
+
+        // Parse transcripts for metrics, summed over the sessions
+        let mut transcript_metrics = backend.and_then(|backend| {
+            transcript::aggregate(transcripts.iter().filter_map(|p| backend.parse_transcript(p)).collect())
+        });
+        if let Some(m) = transcript_metrics.as_mut().filter(|m| m.model.is_some()) {
+            m.resolve_context_window(&context_windows);
+        }
 
         // Duration from transcript timestamps
         let (duration_secs, duration_human) = transcript_metrics
@@ -83,15 +93,25 @@ pub fn display_status(json: bool) -> Result<()> {
         // Context percent and current tool from transcript
         let context_percent = transcript_metrics.as_ref().map(|m| m.context_percent());
         let current_tool = transcript_metrics.as_ref().and_then(|m| m.current_tool.clone());
+        let model = transcript_metrics.as_ref().and_then(|m| m.model.clone());
 
-        // Get git metrics (additions, deletions, commits, conflict)
-        let git_metrics = worktree_path.and_then(git::get_worktree_metrics);
+        // Get git metrics (additions, deletions, commits, conflict) against the base branch
+        let base_branch = store.base_branch(task_name, config_base.as_deref());
+        let git_metrics = worktree_path.and_then(|path| git::get_worktree_metrics(path, &base_branch));
 
         if let Some(ref m) = git_metrics {
             total_additions += m.additions;
             total_deletions += m.deletions;
         }
 
+        // Token usage and cost across every session of the task
+        let task_usage = instance.map(|inst| cost::task_usage(task_name, inst));
+        if let Some(ref u) = task_usage {
+            total_usage.add(u);
+        }
+        let usage = task_usage.as_ref().map(|u| u.total());
+        let cost_usd = task_usage.as_ref().map(|u| cost::estimate(u, &prices).usd);
+
         // tmux_alive for JSON output (only meaningful for running tasks)
         let tmux_alive_for_output = if final_status == TaskStatus::Running {
             Some(tmux_alive)
@@ -99,6 +119,14 @@ pub fn display_status(json: bool) -> Result<()> {
             None
         };
 
+        // Blocked on a prompt (only meaningful while the window is alive)
+        let waiting_for_input = match instance {
+            Some(inst) if final_status == TaskStatus::Running && tmux_alive => {
+                Some(agent::waiting_for_input(inst))
+            }
+            _ => None,
+        };
+
         // Get idle time and activity status
         let (idle_secs, active) = if let Some(path) = worktree_path {
             if let Some(last_activity) = git::get_last_activity(path) {
@@ -122,21 +150,24 @@ pub fn display_status(json: bool) -> Result<()> {
             duration_secs,
             duration_human,
             context_percent,
+            model,
             current_tool,
+            base_branch: Some(base_branch),
             git: git_metrics,
             idle_secs,
             active,
             tmux_alive: tmux_alive_for_output,
+            waiting_for_input,
             session_id,
             transcript_exists,
+            sessions,
+            usage,
+            cost_usd,
+            checks: store.status.tasks.get(task_name).and_then(|s| s.checks.clone()),
+            metadata: store.get(task_name).map(|t| t.metadata().clone()).unwrap_or_default(),
         });
     }
 
-    // Save status if any task was auto-marked as Done
-    if status_changed {
-        store.save_status()?;
-    }
-
     let output = StatusOutput {
         tasks: metrics_list,
         summary: StatusSummary {
@@ -144,6 +175,8 @@ pub fn display_status(json: bool) -> Result<()> {
             done: done_count,
             total_additions,
             total_deletions,
+            usage: total_usage.total(),
+            cost_usd: cost::estimate(&total_usage, &prices).usd,
         },
     };
 
@@ -168,10 +201,12 @@ fn print_human_readable(output: &StatusOutput) {
     for task in &output.tasks {
         // For Running status, use running_icon for consistent display with TUI
         let (icon_str, status_suffix) = if task.status == TaskStatus::Running {
-            let (icon, color) = running_icon(task.tmux_alive, task.active);
+            let waiting = task.waiting_for_input == Some(true);
+            let (icon, color) = running_icon(task.tmux_alive, task.active, waiting);
             let colored = format!("{}{}{}", color, icon, RESET);
             let suffix = match task.tmux_alive {
                 Some(false) => " (tmux closed)",
+                _ if waiting => " (waiting for input)",
                 _ => match task.active {
                     Some(true) => "",
                     Some(false) => " (idle)",
@@ -196,6 +231,10 @@ fn print_human_readable(output: &StatusOutput) {
             println!("    Duration: {}", duration);
         }
 
+        if let Some(cost) = task.cost_usd.filter(|c| *c > 0.0) {
+            println!("    Cost:     ${:.2}", cost);
+        }
+
         if let Some(ref git) = task.git {
             if git.additions > 0 || git.deletions > 0 {
                 println!("    Changes:  +{} -{}", git.additions, git.deletions);
@@ -209,11 +248,16 @@ fn print_human_readable(output: &StatusOutput) {
     }
 
     println!("---");
+    let cost = Some(output.summary.cost_usd)
+        .filter(|c| *c > 0.0)
+        .map(|c| format!(" | ${:.2}", c))
+        .unwrap_or_default();
     println!(
-        "Summary: {} running, {} done | +{} -{}",
+        "Summary: {} running, {} done | +{} -{}{}",
         output.summary.running,
         output.summary.done,
         output.summary.total_additions,
-        output.summary.total_deletions
+        output.summary.total_deletions,
+        cost
     );
 }
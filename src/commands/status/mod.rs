@@ -5,16 +5,22 @@ mod types;
 use std::process::Command;
 
 use crate::error::Result;
-use crate::models::WtConfig;
+use crate::models::{Actor, WtConfig};
 use crate::tui::TuiAction;
 
-pub fn execute(json: bool, action: Option<String>, task: Option<String>) -> Result<()> {
+pub fn execute(
+    json: bool,
+    action: Option<String>,
+    task: Option<String>,
+    force: bool,
+    message: Option<String>,
+) -> Result<()> {
     // Verify we're in a wt project directory
     WtConfig::load()?;
 
     // Handle --action parameter
     if let Some(action_name) = action {
-        actions::execute_action(&action_name, task);
+        actions::execute_action(&action_name, task, force, message);
         return Ok(());
     }
 
@@ -48,16 +54,17 @@ fn handle_tui_action(action: TuiAction) -> Result<()> {
         }
         TuiAction::ShowResume {
             worktree,
-            session_id,
-            claude_command,
+            command, ..
         } => {
             eprintln!("Tmux window closed. Run this command to resume:");
-            println!("cd {} && {} -r {}", worktree, claude_command, session_id);
+            println!("cd {} && {}", worktree, command);
             Ok(())
         }
+        TuiAction::Resume { name } => crate::commands::resume::execute(name, None),
         TuiAction::Tail { name } => {
             // Execute tail command (default: 1 turn)
-            crate::commands::tail::execute(name, 1)
+            crate::commands::tail::execute(name, Some(1), Vec::new(), None, None)
         }
+        TuiAction::Done { name } => crate::commands::done::execute_as(name, false, Actor::Tui),
     }
 }
@@ -2,8 +2,9 @@ use std::collections::HashMap;
 
 use serde::Serialize;
 
-use crate::models::TaskStatus;
+use crate::models::{CheckReport, SentMessage, TaskMetadata, TaskStatus};
 use crate::services::git::GitMetrics;
+use crate::services::transcript::TokenUsage;
 
 /// Task metrics for status output
 #[derive(Serialize)]
@@ -17,8 +18,13 @@ pub struct TaskMetrics {
     pub duration_human: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context_percent: Option<u8>,
+    /// Model ID from the transcript
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub current_tool: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub git: Option<GitMetrics>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -27,10 +33,26 @@ pub struct TaskMetrics {
     pub active: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tmux_alive: Option<bool>,
+    /// Agent is blocked on a permission or input prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waiting_for_input: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transcript_exists: Option<bool>,
+    /// Session IDs of the task's transcripts, oldest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sessions: Vec<String>,
+    /// Token usage across all of the task's sessions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+    /// Last acceptance check run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checks: Option<CheckReport>,
+    #[serde(flatten)]
+    pub metadata: TaskMetadata,
 }
 
 /// Status output containing all tasks and summary
@@ -47,6 +69,9 @@ pub struct StatusSummary {
     pub done: usize,
     pub total_additions: i32,
     pub total_deletions: i32,
+    pub usage: TokenUsage,
+    /// Estimated cost of `usage` (models without a price count as zero)
+    pub cost_usd: f64,
 }
 
 /// Action response for --action API
@@ -64,6 +89,9 @@ pub struct ActionResponse {
     pub unavailable_actions: Option<HashMap<String, String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub command: Option<CommandInfo>,
+    /// Message delivered by the send action
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent: Option<SentMessage>,
 }
 
 /// Task information in action response
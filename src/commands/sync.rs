@@ -0,0 +1,57 @@
+//! Sync command - detect task branches merged into the base branch.
+
+use crate::error::Result;
+use crate::models::{Actor, TaskStatus, TaskStore, WtConfig};
+use crate::services::git::{self, MergeKind};
+
+use super::merged::mark_merged;
+
+pub fn execute() -> Result<()> {
+    let config = WtConfig::load()?;
+    let mut store = TaskStore::load()?;
+    let merged = sync_merged(&mut store, config.base_branch.as_deref(), false)?;
+
+    if merged.is_empty() {
+        println!("No merged tasks detected.");
+    } else {
+        for (name, kind) in &merged {
+            println!("  {}: merged ({})", name, kind.display_name());
+        }
+        println!("{} task(s) marked as merged.", merged.len());
+    }
+    Ok(())
+}
+
+/// Check every Done or Running task's branch against its base branch and
+/// mark the merged ones as Merged. Returns the tasks that were transitioned.
+pub fn sync_merged(
+    store: &mut TaskStore,
+    config_base: Option<&str>,
+    silent: bool,
+) -> Result<Vec<(String, MergeKind)>> {
+    let repo_root = git::get_repo_root()?;
+
+    let candidates: Vec<(String, String, String, Option<String>)> = store
+        .list()
+        .iter()
+        .map(|t| t.name().to_string())
+        .filter(|name| {
+            matches!(store.get_status(name), TaskStatus::Done | TaskStatus::Running)
+        })
+        .filter_map(|name| {
+            let inst = store.get_instance(&name)?;
+            let base = store.base_branch(&name, config_base);
+            Some((name, inst.branch.clone(), base, inst.base_commit.clone()))
+        })
+        .collect();
+
+    let mut merged = Vec::new();
+    for (name, branch, base, base_commit) in candidates {
+        if let Some(kind) = git::detect_merge(&branch, &base, base_commit.as_deref(), &repo_root) {
+            mark_merged(store, &name, Actor::Auto, silent)?;
+            merged.push((name, kind));
+        }
+    }
+
+    Ok(merged)
+}
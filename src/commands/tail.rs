@@ -1,12 +1,16 @@
-//! Tail command - view last assistant messages from task transcript.
+//! Tail command - view last assistant messages, or the event stream, from task transcripts.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, Duration, Local, Utc};
 use serde::Serialize;
 
+use crate::cli::{TailFormat, TailType};
+use crate::constants::TAIL_EVENTS;
 use crate::error::{Result, WtError};
 use crate::models::{TaskStatus, TaskStore};
-use crate::services::transcript;
+use crate::services::agent::{self, AgentBackend};
+use crate::services::transcript::{EventKind, TranscriptEvent};
 
 #[derive(Serialize)]
 struct Message {
@@ -14,7 +18,13 @@ struct Message {
     content: String,
 }
 
-pub fn execute(task_ref: String, count: usize) -> Result<()> {
+pub fn execute(
+    task_ref: String,
+    count: Option<usize>,
+    types: Vec<TailType>,
+    since: Option<String>,
+    format: Option<TailFormat>,
+) -> Result<()> {
     let store = TaskStore::load()?;
 
     // Resolve task reference (name or index) to actual name
@@ -40,24 +50,66 @@ pub fn execute(task_ref: String, count: usize) -> Result<()> {
         return Err(WtError::WorktreeNotFound(name));
     }
 
-    // Find transcript file
-    let transcript_path = transcript::find_transcript_for_instance(instance)
-        .ok_or_else(|| WtError::TranscriptNotFound(name.clone()))?;
+    // Parse --since before reading anything
+    let since = since.as_deref().map(|s| parse_since(s, Utc::now())).transpose()?;
 
-    // Get last N messages
-    let messages = transcript::get_last_messages(&transcript_path, count)
-        .ok_or_else(|| WtError::TranscriptParseFailed(name.clone()))?;
+    // Find the transcripts of all of the task's sessions
+    let backend = agent::for_instance(instance);
+    let transcripts = backend.session_transcripts(&name, instance);
+    if transcripts.is_empty() {
+        return Err(WtError::TranscriptNotFound(name));
+    }
+
+    // Without any event option, keep the plain message output
+    if types.is_empty() && since.is_none() && format.is_none() {
+        return print_messages(&name, backend, &transcripts, count.unwrap_or(1));
+    }
+
+    let mut events = Vec::new();
+    for path in &transcripts {
+        let session = backend
+            .events(path)
+            .ok_or_else(|| WtError::TranscriptParseFailed(name.clone()))?;
+        events.extend(session);
+    }
+    events.retain(|event| {
+        (types.is_empty() || types.iter().any(|t| event_kind(*t) == event.kind))
+            && since.is_none_or(|since| event.timestamp.is_some_and(|t| t >= since))
+    });
+    let count = count.unwrap_or(if since.is_some() { usize::MAX } else { TAIL_EVENTS });
+    let events = events.split_off(events.len().saturating_sub(count));
+
+    match format.unwrap_or(TailFormat::Json) {
+        TailFormat::Json => println!("{}", serde_json::to_string_pretty(&events)?),
+        TailFormat::Text => print!("{}", format_text(&events)),
+        TailFormat::Markdown => print!("{}", format_markdown(&events)),
+    }
+
+    Ok(())
+}
+
+/// Last `count` assistant messages as `{"role": "assistant"}` JSON, reaching
+/// back into earlier sessions if needed
+fn print_messages(name: &str, backend: &dyn AgentBackend, transcripts: &[PathBuf], count: usize) -> Result<()> {
+    let mut messages = Vec::new();
+    for path in transcripts {
+        let session = backend
+            .last_messages(path, count)
+            .ok_or_else(|| WtError::TranscriptParseFailed(name.to_string()))?;
+        messages.extend(session);
+    }
+    let messages = messages.split_off(messages.len().saturating_sub(count));
 
     if messages.is_empty() {
-        return Err(WtError::NoAssistantMessages(name));
+        return Err(WtError::NoAssistantMessages(name.to_string()));
     }
 
     // Always output JSON
     let output: Vec<Message> = messages
-        .iter()
+        .into_iter()
         .map(|content| Message {
             role: "assistant",
-            content: content.clone(),
+            content,
         })
         .collect();
 
@@ -65,3 +117,175 @@ pub fn execute(task_ref: String, count: usize) -> Result<()> {
 
     Ok(())
 }
+
+fn event_kind(t: TailType) -> EventKind {
+    match t {
+        TailType::Text => EventKind::Text,
+        TailType::Thinking => EventKind::Thinking,
+        TailType::ToolUse => EventKind::ToolUse,
+        TailType::ToolResult => EventKind::ToolResult,
+        TailType::User => EventKind::User,
+        TailType::System => EventKind::System,
+    }
+}
+
+/// A duration ago (`30s`, `10m`, `2h`, `1d`) or an RFC 3339 timestamp
+fn parse_since(since: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(since) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let invalid = || {
+        WtError::InvalidInput(format!(
+            "Invalid --since '{}' (use e.g. 30s, 10m, 2h, 1d or an RFC 3339 timestamp)",
+            since
+        ))
+    };
+    let (index, _) = since.char_indices().last().ok_or_else(invalid)?;
+    let (amount, unit) = since.split_at(index);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let ago = match unit {
+        "s" => Duration::seconds(amount),
+        "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        _ => return Err(invalid()),
+    };
+    Ok(now - ago)
+}
+
+fn local_time(event: &TranscriptEvent) -> String {
+    event
+        .timestamp
+        .map(|t| t.with_timezone(&Local).format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "--:--:--".to_string())
+}
+
+/// Tool name and, for a failed result, "(error)"
+fn tool_label(event: &TranscriptEvent) -> String {
+    let mut label = event.tool.clone().unwrap_or_default();
+    if event.is_error == Some(true) {
+        label.push_str(" (error)");
+    }
+    label
+}
+
+/// One line per event; further lines of its text indented below
+fn format_text(events: &[TranscriptEvent]) -> String {
+    let mut out = String::new();
+    for event in events {
+        let body = match event.kind {
+            EventKind::ToolUse => format!(
+                "{} {}",
+                tool_label(event),
+                event.input.as_ref().map(|i| i.to_string()).unwrap_or_default()
+            ),
+            EventKind::ToolResult => format!("{}: {}", tool_label(event), event.text.as_deref().unwrap_or("")),
+            _ => event.text.clone().unwrap_or_default(),
+        };
+        let mut lines = body.trim_end().lines();
+        out.push_str(&format!(
+            "{} {:<11} {}\n",
+            local_time(event),
+            event.kind.name(),
+            lines.next().unwrap_or("")
+        ));
+        for line in lines {
+            out.push_str(&format!("{:21}{}\n", "", line));
+        }
+    }
+    out
+}
+
+fn format_markdown(events: &[TranscriptEvent]) -> String {
+    let fenced = |text: &str, lang: &str| {
+        let fence = if text.contains("```") { "````" } else { "```" };
+        format!("{}{}\n{}\n{}\n", fence, lang, text.trim_end(), fence)
+    };
+
+    let mut out = String::new();
+    for event in events {
+        let text = event.text.as_deref().unwrap_or("");
+        let (heading, body) = match event.kind {
+            EventKind::ToolUse => {
+                let input = event
+                    .input
+                    .as_ref()
+                    .and_then(|i| serde_json::to_string_pretty(i).ok())
+                    .unwrap_or_default();
+                (format!("tool_use: {}", tool_label(event)), fenced(&input, "json"))
+            }
+            EventKind::ToolResult => (format!("tool_result: {}", tool_label(event)), fenced(text, "")),
+            EventKind::Thinking => (
+                "thinking".to_string(),
+                text.trim_end().lines().map(|l| format!("> {}\n", l)).collect(),
+            ),
+            kind => (kind.name().to_string(), format!("{}\n", text.trim_end())),
+        };
+        out.push_str(&format!("### {} · {}\n\n{}\n", heading, local_time(event), body));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool_use() -> TranscriptEvent {
+        TranscriptEvent {
+            tool: Some("Bash".to_string()),
+            input: Some(json!({"command": "ls"})),
+            text: None,
+            ..TranscriptEvent::new(EventKind::ToolUse, "")
+        }
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = Utc::now();
+        assert_eq!(parse_since("10m", now).unwrap(), now - Duration::minutes(10));
+        assert_eq!(parse_since("2h", now).unwrap(), now - Duration::hours(2));
+        assert_eq!(
+            parse_since("2025-01-01T00:00:00Z", now).unwrap().to_rfc3339(),
+            "2025-01-01T00:00:00+00:00"
+        );
+        assert!(parse_since("soon", now).is_err());
+        assert!(parse_since("5w", now).is_err());
+        assert!(parse_since("", now).is_err());
+        // Multi-byte units are rejected, not split mid-character
+        assert!(matches!(parse_since("5µ", now), Err(WtError::InvalidInput(_))));
+        assert!(matches!(parse_since("10分", now), Err(WtError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_format_text() {
+        let events = vec![
+            TranscriptEvent::new(EventKind::Text, "first line\nsecond line"),
+            tool_use(),
+            TranscriptEvent {
+                tool: Some("Bash".to_string()),
+                is_error: Some(true),
+                ..TranscriptEvent::new(EventKind::ToolResult, "not found")
+            },
+        ];
+
+        assert_eq!(
+            format_text(&events),
+            "--:--:-- text        first line\n\
+             \x20                    second line\n\
+             --:--:-- tool_use    Bash {\"command\":\"ls\"}\n\
+             --:--:-- tool_result Bash (error): not found\n"
+        );
+    }
+
+    #[test]
+    fn test_format_markdown() {
+        let events = vec![tool_use(), TranscriptEvent::new(EventKind::Thinking, "hmm\nok")];
+
+        assert_eq!(
+            format_markdown(&events),
+            "### tool_use: Bash · --:--:--\n\n```json\n{\n  \"command\": \"ls\"\n}\n```\n\n\
+             ### thinking · --:--:--\n\n> hmm\n> ok\n\n"
+        );
+    }
+}
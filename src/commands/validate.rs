@@ -1,6 +1,7 @@
 use crate::constants::TASKS_DIR;
 use crate::error::Result;
-use crate::models::TaskStore;
+use crate::models::{TaskStore, WtConfig};
+use crate::services::template;
 
 pub fn execute(task_ref: Option<String>) -> Result<()> {
     let store = TaskStore::load()?;
@@ -30,6 +31,14 @@ pub fn execute(task_ref: Option<String>) -> Result<()> {
         errors
     };
 
+    // Config templates are used by every task, so always report them
+    let config_errors = WtConfig::load().map(|c| template::check_config(&c)).unwrap_or_default();
+    let errors: Vec<_> = config_errors
+        .into_iter()
+        .map(|e| ("config".to_string(), e))
+        .chain(errors)
+        .collect();
+
     if errors.is_empty() {
         let count = if name.is_some() { 1 } else { store.tasks.len() };
         println!("✓ All {} task(s) valid.", count);
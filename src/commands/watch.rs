@@ -0,0 +1,131 @@
+//! Watch command - notify when running agents finish, go idle or wait for input.
+
+use std::collections::HashSet;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::constants::IDLE_THRESHOLD_SECS;
+use crate::display::{format_duration, log};
+use crate::error::Result;
+use crate::models::{TaskStatus, TaskStore, WtConfig};
+use crate::services::notify::{self, Notification, NotifyEvent};
+use crate::services::{agent, git};
+
+pub fn execute(interval: u64, once: bool) -> Result<()> {
+    let config = WtConfig::load()?;
+    let idle_secs = config.notify.idle_secs.unwrap_or(IDLE_THRESHOLD_SECS);
+    let mut sinks = notify::sinks(&config.notify);
+
+    if !once {
+        let names: Vec<&str> = sinks.iter().map(|s| s.name()).collect();
+        println!("Watching running tasks (notify: {}). Press Ctrl+C to stop.", names.join(", "));
+    }
+
+    // States already reported, so each stretch of idling or waiting notifies once
+    let mut reported: HashSet<(String, NotifyEvent)> = HashSet::new();
+
+    loop {
+        let mut store = TaskStore::load()?;
+        for notification in poll(&mut store, &mut reported, idle_secs, &config.checks)? {
+            log(&format!("'{}' {}: {}", notification.task, notification.event.name(), notification.message));
+            for sink in sinks.iter_mut() {
+                if let Err(e) = sink.send(&notification) {
+                    eprintln!("  Warning: {}", e);
+                }
+            }
+        }
+
+        if once {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_secs(interval));
+    }
+}
+
+/// Check every running task once, with the signals the TUI shows: a closed
+/// tmux window marks the task done (unless it has acceptance checks to run),
+/// a prompt on screen means it's waiting for input, and no worktree or transcript activity
+/// for `idle_secs` makes it idle.
+fn poll(
+    store: &mut TaskStore,
+    reported: &mut HashSet<(String, NotifyEvent)>,
+    idle_secs: u64,
+    config_checks: &[String],
+) -> Result<Vec<Notification>> {
+    let mut notifications = Vec::new();
+    let running: Vec<String> = store
+        .list()
+        .iter()
+        .map(|t| t.name().to_string())
+        .filter(|name| store.get_status(name) == TaskStatus::Running)
+        .collect();
+    reported.retain(|(name, _)| running.contains(name));
+
+    for name in &running {
+        if store.auto_mark_done_if_needed(name, config_checks)? {
+            reported.retain(|(n, _)| n != name);
+            notifications.push(Notification::new(
+                NotifyEvent::Finished,
+                name,
+                "agent exited, task marked done",
+            ));
+            continue;
+        }
+        // With acceptance checks the task stays Running until `wt done`
+        if store.window_exited(name) {
+            if reported.insert((name.clone(), NotifyEvent::Finished)) {
+                notifications.push(Notification::new(
+                    NotifyEvent::Finished,
+                    name,
+                    "agent exited, run `wt done` to check and mark it done",
+                ));
+            }
+            continue;
+        }
+
+        let Some(instance) = store.get_instance(name) else {
+            continue;
+        };
+        let backend = agent::for_instance(instance);
+        let metrics = backend
+            .find_transcript(name, instance)
+            .and_then(|path| backend.parse_transcript(&path));
+
+        // Each state is reported once when entered, again after it clears
+        let mut report = |event: NotifyEvent, active: bool, message: String| {
+            let key = (name.clone(), event);
+            if !active {
+                reported.remove(&key);
+            } else if reported.insert(key) {
+                notifications.push(Notification::new(event, name, message));
+            }
+        };
+
+        let waiting = agent::waiting_for_input(instance);
+        let tool = metrics.as_ref().and_then(|m| m.pending_tool.as_deref());
+        report(
+            NotifyEvent::Waiting,
+            waiting,
+            match tool {
+                Some(tool) => format!("waiting for input ({})", tool),
+                None => "waiting for input".to_string(),
+            },
+        );
+
+        let transcript_activity = metrics.as_ref().and_then(|m| m.finished_at).map(SystemTime::from);
+        let Some(last) = git::get_last_activity(&instance.worktree_path).max(transcript_activity) else {
+            continue;
+        };
+        let elapsed = SystemTime::now()
+            .duration_since(last)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        // A blocked agent is reported as waiting, not idle
+        report(
+            NotifyEvent::Idle,
+            !waiting && elapsed >= idle_secs,
+            format!("no activity for {}", format_duration(elapsed as i64)),
+        );
+    }
+    Ok(notifications)
+}
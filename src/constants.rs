@@ -18,12 +18,33 @@ pub const BRANCH_PREFIX: &str = "wt/";
 /// Status file for runtime state
 pub const STATUS_FILE: &str = ".wt/status.json";
 
+/// Append-only journal of task state transitions
+pub const EVENTS_FILE: &str = ".wt/events.jsonl";
+
 /// Logs directory for debug output
 pub const LOGS_DIR: &str = ".wt/logs";
 
+/// Output of a `shell` agent, kept under .wt/logs/<task>/ as its transcript
+pub const AGENT_LOG_FILE: &str = "agent.log";
+
+/// Output of lifecycle hooks, appended under .wt/logs/<task>/
+pub const HOOKS_LOG_FILE: &str = "hooks.log";
+
 /// Backups directory for reset command
 pub const BACKUPS_DIR: &str = ".wt/backups";
 
+/// Lines of output kept per acceptance check result
+pub const CHECK_OUTPUT_LINES: usize = 20;
+
+/// Agent messages of the previous session carried over by `wt restart --keep-history`
+pub const HANDOFF_MESSAGES: usize = 3;
+
+/// Characters of a tool result kept in `wt tail` events
+pub const TOOL_RESULT_CHARS: usize = 2000;
+
+/// Events shown by `wt tail --types/--format` without `-n` or `--since`
+pub const TAIL_EVENTS: usize = 20;
+
 /// Idle threshold in seconds (for status command)
 pub const IDLE_THRESHOLD_SECS: u64 = 120;
 
@@ -1,9 +1,12 @@
 //! Display formatting utilities.
 
+use chrono::Local;
+
 // ANSI color codes
 pub const RESET: &str = "\x1b[0m";
 pub const WHITE: &str = "\x1b[37m";
 pub const GREEN: &str = "\x1b[32m";
+pub const RED: &str = "\x1b[31m";
 pub const YELLOW: &str = "\x1b[33m";
 pub const MAGENTA: &str = "\x1b[35m";
 pub const GRAY: &str = "\x1b[90m";
@@ -17,12 +20,14 @@ pub fn colored_index(idx: usize) -> String {
 ///
 /// Returns (icon, color_code) tuple for terminal display.
 /// - tmux dead: ⚠ yellow (warning)
+/// - waiting for input: ? magenta
 /// - active: ● green
 /// - idle: ● yellow
 /// - unknown: ● green (default)
-pub fn running_icon(tmux_alive: Option<bool>, active: Option<bool>) -> (&'static str, &'static str) {
+pub fn running_icon(tmux_alive: Option<bool>, active: Option<bool>, waiting: bool) -> (&'static str, &'static str) {
     match tmux_alive {
         Some(false) => ("⚠", YELLOW), // tmux window closed
+        _ if waiting => ("?", MAGENTA), // blocked on a prompt
         _ => match active {
             Some(true) => ("●", GREEN),  // actively working
             Some(false) => ("●", YELLOW), // idle
@@ -54,6 +59,11 @@ pub fn format_duration(secs: i64) -> String {
     }
 }
 
+/// Print a message prefixed with the local time, for long-running commands
+pub fn log(message: &str) {
+    println!("{}[{}]{} {}", GRAY, Local::now().format("%H:%M:%S"), RESET, message);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -17,6 +17,9 @@ pub enum WtError {
     #[error("Invalid task index {index}: valid range is 1-{total}")]
     InvalidTaskIndex { index: usize, total: usize },
 
+    #[error("No task with ID {0}")]
+    InvalidTaskId(usize),
+
     #[error("Task '{0}' already exists")]
     TaskExists(String),
 
@@ -26,9 +29,22 @@ pub enum WtError {
     #[error("Cannot start task '{task}': dependency '{dep}' is not merged")]
     DependencyNotMerged { task: String, dep: String },
 
+    #[error("Cannot start task '{task}': dependency '{dep}' is not done or merged")]
+    DependencyNotDone { task: String, dep: String },
+
     #[error("Git command failed: {0}")]
     Git(String),
 
+    #[error("Cannot merge '{branch}' into {base}: {message}\nThe merge was aborted and the repository left unchanged.")]
+    MergeFailed {
+        branch: String,
+        base: String,
+        message: String,
+    },
+
+    #[error("Cannot restack '{task}': {message}\nThe rebase was aborted and the branch left unchanged.")]
+    RestackFailed { task: String, message: String },
+
     #[error("Tmux command failed: {0}")]
     Tmux(String),
 
@@ -47,6 +63,9 @@ pub enum WtError {
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    #[error("Invalid template: {0}")]
+    Template(String),
+
     #[error("IO error during {operation} on '{path}': {message}")]
     Io {
         operation: String,
@@ -63,6 +82,16 @@ pub enum WtError {
         exit_code: Option<i32>,
     },
 
+    #[error("Hook {hook} failed for '{task}': {message}\nSee .wt/logs/{task}/hooks.log for its output.")]
+    HookFailed {
+        hook: String,
+        task: String,
+        message: String,
+    },
+
+    #[error("Acceptance checks failed for '{task}': {}\nFix the failures or use --force to mark it done anyway.", failed.join(", "))]
+    ChecksFailed { task: String, failed: Vec<String> },
+
     #[error("Invalid state transition: cannot change task from {from} to {to}")]
     InvalidStateTransition { from: String, to: String },
 
@@ -73,6 +102,12 @@ pub enum WtError {
         status: String,
     },
 
+    #[error("Notification failed: {0}")]
+    Notify(String),
+
+    #[error("Scheduler stopped: {0}")]
+    Scheduler(String),
+
     #[error("Task '{0}' has not been started")]
     TaskNotStarted(String),
 
@@ -12,21 +12,39 @@ use cli::{Cli, Commands, CompletionsAction};
 
 fn main() {
     let cli = Cli::parse();
+    if let Some(legacy) = cli.legacy_index {
+        models::TaskStore::set_legacy_index(legacy);
+    }
 
     let result = match cli.command {
         Commands::Init => commands::init::execute(),
         Commands::Create { json } => commands::create::execute(json),
         Commands::Validate { name } => commands::validate::execute(name),
-        Commands::List { tree, json } => commands::list::execute(tree, json),
+        Commands::Show { name, json } => commands::show::execute(name, json),
+        Commands::List { tree, json, tag, owner } => commands::list::execute(tree, json, tag, owner),
         Commands::Start { name, all } => commands::start::execute(name, all),
-        Commands::Done { name } => commands::done::execute(name),
+        Commands::Run { interval } => commands::run::execute(interval),
+        Commands::Watch { interval, once } => commands::watch::execute(interval, once),
+        Commands::Send { name, message } => commands::send::execute(name, message),
+        Commands::Restart { name, keep_history } => commands::restart::execute(name, keep_history),
+        Commands::Resume { name, prompt } => commands::resume::execute(name, prompt),
+        Commands::Done { name, force } => commands::done::execute(name, force),
         Commands::Merged { name } => commands::merged::execute(name, false),
+        Commands::Merge { name, strategy } => commands::merge::execute(name, strategy),
+        Commands::Restack { name } => commands::restack::execute(name),
+        Commands::Sync => commands::sync::execute(),
         Commands::Archive { name } => commands::archive::execute(name, false),
         Commands::Next { json } => commands::next::execute(json),
         Commands::Reset { name } => commands::reset::execute(name),
-        Commands::Status { json, action, task } => commands::status::execute(json, action, task),
-        Commands::Tail { name, count } => commands::tail::execute(name, count),
+        Commands::Status { json, action, task, force, message } => {
+            commands::status::execute(json, action, task, force, message)
+        }
+        Commands::Tail { name, count, types, since, format } => {
+            commands::tail::execute(name, count, types, since, format)
+        }
         Commands::Logs => commands::logs::execute(),
+        Commands::Cost { name, json } => commands::cost::execute(name, json),
+        Commands::History { name, json } => commands::history::execute(name, json),
         Commands::New { name, print_path } => commands::new::execute(name, print_path),
         Commands::Completions { action } => match action {
             CompletionsAction::Generate { shell } => commands::completions::generate_completions(shell),
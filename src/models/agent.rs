@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Which coding agent runs in a task's tmux window
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AgentKind {
+    /// Claude Code CLI (`claude_command` + `start_args`)
+    #[default]
+    Claude,
+    /// Any shell command (`shell_command`), with its output kept as the transcript
+    Shell,
+}
+
+impl AgentKind {
+    /// Get display name for the agent.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AgentKind::Claude => "claude",
+            AgentKind::Shell => "shell",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_kind_default_is_claude() {
+        assert_eq!(AgentKind::default(), AgentKind::Claude);
+    }
+
+    #[test]
+    fn test_agent_kind_serde_lowercase() {
+        let kind: AgentKind = serde_yaml::from_str("shell").unwrap();
+        assert_eq!(kind, AgentKind::Shell);
+        assert_eq!(serde_json::to_string(&AgentKind::Claude).unwrap(), "\"claude\"");
+    }
+}
@@ -0,0 +1,84 @@
+//! Results of running a task's acceptance checks.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a single acceptance check command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub command: String,
+    pub passed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// Tail of the combined stdout/stderr
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub output: String,
+}
+
+/// Last acceptance check run for a task, stored in status.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckReport {
+    pub ran_at: DateTime<Utc>,
+    pub passed: bool,
+    /// The task was marked done despite failing checks (`--force`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub forced: bool,
+    pub results: Vec<CheckResult>,
+}
+
+impl CheckReport {
+    pub fn new(results: Vec<CheckResult>) -> Self {
+        Self {
+            ran_at: Utc::now(),
+            passed: results.iter().all(|r| r.passed),
+            forced: false,
+            results,
+        }
+    }
+
+    /// Commands of the checks that failed
+    pub fn failed(&self) -> Vec<&str> {
+        self.results
+            .iter()
+            .filter(|r| !r.passed)
+            .map(|r| r.command.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(command: &str, passed: bool) -> CheckResult {
+        CheckResult {
+            command: command.to_string(),
+            passed,
+            exit_code: Some(if passed { 0 } else { 1 }),
+            duration_ms: 10,
+            output: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_report_passed() {
+        let report = CheckReport::new(vec![result("a", true), result("b", true)]);
+        assert!(report.passed);
+        assert!(report.failed().is_empty());
+
+        let report = CheckReport::new(vec![result("a", true), result("b", false)]);
+        assert!(!report.passed);
+        assert_eq!(report.failed(), vec!["b"]);
+    }
+
+    #[test]
+    fn test_check_report_serialization() {
+        let report = CheckReport::new(vec![result("cargo test", true)]);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["passed"], true);
+        assert!(json.get("forced").is_none());
+        assert_eq!(json["results"][0]["command"], "cargo test");
+        assert!(json["results"][0].get("output").is_none());
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
+use super::{AgentKind, Hook, Task};
 use crate::constants::{CONFIG_FILE, DEFAULT_TMUX_SESSION, DEFAULT_WORKTREE_DIR};
 use crate::error::{Result, WtError};
 
@@ -10,19 +12,144 @@ pub struct WtConfig {
     pub claude_command: String,
     #[serde(default = "default_start_args")]
     pub start_args: String,
+    /// Agent backend tasks are started with (a task's `agent` replaces this)
+    #[serde(default)]
+    pub agent: AgentKind,
+    /// Command line run by the `shell` agent (`${task}`, `${branch}`,
+    /// `${worktree}` and `${session_id}` are expanded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell_command: Option<String>,
     #[serde(default = "default_tmux_session")]
     pub tmux_session: String,
     #[serde(default = "default_worktree_dir")]
     pub worktree_dir: String,
+    /// Branch tasks fork from and merge into (default: main, then master)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub copy_files: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub init_script: Option<String>,
+    /// Environment variables exported in every task's tmux window
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
     #[serde(default)]
     pub logs: LogsConfig,
     /// Script to run before archiving/reset (optional, for cleanup like rm -rf node_modules/)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub archive_script: Option<String>,
+    /// Lifecycle hook scripts
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    /// Where `wt watch` sends notifications
+    #[serde(default)]
+    pub notify: NotifyConfig,
+    /// Model prices for `wt cost`, keyed by a substring of the model ID
+    /// (added to and overriding the built-in table)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub prices: BTreeMap<String, ModelPrice>,
+    /// Context window sizes in tokens, keyed by a substring of the model ID
+    /// (added to and overriding the built-in table)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub context_windows: BTreeMap<String, u64>,
+    /// Run merge detection (`wt sync`) on every TUI refresh
+    #[serde(default)]
+    pub auto_sync: bool,
+    /// Let tasks start on top of Done (not yet merged) dependency branches
+    #[serde(default)]
+    pub stack: bool,
+    /// Acceptance checks run in the worktree before a task can be marked done
+    /// (a task's own `acceptance` list replaces these)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<String>,
+    /// Maximum number of tasks Running at once (unset or 0: unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallel: Option<usize>,
+    /// Number tasks by alphabetical position (renumbered whenever tasks are
+    /// added) instead of by stable ID
+    #[serde(default)]
+    pub legacy_index: bool,
+}
+
+/// Effective launch settings of a task: the project config with the task's
+/// frontmatter overrides applied
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LaunchSettings {
+    pub agent: AgentKind,
+    /// Agent executable (None: the shell agent has no `shell_command`)
+    pub command: Option<String>,
+    pub start_args: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init_script: Option<String>,
+    /// Settings that come from the task rather than the config
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overridden: Vec<&'static str>,
+}
+
+/// Scripts run at points of a task's lifecycle (see [`Hook`])
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HooksConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_done: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_merged: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_archive: Option<String>,
+}
+
+impl HooksConfig {
+    /// Script configured for a hook
+    pub fn get(&self, hook: Hook) -> Option<&str> {
+        match hook {
+            Hook::PreStart => self.pre_start.as_deref(),
+            Hook::PostStart => self.post_start.as_deref(),
+            Hook::OnDone => self.on_done.as_deref(),
+            Hook::OnMerged => self.on_merged.as_deref(),
+            Hook::PreArchive => self.pre_archive.as_deref(),
+        }
+    }
+}
+
+/// Price of a model in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    /// Cache reads (default: a tenth of `input`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read: Option<f64>,
+    /// Cache writes (default: 1.25 × `input`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+}
+
+/// Notification sinks used by `wt watch` (the terminal bell when none is set)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotifyConfig {
+    /// Ring the terminal bell
+    #[serde(default)]
+    pub bell: bool,
+    /// Show a desktop notification with `notify-send`
+    #[serde(default)]
+    pub notify_send: bool,
+    /// Command run with `WT_EVENT`, `WT_TASK` and `WT_MESSAGE` set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// URL the notification is POSTed to as JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+    /// Seconds without activity before a running agent counts as idle
+    /// (default: 120)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_secs: Option<u64>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -61,7 +188,64 @@ impl WtConfig {
         Self::from_str(&content)
     }
 
+    /// Number of tasks that may still be started with `running` already Running
+    pub fn free_slots(&self, running: usize) -> usize {
+        match self.max_parallel {
+            Some(max) if max > 0 => max.saturating_sub(running),
+            _ => usize::MAX,
+        }
+    }
+
+    /// Launch settings for a task (or for the config alone, with no task)
+    pub fn launch_settings(&self, task: Option<&Task>) -> LaunchSettings {
+        let agent = task.map(|t| t.agent(self.agent)).unwrap_or(self.agent);
+        let mut settings = LaunchSettings {
+            agent,
+            command: match agent {
+                AgentKind::Claude => Some(self.claude_command.clone()),
+                AgentKind::Shell => self.shell_command.clone(),
+            },
+            start_args: self.start_args.clone(),
+            model: None,
+            env: self.env.clone(),
+            init_script: self.init_script.clone(),
+            overridden: Vec::new(),
+        };
+        let Some(task) = task else {
+            return settings;
+        };
+
+        if task.frontmatter.agent.is_some() {
+            settings.overridden.push("agent");
+        }
+        let overrides = task.overrides();
+        if let Some(command) = &overrides.agent_command {
+            settings.command = Some(command.clone());
+            settings.overridden.push("command");
+        }
+        if let Some(args) = &overrides.start_args {
+            settings.start_args = args.clone();
+            settings.overridden.push("start_args");
+        }
+        if let Some(model) = &overrides.model {
+            settings.model = Some(model.clone());
+            settings.overridden.push("model");
+        }
+        if !overrides.env.is_empty() {
+            settings
+                .env
+                .extend(overrides.env.iter().map(|(k, v)| (k.clone(), v.clone())));
+            settings.overridden.push("env");
+        }
+        if let Some(script) = &overrides.init_script {
+            settings.init_script = Some(script.clone());
+            settings.overridden.push("init_script");
+        }
+        settings
+    }
+
     /// Parse config from string
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(content: &str) -> Result<Self> {
         let config: WtConfig = serde_yaml::from_str(content)?;
         Ok(config)
@@ -94,6 +278,78 @@ mod tests {
         assert!(config.start_args.contains("--output-format=stream-json"));
         assert_eq!(config.tmux_session, "wt");
         assert_eq!(config.worktree_dir, ".wt/worktrees");
+        assert!(config.base_branch.is_none());
+    }
+
+    #[test]
+    fn test_config_free_slots() {
+        let mut config = WtConfig::from_str("{}\n").unwrap();
+        assert_eq!(config.free_slots(5), usize::MAX);
+
+        config.max_parallel = Some(2);
+        assert_eq!(config.free_slots(0), 2);
+        assert_eq!(config.free_slots(1), 1);
+        assert_eq!(config.free_slots(3), 0);
+
+        config.max_parallel = Some(0);
+        assert_eq!(config.free_slots(3), usize::MAX);
+    }
+
+    #[test]
+    fn test_config_checks() {
+        let config = WtConfig::from_str("checks:\n  - cargo test\n  - npm run lint\n").unwrap();
+        assert_eq!(config.checks, vec!["cargo test", "npm run lint"]);
+    }
+
+    #[test]
+    fn test_config_agent() {
+        let config = WtConfig::from_str("{}\n").unwrap();
+        assert_eq!(config.agent, AgentKind::Claude);
+        assert!(config.shell_command.is_none());
+
+        let config = WtConfig::from_str("agent: shell\nshell_command: ./run.sh ${task}\n").unwrap();
+        assert_eq!(config.agent, AgentKind::Shell);
+        assert_eq!(config.shell_command.as_deref(), Some("./run.sh ${task}"));
+    }
+
+    #[test]
+    fn test_launch_settings_merges_task_overrides() {
+        use crate::models::TaskStore;
+
+        let config = WtConfig::from_str(
+            "claude_command: claude\nstart_args: -p go\ninit_script: npm i\nenv:\n  PORT: '3000'\n  MODE: dev\n",
+        )
+        .unwrap();
+        let task = TaskStore::parse_markdown(
+            "---\nname: big\nagent_command: ccc\nmodel: opus\nenv:\n  MODE: ci\n---\n\nBig task",
+            "big.md".to_string(),
+        )
+        .unwrap();
+
+        let settings = config.launch_settings(Some(&task));
+        assert_eq!(settings.command.as_deref(), Some("ccc"));
+        assert_eq!(settings.start_args, "-p go");
+        assert_eq!(settings.model.as_deref(), Some("opus"));
+        assert_eq!(settings.init_script.as_deref(), Some("npm i"));
+        assert_eq!(settings.env["PORT"], "3000");
+        assert_eq!(settings.env["MODE"], "ci");
+        assert_eq!(settings.overridden, vec!["command", "model", "env"]);
+
+        let defaults = config.launch_settings(None);
+        assert_eq!(defaults.command.as_deref(), Some("claude"));
+        assert!(defaults.overridden.is_empty());
+    }
+
+    #[test]
+    fn test_config_legacy_index() {
+        assert!(!WtConfig::from_str("{}\n").unwrap().legacy_index);
+        assert!(WtConfig::from_str("legacy_index: true\n").unwrap().legacy_index);
+    }
+
+    #[test]
+    fn test_config_base_branch() {
+        let config = WtConfig::from_str("base_branch: develop\n").unwrap();
+        assert_eq!(config.base_branch, Some("develop".to_string()));
     }
 
     #[test]
@@ -149,12 +405,25 @@ init_script: |
         let config = WtConfig {
             claude_command: "ccc".to_string(),
             start_args: "-p test".to_string(),
+            agent: AgentKind::Claude,
+            shell_command: None,
             tmux_session: "wt".to_string(),
             worktree_dir: ".wt/worktrees".to_string(),
+            base_branch: None,
             copy_files: vec![".env".to_string()],
             init_script: Some("npm i".to_string()),
+            env: BTreeMap::new(),
             logs: LogsConfig::default(),
             archive_script: None,
+            hooks: HooksConfig::default(),
+            notify: NotifyConfig::default(),
+            prices: BTreeMap::new(),
+            context_windows: BTreeMap::new(),
+            auto_sync: false,
+            stack: false,
+            checks: Vec::new(),
+            max_parallel: None,
+            legacy_index: false,
         };
         let yaml = serde_yaml::to_string(&config).unwrap();
 
@@ -0,0 +1,163 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, WtError};
+use crate::models::TaskStatus;
+
+/// Who triggered a state transition
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Actor {
+    /// A `wt` subcommand run by a user or agent
+    Cli,
+    /// The status TUI (including `status --action`)
+    Tui,
+    /// Detected automatically (e.g. tmux window closed)
+    Auto,
+}
+
+impl Actor {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Actor::Cli => "cli",
+            Actor::Tui => "tui",
+            Actor::Auto => "auto",
+        }
+    }
+}
+
+/// A single task state transition, one line in .wt/events.jsonl
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskEvent {
+    pub timestamp: DateTime<Utc>,
+    pub task: String,
+    pub actor: Actor,
+    pub from: TaskStatus,
+    pub to: TaskStatus,
+}
+
+impl TaskEvent {
+    pub fn new(task: &str, actor: Actor, from: TaskStatus, to: TaskStatus) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            task: task.to_string(),
+            actor,
+            from,
+            to,
+        }
+    }
+}
+
+/// Append events to the journal (one JSON object per line).
+pub fn append_events(path: &Path, events: &[TaskEvent]) -> Result<()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let mut content = String::new();
+    for event in events {
+        content.push_str(&serde_json::to_string(event)?);
+        content.push('\n');
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| WtError::Io {
+            operation: "open event journal".to_string(),
+            path: path.to_string_lossy().to_string(),
+            message: e.to_string(),
+        })?;
+
+    file.write_all(content.as_bytes()).map_err(|e| WtError::Io {
+        operation: "append event journal".to_string(),
+        path: path.to_string_lossy().to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// Load all events from the journal, oldest first.
+/// Malformed lines (e.g. a torn write) are skipped.
+pub fn load_events(path: &Path) -> Result<Vec<TaskEvent>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path).map_err(|e| WtError::Io {
+        operation: "read event journal".to_string(),
+        path: path.to_string_lossy().to_string(),
+        message: e.to_string(),
+    })?;
+
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_event_serialize() {
+        let event = TaskEvent::new("auth", Actor::Auto, TaskStatus::Running, TaskStatus::Done);
+        let json = serde_json::to_string(&event).unwrap();
+
+        assert!(json.contains("\"task\":\"auth\""));
+        assert!(json.contains("\"actor\":\"auto\""));
+        assert!(json.contains("\"from\":\"running\""));
+        assert!(json.contains("\"to\":\"done\""));
+    }
+
+    #[test]
+    fn test_append_and_load_events() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("events.jsonl");
+
+        append_events(
+            &path,
+            &[TaskEvent::new("a", Actor::Cli, TaskStatus::Pending, TaskStatus::Running)],
+        )
+        .unwrap();
+        append_events(
+            &path,
+            &[TaskEvent::new("a", Actor::Tui, TaskStatus::Running, TaskStatus::Done)],
+        )
+        .unwrap();
+
+        let events = load_events(&path).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].to, TaskStatus::Running);
+        assert_eq!(events[1].actor, Actor::Tui);
+    }
+
+    #[test]
+    fn test_load_events_missing_file() {
+        let dir = TempDir::new().unwrap();
+        let events = load_events(&dir.path().join("events.jsonl")).unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_load_events_skips_malformed_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("events.jsonl");
+        fs::write(&path, "not json\n\n").unwrap();
+        append_events(
+            &path,
+            &[TaskEvent::new("a", Actor::Cli, TaskStatus::Done, TaskStatus::Merged)],
+        )
+        .unwrap();
+
+        let events = load_events(&path).unwrap();
+        assert_eq!(events.len(), 1);
+    }
+}
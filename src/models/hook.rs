@@ -0,0 +1,33 @@
+/// Points in a task's lifecycle where a hook can run.
+///
+/// `pre_start`, `on_done` and `pre_archive` run before the transition and
+/// veto it by failing; `post_start` and `on_merged` run after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    PreStart,
+    PostStart,
+    OnDone,
+    OnMerged,
+    PreArchive,
+}
+
+impl Hook {
+    pub const ALL: [Hook; 5] = [
+        Hook::PreStart,
+        Hook::PostStart,
+        Hook::OnDone,
+        Hook::OnMerged,
+        Hook::PreArchive,
+    ];
+
+    /// Config key of the hook
+    pub fn name(&self) -> &'static str {
+        match self {
+            Hook::PreStart => "pre_start",
+            Hook::PostStart => "post_start",
+            Hook::OnDone => "on_done",
+            Hook::OnMerged => "on_merged",
+            Hook::PreArchive => "pre_archive",
+        }
+    }
+}
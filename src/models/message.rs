@@ -0,0 +1,45 @@
+//! Follow-up prompts sent to a running agent (`wt send`).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::Actor;
+
+/// How a message was typed into the agent's tmux window
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageFormat {
+    /// The text as-is, followed by Enter
+    Text,
+    /// A JSON user message on one line (`--input-format=stream-json`)
+    StreamJson,
+}
+
+impl MessageFormat {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            MessageFormat::Text => "text",
+            MessageFormat::StreamJson => "stream-json",
+        }
+    }
+}
+
+/// A message delivered to a task's agent, stored in status.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentMessage {
+    pub sent_at: DateTime<Utc>,
+    pub actor: Actor,
+    pub format: MessageFormat,
+    pub text: String,
+}
+
+impl SentMessage {
+    pub fn new(actor: Actor, format: MessageFormat, text: &str) -> Self {
+        Self {
+            sent_at: Utc::now(),
+            actor,
+            format,
+            text: text.to_string(),
+        }
+    }
+}
@@ -1,9 +1,19 @@
+mod agent;
+mod check;
 mod config;
+mod event;
+mod hook;
+mod message;
 mod status;
 mod store;
 mod task;
 
-pub use config::WtConfig;
+pub use agent::AgentKind;
+pub use check::{CheckReport, CheckResult};
+pub use config::{LaunchSettings, ModelPrice, NotifyConfig, WtConfig};
+pub use event::{Actor, TaskEvent};
+pub use hook::Hook;
+pub use message::{MessageFormat, SentMessage};
 pub use status::StatusStore;
 pub use store::TaskStore;
-pub use task::{Instance, Task, TaskFrontmatter, TaskInput, TaskStatus};
+pub use task::{Instance, Task, TaskFrontmatter, TaskInput, TaskMetadata, TaskStatus};
@@ -1,12 +1,13 @@
 use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-use crate::constants::STATUS_FILE;
+use crate::constants::{EVENTS_FILE, STATUS_FILE};
 use crate::error::{Result, WtError};
-use crate::models::{Instance, TaskStatus};
+use crate::models::event::{self, Actor, TaskEvent};
+use crate::models::{CheckReport, Instance, SentMessage, TaskStatus};
 
 /// Runtime state for a single task
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +17,12 @@ pub struct TaskState {
     pub instance: Option<Instance>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scratch: Option<bool>,
+    /// Result of the last acceptance check run (see `wt done`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checks: Option<CheckReport>,
+    /// Messages delivered to the agent with `wt send`, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<SentMessage>,
 }
 
 impl Default for TaskState {
@@ -24,14 +31,22 @@ impl Default for TaskState {
             status: TaskStatus::Pending,
             instance: None,
             scratch: None,
+            checks: None,
+            messages: Vec::new(),
         }
     }
 }
 
 /// Store for all task runtime states
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StatusStore {
     pub tasks: HashMap<String, TaskState>,
+    /// Stable task IDs (name -> id). Kept across reset/archive, never reused.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub ids: HashMap<String, usize>,
+    /// Transitions recorded in this transaction, journaled on save
+    #[serde(skip)]
+    pending_events: Vec<TaskEvent>,
 }
 
 impl StatusStore {
@@ -53,9 +68,33 @@ impl StatusStore {
         })
     }
 
+    /// Run a read-modify-write transaction on .wt/status.json.
+    ///
+    /// Holds an exclusive advisory lock for the whole transaction and re-reads
+    /// the file under it, so concurrent `wt` processes (including ones running
+    /// inside worktrees via the status.json symlink) never drop each other's
+    /// updates. The closure's changes are saved only if it returns `Ok`.
+    ///
+    /// Must not be nested: the lock is not re-entrant.
+    pub fn update<T>(f: impl FnOnce(&mut StatusStore) -> Result<T>) -> Result<T> {
+        let _lock = StatusLock::acquire()?;
+        let mut store = Self::load()?;
+        let result = f(&mut store)?;
+        store.save()?;
+        event::append_events(&events_path(), &store.pending_events)?;
+        store.pending_events.clear();
+        Ok(result)
+    }
+
+    /// Load the transition journal (.wt/events.jsonl), oldest first
+    pub fn load_events() -> Result<Vec<TaskEvent>> {
+        event::load_events(&events_path())
+    }
+
     /// Save status to .wt/status.json (atomic write via temp file + rename)
-    pub fn save(&self) -> Result<()> {
-        let path = Path::new(STATUS_FILE);
+    fn save(&self) -> Result<()> {
+        let resolved = resolve_status_path();
+        let path = resolved.as_path();
 
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
@@ -73,7 +112,7 @@ impl StatusStore {
         })?;
 
         // Atomic write: write to temp file, then rename
-        let temp_path = format!("{}.tmp", STATUS_FILE);
+        let temp_path = format!("{}.tmp", path.display());
         fs::write(&temp_path, &content).map_err(|e| WtError::Io {
             operation: "write temp status file".to_string(),
             path: temp_path.clone(),
@@ -82,7 +121,7 @@ impl StatusStore {
 
         fs::rename(&temp_path, path).map_err(|e| WtError::Io {
             operation: "rename status file".to_string(),
-            path: STATUS_FILE.to_string(),
+            path: path.to_string_lossy().to_string(),
             message: e.to_string(),
         })?;
 
@@ -105,6 +144,17 @@ impl StatusStore {
             .status = status;
     }
 
+    /// Set status for a task and record the transition in the event journal.
+    /// No-op transitions (same status) are not recorded.
+    pub fn transition(&mut self, name: &str, status: TaskStatus, actor: Actor) {
+        let from = self.get_status(name);
+        if from != status {
+            self.pending_events
+                .push(TaskEvent::new(name, actor, from, status.clone()));
+        }
+        self.set_status(name, status);
+    }
+
     /// Get instance for a task
     pub fn get_instance(&self, name: &str) -> Option<&Instance> {
         self.tasks.get(name).and_then(|s| s.instance.as_ref())
@@ -118,6 +168,112 @@ impl StatusStore {
             .instance = instance;
     }
 
+    /// Record the latest acceptance check report for a task
+    pub fn set_checks(&mut self, name: &str, report: CheckReport) {
+        self.tasks
+            .entry(name.to_string())
+            .or_default()
+            .checks = Some(report);
+    }
+
+    /// Record a message delivered to a task's agent
+    pub fn record_message(&mut self, name: &str, message: SentMessage) {
+        self.tasks
+            .entry(name.to_string())
+            .or_default()
+            .messages
+            .push(message);
+    }
+
+    /// Set scratch flag for a task
+    pub fn set_scratch(&mut self, name: &str, scratch: bool) {
+        self.tasks
+            .entry(name.to_string())
+            .or_default()
+            .scratch = Some(scratch);
+    }
+
+    /// Get the stable ID of a task, allocating the next free one if it has none
+    pub fn assign_id(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.ids.values().max().copied().unwrap_or(0) + 1;
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    /// Validate that status transition is allowed
+    pub fn validate_transition(&self, name: &str, target: TaskStatus) -> Result<()> {
+        let current = self.get_status(name);
+        if !current.can_transition_to(&target) {
+            return Err(WtError::InvalidStateTransition {
+                from: current.display_name().to_string(),
+                to: target.display_name().to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Resolve the real status file path.
+///
+/// Inside a worktree `.wt/status.json` is a symlink to the main project's
+/// file. Writing through the symlink with rename would replace the link, and
+/// locking next to it would not exclude the main project, so follow it.
+fn resolve_status_path() -> PathBuf {
+    let path = Path::new(STATUS_FILE);
+    match fs::read_link(path) {
+        Ok(target) if target.is_absolute() => target,
+        Ok(target) => path.parent().unwrap_or(Path::new(".")).join(target),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Path of the event journal, kept next to the real status file
+fn events_path() -> PathBuf {
+    let file_name = Path::new(EVENTS_FILE).file_name().unwrap_or_default();
+    resolve_status_path().with_file_name(file_name)
+}
+
+/// Exclusive advisory lock on the status file, released on drop.
+struct StatusLock {
+    _file: File,
+}
+
+impl StatusLock {
+    fn acquire() -> Result<Self> {
+        let lock_path = PathBuf::from(format!("{}.lock", resolve_status_path().display()));
+
+        if let Some(parent) = lock_path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                fs::create_dir_all(parent).map_err(|e| WtError::Io {
+                    operation: "create status directory".to_string(),
+                    path: parent.to_string_lossy().to_string(),
+                    message: e.to_string(),
+                })?;
+            }
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .map_err(|e| WtError::Io {
+                operation: "open status lock".to_string(),
+                path: lock_path.to_string_lossy().to_string(),
+                message: e.to_string(),
+            })?;
+
+        file.lock().map_err(|e| WtError::Io {
+            operation: "lock status file".to_string(),
+            path: lock_path.to_string_lossy().to_string(),
+            message: e.to_string(),
+        })?;
+
+        Ok(Self { _file: file })
+    }
 }
 
 #[cfg(test)]
@@ -142,9 +298,11 @@ mod tests {
 
     #[test]
     fn test_task_state_scratch_serialization() {
-        let mut state = TaskState::default();
-        state.status = TaskStatus::Running;
-        state.scratch = Some(true);
+        let mut state = TaskState {
+            status: TaskStatus::Running,
+            scratch: Some(true),
+            ..Default::default()
+        };
 
         let json = serde_json::to_string(&state).unwrap();
         assert!(json.contains("\"scratch\":true"));
@@ -186,6 +344,11 @@ mod tests {
             tmux_session: "wt".to_string(),
             tmux_window: "test".to_string(),
             session_id: None,
+            base_commit: None,
+            base_branch: None,
+            stack_parents: Vec::new(),
+            agent: None,
+            previous_sessions: Vec::new(),
         };
         store.set_instance("test", Some(instance.clone()));
 
@@ -193,6 +356,21 @@ mod tests {
         assert_eq!(got.branch, "wt/test");
     }
 
+    #[test]
+    fn test_status_store_assign_id() {
+        let mut store = StatusStore::default();
+        assert_eq!(store.assign_id("b"), 1);
+        assert_eq!(store.assign_id("a"), 2);
+        // Existing IDs are returned unchanged
+        assert_eq!(store.assign_id("b"), 1);
+
+        // IDs outlive the task's state (reset/archive drop it)
+        store.set_status("b", TaskStatus::Done);
+        store.tasks.remove("b");
+        assert_eq!(store.assign_id("c"), 3);
+        assert_eq!(store.assign_id("b"), 1);
+    }
+
     #[test]
     fn test_status_store_serialize() {
         let mut store = StatusStore::default();
@@ -1,26 +1,39 @@
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use crate::constants::TASKS_DIR;
 use crate::error::{Result, WtError};
-use crate::models::{Instance, StatusStore, Task, TaskFrontmatter, TaskInput, TaskStatus};
-use crate::services::tmux;
+use crate::models::{
+    Actor, Instance, StatusStore, Task, TaskFrontmatter, TaskInput, TaskStatus, WtConfig,
+};
+use crate::services::{git, tmux};
+
+/// `--legacy-index` from the command line, which takes precedence over the config
+static LEGACY_INDEX: OnceLock<bool> = OnceLock::new();
 
 #[derive(Debug, Default)]
 pub struct TaskStore {
     pub tasks: HashMap<String, Task>,
     pub status: StatusStore,
+    /// Address tasks by alphabetical position instead of stable ID
+    /// (`--legacy-index`, else config `legacy_index`)
+    pub legacy_index: bool,
 }
 
 impl TaskStore {
     /// Load all tasks from .wt/tasks/*.md and status from .wt/status.json
     pub fn load() -> Result<Self> {
         let status = StatusStore::load()?;
+        let legacy_index = match LEGACY_INDEX.get() {
+            Some(flag) => *flag,
+            None => WtConfig::load().map(|c| c.legacy_index).unwrap_or(false),
+        };
 
         let dir = Path::new(TASKS_DIR);
         if !dir.exists() {
-            return Ok(Self { tasks: HashMap::new(), status });
+            return Ok(Self { tasks: HashMap::new(), status, legacy_index });
         }
 
         let mut tasks = HashMap::new();
@@ -49,7 +62,32 @@ impl TaskStore {
             }
         }
 
-        Ok(Self { tasks, status })
+        let mut store = Self { tasks, status, legacy_index };
+        // A task's ID is saved the first time it's seen, so it never shifts
+        let names = store.names();
+        if names.iter().any(|name| !store.status.ids.contains_key(name)) {
+            store.update_status(|_| Ok(()))?;
+        }
+        Ok(store)
+    }
+
+    /// Override the config's `legacy_index` for this process (`--legacy-index`)
+    pub fn set_legacy_index(legacy: bool) {
+        let _ = LEGACY_INDEX.set(legacy);
+    }
+
+    /// Task names in name order
+    fn names(&self) -> Vec<String> {
+        self.list().iter().map(|t| t.name().to_string()).collect()
+    }
+
+    /// Give every task in `names` that has no stable ID yet (hand-written task
+    /// files, repos from before IDs existed) the next free ones, in name order,
+    /// so an existing repo keeps the numbers it had as alphabetical indexes.
+    fn assign_missing_ids(status: &mut StatusStore, names: &[String]) {
+        for name in names {
+            status.assign_id(name);
+        }
     }
 
     /// Parse a single task file
@@ -101,8 +139,13 @@ impl TaskStore {
             .ok_or_else(|| WtError::TaskNotFound(name.to_string()))
     }
 
-    /// Resolve a task reference (name or 1-based index) to a task name.
+    /// Resolve a task reference (name or index) to a task name.
     /// Priority: exact name match (task file or scratch) > numeric index
+    ///
+    /// The index is the task's stable ID, allocated when the task is first
+    /// seen and never reused, so adding tasks or changing priorities never
+    /// renumbers anything. With `legacy_index` it is instead the 1-based
+    /// position in the alphabetical order of `list()`.
     pub fn resolve_task_ref(&self, task_ref: &str) -> Result<String> {
         // Priority 1: exact name match in task files
         if self.tasks.contains_key(task_ref) {
@@ -112,9 +155,12 @@ impl TaskStore {
         if self.status.tasks.contains_key(task_ref) {
             return Ok(task_ref.to_string());
         }
-        // Priority 3: try numeric index (1-based)
+        // Priority 3: try numeric index
         if let Ok(index) = task_ref.parse::<usize>() {
-            return self.get_name_by_index(index);
+            if self.legacy_index {
+                return self.get_name_by_index(index);
+            }
+            return self.get_name_by_id(index);
         }
         Err(WtError::TaskNotFound(task_ref.to_string()))
     }
@@ -129,16 +175,36 @@ impl TaskStore {
         Ok(tasks[index - 1].name().to_string())
     }
 
+    /// Get task name by stable ID
+    fn get_name_by_id(&self, id: usize) -> Result<String> {
+        self.status
+            .ids
+            .iter()
+            .find(|(name, task_id)| **task_id == id && self.tasks.contains_key(*name))
+            .map(|(name, _)| name.clone())
+            .ok_or(WtError::InvalidTaskId(id))
+    }
+
+    /// Index each task is displayed with and can be addressed by (see
+    /// `resolve_task_ref`): its stable ID, or its position with `legacy_index`
+    pub fn index_map(&self) -> HashMap<String, usize> {
+        self.list()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, t)| {
+                let index = if self.legacy_index {
+                    Some(i + 1)
+                } else {
+                    self.status.ids.get(t.name()).copied()
+                };
+                index.map(|idx| (t.name().to_string(), idx))
+            })
+            .collect()
+    }
+
     /// Validate that status transition is allowed
     pub fn validate_transition(&self, name: &str, target: TaskStatus) -> Result<()> {
-        let current = self.get_status(name);
-        if !current.can_transition_to(&target) {
-            return Err(WtError::InvalidStateTransition {
-                from: current.display_name().to_string(),
-                to: target.display_name().to_string(),
-            });
-        }
-        Ok(())
+        self.status.validate_transition(name, target)
     }
 
     /// List all tasks sorted by name
@@ -148,6 +214,68 @@ impl TaskStore {
         tasks
     }
 
+    /// List all tasks in scheduling order: higher `priority` first, then longer
+    /// critical path (more work waiting downstream), then name.
+    pub fn list_by_priority(&self) -> Vec<&Task> {
+        let paths = self.critical_path_lengths();
+        let mut tasks = self.list();
+        tasks.sort_by(|a, b| {
+            b.priority()
+                .cmp(&a.priority())
+                .then_with(|| paths[b.name()].cmp(&paths[a.name()]))
+                .then_with(|| a.name().cmp(b.name()))
+        });
+        tasks
+    }
+
+    /// Number of tasks on the longest chain of dependents starting at each
+    /// task, counting the task itself (a task nothing depends on has 1).
+    pub fn critical_path_lengths(&self) -> HashMap<String, usize> {
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+        for task in self.tasks.values() {
+            for dep in task.depends() {
+                dependents.entry(dep.as_str()).or_default().push(task.name());
+            }
+        }
+
+        let mut lengths = HashMap::new();
+        for name in self.tasks.keys() {
+            self.critical_path_recursive(name, &dependents, &mut lengths, &mut HashSet::new());
+        }
+        lengths
+    }
+
+    fn critical_path_recursive(
+        &self,
+        current: &str,
+        dependents: &HashMap<&str, Vec<&str>>,
+        lengths: &mut HashMap<String, usize>,
+        in_progress: &mut HashSet<String>,
+    ) -> usize {
+        if let Some(len) = lengths.get(current) {
+            return *len;
+        }
+        // Cycles are reported by validate; just don't recurse forever
+        if !in_progress.insert(current.to_string()) {
+            return 0;
+        }
+
+        let longest = dependents
+            .get(current)
+            .map(|children| {
+                children
+                    .iter()
+                    .map(|c| self.critical_path_recursive(c, dependents, lengths, in_progress))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .unwrap_or(0);
+
+        in_progress.remove(current);
+        lengths.insert(current.to_string(), longest + 1);
+        longest + 1
+    }
+
     // ==================== Status Accessors ====================
 
     /// Get status for a task (default: Pending)
@@ -155,9 +283,12 @@ impl TaskStore {
         self.status.get_status(name)
     }
 
-    /// Set status for a task
-    pub fn set_status(&mut self, name: &str, status: TaskStatus) {
-        self.status.set_status(name, status);
+    /// Number of tasks (excluding scratch environments) in the given status
+    pub fn count_status(&self, status: TaskStatus) -> usize {
+        self.list()
+            .iter()
+            .filter(|t| self.get_status(t.name()) == status)
+            .count()
     }
 
     /// Get instance for a task
@@ -165,9 +296,15 @@ impl TaskStore {
         self.status.get_instance(name)
     }
 
-    /// Set instance for a task
-    pub fn set_instance(&mut self, name: &str, instance: Option<Instance>) {
-        self.status.set_instance(name, instance);
+    /// Base branch of a task: the one recorded when it was started, else the
+    /// frontmatter `base`, else `config_base`, else main/master.
+    pub fn base_branch(&self, name: &str, config_base: Option<&str>) -> String {
+        let explicit = self
+            .get_instance(name)
+            .and_then(|i| i.base_branch.as_deref())
+            .or_else(|| self.get(name).and_then(|t| t.base()))
+            .or(config_base);
+        git::resolve_base_branch(explicit)
     }
 
     /// Check if a task is a scratch environment
@@ -179,59 +316,77 @@ impl TaskStore {
             .unwrap_or(false)
     }
 
-    /// Set scratch flag for a task
-    pub fn set_scratch(&mut self, name: &str, scratch: bool) {
-        self.status
-            .tasks
-            .entry(name.to_string())
-            .or_default()
-            .scratch = Some(scratch);
-    }
-
     /// Check if a name exists in status.json (for scratch name collision)
     pub fn name_exists_in_status(&self, name: &str) -> bool {
         self.status.tasks.contains_key(name)
     }
 
-    /// Save status to .wt/status.json
-    pub fn save_status(&self) -> Result<()> {
-        self.status.save()
+    /// Update .wt/status.json in a locked read-modify-write transaction.
+    ///
+    /// The closure sees the freshly re-read status, not `self.status`, so
+    /// checks that must not race should be repeated inside it. On success the
+    /// in-memory status is replaced with the committed state.
+    pub fn update_status<T>(&mut self, f: impl FnOnce(&mut StatusStore) -> Result<T>) -> Result<T> {
+        let names = self.names();
+        let (result, status) = StatusStore::update(|status| {
+            Self::assign_missing_ids(status, &names);
+            let result = f(status)?;
+            Ok((result, status.clone()))
+        })?;
+        self.status = status;
+        Ok(result)
+    }
+
+    /// Whether a Running task's tmux window has closed (its agent exited).
+    pub fn window_exited(&self, task_name: &str) -> bool {
+        if self.get_status(task_name) != TaskStatus::Running {
+            return false;
+        }
+        match self.get_instance(task_name) {
+            Some(inst) => !tmux::window_exists(&inst.tmux_session, &inst.tmux_window),
+            None => false,
+        }
     }
 
     /// Check if a task should be auto-marked as Done.
-    /// Condition: status is Running but tmux window is closed.
-    /// Returns: whether auto-mark was performed.
-    pub fn auto_mark_done_if_needed(&mut self, task_name: &str) -> Result<bool> {
-        let status = self.get_status(task_name);
-        if status != TaskStatus::Running {
+    /// Condition: status is Running but tmux window is closed, and the task
+    /// has no acceptance checks (given `config_checks`); those have to pass
+    /// first, see `done::mark_done`.
+    /// Returns: whether auto-mark was performed (and saved).
+    pub fn auto_mark_done_if_needed(&mut self, task_name: &str, config_checks: &[String]) -> Result<bool> {
+        if !self.window_exited(task_name) {
             return Ok(false);
         }
-
-        let state = match self.status.tasks.get(task_name) {
-            Some(s) => s,
-            None => return Ok(false),
-        };
-
-        let instance = match &state.instance {
-            Some(inst) => inst,
-            None => return Ok(false),
-        };
-
-        // Check if tmux window still exists
-        if tmux::window_exists(&instance.tmux_session, &instance.tmux_window) {
+        if self.get(task_name).is_some_and(|t| !t.checks(config_checks).is_empty()) {
             return Ok(false);
         }
 
-        // Window closed, auto-mark as Done
-        self.set_status(task_name, TaskStatus::Done);
-        Ok(true)
+        // Window closed, auto-mark as Done (unless another process got there first)
+        self.update_status(|status| {
+            if status.get_status(task_name) != TaskStatus::Running {
+                return Ok(false);
+            }
+            status.transition(task_name, TaskStatus::Done, Actor::Auto);
+            Ok(true)
+        })
     }
 
-    /// Create a new task from JSON input
-    pub fn create(input: &TaskInput) -> Result<PathBuf> {
+    /// Create a new task from JSON input.
+    /// Returns the task file path and the task's stable ID.
+    pub fn create(input: &TaskInput) -> Result<(PathBuf, usize)> {
         // Validate name
         Self::validate_task_name(&input.name)?;
 
+        let mut problems = input.metadata.validate();
+        problems.extend(input.overrides.validate());
+        if !problems.is_empty() {
+            return Err(WtError::InvalidInput(format!(
+                "Invalid task '{}': {}",
+                input.name,
+                problems.join("; ")
+            )));
+        }
+
         // Ensure directory exists
         let dir = Path::new(TASKS_DIR);
         if !dir.exists() {
@@ -249,12 +404,10 @@ impl TaskStore {
         }
 
         // Validate depends exist
-        if !input.depends.is_empty() {
-            let store = Self::load()?;
-            for dep in &input.depends {
-                if !store.tasks.contains_key(dep) {
-                    return Err(WtError::DependencyNotFound(dep.clone()));
-                }
+        let store = Self::load()?;
+        for dep in &input.depends {
+            if !store.tasks.contains_key(dep) {
+                return Err(WtError::DependencyNotFound(dep.clone()));
             }
         }
 
@@ -266,7 +419,14 @@ impl TaskStore {
             message: e.to_string(),
         })?;
 
-        Ok(file_path)
+        // Existing tasks keep the IDs they were shown with
+        let names = store.names();
+        let id = StatusStore::update(|status| {
+            Self::assign_missing_ids(status, &names);
+            Ok(status.assign_id(&input.name))
+        })?;
+
+        Ok((file_path, id))
     }
 
     /// Validate all tasks and return errors
@@ -274,6 +434,10 @@ impl TaskStore {
         let mut errors = Vec::new();
 
         for task in self.tasks.values() {
+            for problem in task.metadata().validate().into_iter().chain(task.overrides().validate()) {
+                errors.push((task.name().to_string(), problem));
+            }
+
             // Check depends exist
             for dep in task.depends() {
                 if !self.tasks.contains_key(dep) {
@@ -558,6 +722,7 @@ mod tests {
             frontmatter: TaskFrontmatter {
                 name: name.to_string(),
                 depends: depends.into_iter().map(String::from).collect(),
+                ..Default::default()
             },
             content: String::new(),
             file_path: format!("{}.md", name),
@@ -683,6 +848,53 @@ mod tests {
         assert_eq!(list[2].name(), "zebra");
     }
 
+    #[test]
+    fn test_critical_path_lengths() {
+        let mut store = TaskStore::default();
+        store.tasks.insert("a".to_string(), create_test_task("a", vec![]));
+        store.tasks.insert("b".to_string(), create_test_task("b", vec!["a"]));
+        store.tasks.insert("c".to_string(), create_test_task("c", vec!["b"]));
+        store.tasks.insert("d".to_string(), create_test_task("d", vec!["a"]));
+        store.tasks.insert("e".to_string(), create_test_task("e", vec![]));
+
+        let paths = store.critical_path_lengths();
+        assert_eq!(paths["a"], 3);
+        assert_eq!(paths["b"], 2);
+        assert_eq!(paths["c"], 1);
+        assert_eq!(paths["d"], 1);
+        assert_eq!(paths["e"], 1);
+    }
+
+    #[test]
+    fn test_critical_path_lengths_cycle_terminates() {
+        let mut store = TaskStore::default();
+        store.tasks.insert("a".to_string(), create_test_task("a", vec!["b"]));
+        store.tasks.insert("b".to_string(), create_test_task("b", vec!["a"]));
+
+        let paths = store.critical_path_lengths();
+        assert_eq!(paths.len(), 2);
+    }
+
+    #[test]
+    fn test_list_by_priority_ordering() {
+        let mut store = TaskStore::default();
+        store.tasks.insert("aaa".to_string(), create_test_task("aaa", vec![]));
+        store.tasks.insert("base".to_string(), create_test_task("base", vec![]));
+        store.tasks.insert("top".to_string(), create_test_task("top", vec!["base"]));
+        let mut urgent = create_test_task("zzz", vec![]);
+        urgent.frontmatter.priority = 5;
+        store.tasks.insert("zzz".to_string(), urgent);
+
+        let order: Vec<&str> = store.list_by_priority().iter().map(|t| t.name()).collect();
+        // priority, then critical path (base unblocks top), then name
+        assert_eq!(order, vec!["zzz", "base", "aaa", "top"]);
+
+        // Positional indexes keep following the alphabetical list
+        store.legacy_index = true;
+        assert_eq!(store.resolve_task_ref("1").unwrap(), "aaa");
+        assert_eq!(store.resolve_task_ref("4").unwrap(), "zzz");
+    }
+
     #[test]
     fn test_list_empty() {
         let store = TaskStore::default();
@@ -701,7 +913,7 @@ mod tests {
     #[test]
     fn test_store_set_and_get_status() {
         let mut store = TaskStore::default();
-        store.set_status("test", TaskStatus::Running);
+        store.status.set_status("test", TaskStatus::Running);
         assert_eq!(store.get_status("test"), TaskStatus::Running);
     }
 
@@ -714,12 +926,49 @@ mod tests {
             tmux_session: "wt".to_string(),
             tmux_window: "test".to_string(),
             session_id: None,
+            base_commit: None,
+            base_branch: None,
+            stack_parents: Vec::new(),
+            agent: None,
+            previous_sessions: Vec::new(),
         };
-        store.set_instance("test", Some(instance));
+        store.status.set_instance("test", Some(instance));
         assert!(store.get_instance("test").is_some());
         assert_eq!(store.get_instance("test").unwrap().branch, "wt/test");
     }
 
+    #[test]
+    fn test_store_base_branch_precedence() {
+        let mut store = TaskStore::default();
+        let mut task = create_test_task("test", vec![]);
+        task.frontmatter.base = Some("release/2.0".to_string());
+        store.tasks.insert("test".to_string(), task);
+
+        // Frontmatter wins over config
+        assert_eq!(store.base_branch("test", Some("develop")), "release/2.0");
+        // Config applies to tasks without their own base
+        store.tasks.insert("other".to_string(), create_test_task("other", vec![]));
+        assert_eq!(store.base_branch("other", Some("develop")), "develop");
+
+        // The branch recorded at start wins over everything
+        store.status.set_instance(
+            "test",
+            Some(Instance {
+                branch: "wt/test".to_string(),
+                worktree_path: "/path".to_string(),
+                tmux_session: "wt".to_string(),
+                tmux_window: "test".to_string(),
+                session_id: None,
+                base_commit: None,
+                base_branch: Some("hotfix".to_string()),
+                stack_parents: Vec::new(),
+                agent: None,
+                previous_sessions: Vec::new(),
+            }),
+        );
+        assert_eq!(store.base_branch("test", Some("develop")), "hotfix");
+    }
+
     #[test]
     fn test_store_is_scratch_default() {
         let store = TaskStore::default();
@@ -729,10 +978,10 @@ mod tests {
     #[test]
     fn test_store_set_and_get_scratch() {
         let mut store = TaskStore::default();
-        store.set_scratch("test", true);
+        store.status.set_scratch("test", true);
         assert!(store.is_scratch("test"));
 
-        store.set_scratch("test", false);
+        store.status.set_scratch("test", false);
         assert!(!store.is_scratch("test"));
     }
 
@@ -741,7 +990,7 @@ mod tests {
         let mut store = TaskStore::default();
         assert!(!store.name_exists_in_status("test"));
 
-        store.set_status("test", TaskStatus::Running);
+        store.status.set_status("test", TaskStatus::Running);
         assert!(store.name_exists_in_status("test"));
     }
 
@@ -773,7 +1022,7 @@ mod tests {
     #[test]
     fn test_validate_transition_valid() {
         let mut store = TaskStore::default();
-        store.set_status("test", TaskStatus::Running);
+        store.status.set_status("test", TaskStatus::Running);
 
         let result = store.validate_transition("test", TaskStatus::Done);
         assert!(result.is_ok());
@@ -793,6 +1042,13 @@ mod tests {
 
     // ==================== resolve_task_ref Tests ====================
 
+    fn legacy_store() -> TaskStore {
+        TaskStore {
+            legacy_index: true,
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_resolve_task_ref_by_name() {
         let mut store = TaskStore::default();
@@ -805,8 +1061,51 @@ mod tests {
     }
 
     #[test]
-    fn test_resolve_task_ref_by_index() {
+    fn test_resolve_task_ref_by_id() {
+        let mut store = TaskStore::default();
+        store.tasks.insert("beta".to_string(), create_test_task("beta", vec![]));
+        store.status.assign_id("beta");
+        // Added later, sorts first, but doesn't take over ID 1
+        store.tasks.insert("alpha".to_string(), create_test_task("alpha", vec![]));
+        store.status.assign_id("alpha");
+
+        assert_eq!(store.resolve_task_ref("1").unwrap(), "beta");
+        assert_eq!(store.resolve_task_ref("2").unwrap(), "alpha");
+        assert_eq!(store.index_map()["alpha"], 2);
+
+        let err = store.resolve_task_ref("3").unwrap_err().to_string();
+        assert!(err.contains("No task with ID 3"));
+    }
+
+    #[test]
+    fn test_resolve_task_ref_id_of_deleted_task() {
+        let mut store = TaskStore::default();
+        store.tasks.insert("alpha".to_string(), create_test_task("alpha", vec![]));
+        store.status.assign_id("gone");
+        store.status.assign_id("alpha");
+
+        assert!(store.resolve_task_ref("1").is_err());
+        assert_eq!(store.resolve_task_ref("2").unwrap(), "alpha");
+    }
+
+    #[test]
+    fn test_index_map_legacy_positions() {
         let mut store = TaskStore::default();
+        store.tasks.insert("beta".to_string(), create_test_task("beta", vec![]));
+        store.status.assign_id("beta");
+        store.tasks.insert("alpha".to_string(), create_test_task("alpha", vec![]));
+        store.status.assign_id("alpha");
+        store.legacy_index = true;
+
+        let index_map = store.index_map();
+        assert_eq!(index_map["alpha"], 1);
+        assert_eq!(index_map["beta"], 2);
+        assert_eq!(store.resolve_task_ref("1").unwrap(), "alpha");
+    }
+
+    #[test]
+    fn test_resolve_task_ref_by_index() {
+        let mut store = legacy_store();
         store.tasks.insert("alpha".to_string(), create_test_task("alpha", vec![]));
         store.tasks.insert("beta".to_string(), create_test_task("beta", vec![]));
         store.tasks.insert("gamma".to_string(), create_test_task("gamma", vec![]));
@@ -836,7 +1135,7 @@ mod tests {
 
     #[test]
     fn test_resolve_task_ref_index_zero_error() {
-        let mut store = TaskStore::default();
+        let mut store = legacy_store();
         store.tasks.insert("alpha".to_string(), create_test_task("alpha", vec![]));
 
         let result = store.resolve_task_ref("0");
@@ -848,7 +1147,7 @@ mod tests {
 
     #[test]
     fn test_resolve_task_ref_index_out_of_range() {
-        let mut store = TaskStore::default();
+        let mut store = legacy_store();
         store.tasks.insert("alpha".to_string(), create_test_task("alpha", vec![]));
         store.tasks.insert("beta".to_string(), create_test_task("beta", vec![]));
 
@@ -873,7 +1172,7 @@ mod tests {
 
     #[test]
     fn test_resolve_task_ref_empty_store() {
-        let store = TaskStore::default();
+        let store = legacy_store();
 
         let result = store.resolve_task_ref("1");
         assert!(result.is_err());
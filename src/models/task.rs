@@ -1,4 +1,9 @@
-use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::AgentKind;
+use crate::services::template::{self, is_env_name};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -19,6 +24,7 @@ impl TaskStatus {
     /// - Running -> Done
     /// - Running -> Merged (skip done)
     /// - Done -> Merged
+    /// - Done -> Running (resume the agent session)
     /// - Merged -> Archived
     pub fn can_transition_to(&self, target: &TaskStatus) -> bool {
         matches!(
@@ -27,6 +33,7 @@ impl TaskStatus {
                 | (TaskStatus::Running, TaskStatus::Done)
                 | (TaskStatus::Running, TaskStatus::Merged)
                 | (TaskStatus::Done, TaskStatus::Merged)
+                | (TaskStatus::Done, TaskStatus::Running)
                 | (TaskStatus::Merged, TaskStatus::Archived)
         )
     }
@@ -76,21 +83,195 @@ pub struct Instance {
     pub tmux_window: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub session_id: Option<String>,
+    /// Commit the branch was created from (for merge detection)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_commit: Option<String>,
+    /// Branch the task is compared against and merged into
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    /// Unmerged dependencies whose branches this one was stacked on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stack_parents: Vec<String>,
+    /// Agent backend the task was started with (unset: claude)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<AgentKind>,
+    /// Earlier agent sessions on this worktree, oldest first (see `wt restart`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_sessions: Vec<String>,
+}
+
+impl Instance {
+    /// Agent backend driving this instance
+    pub fn agent_kind(&self) -> AgentKind {
+        self.agent.unwrap_or_default()
+    }
 }
 
 /// Frontmatter of task markdown file (definition only, no runtime state)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TaskFrontmatter {
     pub name: String,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub depends: Vec<String>,
+    /// Base branch for this task (overrides config `base_branch`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// Start on top of Done dependency branches (overrides config `stack`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<bool>,
+    /// Scheduling priority, higher starts first (default 0)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub priority: i32,
+    /// Agent backend for this task (overrides config `agent`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<AgentKind>,
+    #[serde(flatten)]
+    pub overrides: AgentOverrides,
+    #[serde(flatten)]
+    pub metadata: TaskMetadata,
+}
+
+fn is_zero(value: &i32) -> bool {
+    *value == 0
+}
+
+/// Planning metadata about a task. Shared by the task file, `wt create` input
+/// and the JSON outputs, so it survives the whole lifecycle unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskMetadata {
+    #[serde(default, deserialize_with = "scalar_list", skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, deserialize_with = "optional_scalar", skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Free-form effort estimate (e.g. "2h", "3d")
+    #[serde(default, deserialize_with = "optional_scalar", skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<String>,
+    /// Acceptance checks: shell commands that must succeed in the worktree
+    /// before the task can be marked done
+    #[serde(
+        default,
+        alias = "checks",
+        deserialize_with = "scalar_list",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub acceptance: Vec<String>,
+    /// Free-form extra data, kept as-is
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub meta: BTreeMap<String, serde_json::Value>,
+}
+
+/// Per-task launch settings, applied over the project config by `wt start`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentOverrides {
+    /// Replaces `claude_command` (or `shell_command` for the shell agent)
+    #[serde(default, deserialize_with = "optional_scalar", skip_serializing_if = "Option::is_none")]
+    pub agent_command: Option<String>,
+    #[serde(default, deserialize_with = "optional_scalar", skip_serializing_if = "Option::is_none")]
+    pub start_args: Option<String>,
+    /// Model the agent runs with (Claude: `--model`)
+    #[serde(default, deserialize_with = "optional_scalar", skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Extra environment variables, merged over the config's `env`
+    #[serde(default, deserialize_with = "scalar_map", skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(default, deserialize_with = "optional_scalar", skip_serializing_if = "Option::is_none")]
+    pub init_script: Option<String>,
+}
+
+impl AgentOverrides {
+    /// Problems with the overrides, one message each
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (field, value) in [
+            ("agent_command", &self.agent_command),
+            ("model", &self.model),
+        ] {
+            if value.as_deref().is_some_and(|v| v.trim().is_empty()) {
+                errors.push(format!("{} is empty", field));
+            }
+        }
+        for name in self.env.keys() {
+            if !is_env_name(name) {
+                errors.push(format!("env name '{}' is not a valid variable name", name));
+            }
+        }
+        for (field, value) in [
+            ("agent_command", &self.agent_command),
+            ("start_args", &self.start_args),
+            ("init_script", &self.init_script),
+        ] {
+            if let Some(Err(e)) = value.as_deref().map(template::check) {
+                errors.push(format!("{}: {}", field, e));
+            }
+        }
+        errors
+    }
+}
+
+// Flattened fields lose serde_yaml's scalar-to-string coercion, so accept
+// `estimate: 3` or `- true` explicitly.
+fn scalar_to_string<E: serde::de::Error>(value: serde_json::Value) -> Result<String, E> {
+    match value {
+        serde_json::Value::String(s) => Ok(s),
+        serde_json::Value::Number(n) => Ok(n.to_string()),
+        serde_json::Value::Bool(b) => Ok(b.to_string()),
+        other => Err(E::custom(format!("expected a string, found {}", other))),
+    }
+}
+
+fn optional_scalar<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Null => Ok(None),
+        value => scalar_to_string(value).map(Some),
+    }
+}
+
+fn scalar_map<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error> {
+    BTreeMap::<String, serde_json::Value>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(key, value)| scalar_to_string(value).map(|v| (key, v)))
+        .collect()
+}
+
+fn scalar_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    Vec::<serde_json::Value>::deserialize(deserializer)?
+        .into_iter()
+        .map(scalar_to_string)
+        .collect()
+}
+
+impl TaskMetadata {
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Problems with the metadata, one message each
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+        for tag in &self.tags {
+            if tag.trim().is_empty() {
+                errors.push("tags contains an empty tag".to_string());
+            } else if !seen.insert(tag.as_str()) {
+                errors.push(format!("duplicate tag '{}'", tag));
+            }
+        }
+        if self.owner.as_deref().is_some_and(|o| o.trim().is_empty()) {
+            errors.push("owner is empty".to_string());
+        }
+        for (i, check) in self.acceptance.iter().enumerate() {
+            if check.trim().is_empty() {
+                errors.push(format!("acceptance check {} is empty", i + 1));
+            }
+        }
+        errors
+    }
 }
 
 /// Full task with frontmatter and content
 #[derive(Debug, Clone)]
 pub struct Task {
     pub frontmatter: TaskFrontmatter,
-    #[allow(dead_code)]
     pub content: String,
     pub file_path: String,
 }
@@ -103,15 +284,62 @@ impl Task {
     pub fn depends(&self) -> &[String] {
         &self.frontmatter.depends
     }
+
+    pub fn base(&self) -> Option<&str> {
+        self.frontmatter.base.as_deref()
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.frontmatter.priority
+    }
+
+    pub fn overrides(&self) -> &AgentOverrides {
+        &self.frontmatter.overrides
+    }
+
+    pub fn metadata(&self) -> &TaskMetadata {
+        &self.frontmatter.metadata
+    }
+
+    /// Acceptance checks to run before done: the task's own, else the config's
+    pub fn checks<'a>(&'a self, config_checks: &'a [String]) -> &'a [String] {
+        if self.frontmatter.metadata.acceptance.is_empty() {
+            config_checks
+        } else {
+            &self.frontmatter.metadata.acceptance
+        }
+    }
+
+    /// Agent backend to start the task with, given the config default
+    pub fn agent(&self, config_agent: AgentKind) -> AgentKind {
+        self.frontmatter.agent.unwrap_or(config_agent)
+    }
+
+    /// Whether the task stacks on its dependencies, given the config default
+    pub fn is_stacked(&self, config_stack: bool) -> bool {
+        self.frontmatter.stack.unwrap_or(config_stack)
+    }
 }
 
 /// Input for creating a task via JSON
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct TaskInput {
     pub name: String,
     #[serde(default)]
     pub depends: Vec<String>,
     pub description: String,
+    #[serde(default)]
+    pub base: Option<String>,
+    #[serde(default)]
+    pub stack: Option<bool>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub agent: Option<AgentKind>,
+    #[serde(flatten)]
+    pub overrides: AgentOverrides,
+    #[serde(flatten)]
+    pub metadata: TaskMetadata,
 }
 
 impl TaskInput {
@@ -119,6 +347,12 @@ impl TaskInput {
         let frontmatter = TaskFrontmatter {
             name: self.name.clone(),
             depends: self.depends.clone(),
+            base: self.base.clone(),
+            stack: self.stack,
+            priority: self.priority,
+            agent: self.agent,
+            overrides: self.overrides.clone(),
+            metadata: self.metadata.clone(),
         };
         format_task_markdown(&frontmatter, &self.description)
     }
@@ -149,13 +383,14 @@ mod tests {
         assert!(TaskStatus::Running.can_transition_to(&TaskStatus::Done));
         assert!(TaskStatus::Running.can_transition_to(&TaskStatus::Merged));
         assert!(TaskStatus::Done.can_transition_to(&TaskStatus::Merged));
+        assert!(TaskStatus::Done.can_transition_to(&TaskStatus::Running));
         assert!(TaskStatus::Merged.can_transition_to(&TaskStatus::Archived));
 
         // Invalid transitions
         assert!(!TaskStatus::Pending.can_transition_to(&TaskStatus::Done));
         assert!(!TaskStatus::Pending.can_transition_to(&TaskStatus::Merged));
         assert!(!TaskStatus::Pending.can_transition_to(&TaskStatus::Archived));
-        assert!(!TaskStatus::Done.can_transition_to(&TaskStatus::Running));
+        assert!(!TaskStatus::Merged.can_transition_to(&TaskStatus::Running));
         assert!(!TaskStatus::Done.can_transition_to(&TaskStatus::Archived));
         assert!(!TaskStatus::Merged.can_transition_to(&TaskStatus::Pending));
         assert!(!TaskStatus::Archived.can_transition_to(&TaskStatus::Pending));
@@ -232,6 +467,7 @@ mod tests {
             name: "auth".to_string(),
             depends: vec![],
             description: "Implement authentication".to_string(),
+            ..Default::default()
         };
         let md = input.to_markdown();
 
@@ -248,6 +484,7 @@ mod tests {
             name: "api".to_string(),
             depends: vec!["auth".to_string(), "database".to_string()],
             description: "Build API".to_string(),
+            ..Default::default()
         };
         let md = input.to_markdown();
 
@@ -263,6 +500,7 @@ mod tests {
             name: "feature".to_string(),
             depends: vec![],
             description: "Line 1\n\nLine 2\n- bullet".to_string(),
+            ..Default::default()
         };
         let md = input.to_markdown();
 
@@ -275,6 +513,7 @@ mod tests {
             name: "unicode".to_string(),
             depends: vec![],
             description: "实现用户认证 🔐".to_string(),
+            ..Default::default()
         };
         let md = input.to_markdown();
 
@@ -320,7 +559,7 @@ mod tests {
     fn test_task_frontmatter_serialize_minimal() {
         let fm = TaskFrontmatter {
             name: "test".to_string(),
-            depends: vec![],
+            ..Default::default()
         };
         let yaml = serde_yaml::to_string(&fm).unwrap();
 
@@ -337,6 +576,7 @@ mod tests {
         let fm = TaskFrontmatter {
             name: "test".to_string(),
             depends: vec!["dep1".to_string()],
+            ..Default::default()
         };
         let yaml = serde_yaml::to_string(&fm).unwrap();
 
@@ -352,6 +592,157 @@ mod tests {
 
         assert_eq!(fm.name, "test");
         assert!(fm.depends.is_empty());
+        assert!(fm.base.is_none());
+    }
+
+    #[test]
+    fn test_task_frontmatter_metadata_roundtrip() {
+        let yaml = r#"
+name: auth
+tags: [backend, security]
+owner: alice
+estimate: 2h
+acceptance:
+  - cargo test
+meta:
+  ticket: AUTH-12
+  points: 3
+"#;
+        let fm: TaskFrontmatter = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(fm.metadata.tags, vec!["backend", "security"]);
+        assert_eq!(fm.metadata.owner.as_deref(), Some("alice"));
+        assert_eq!(fm.metadata.estimate.as_deref(), Some("2h"));
+        assert_eq!(fm.metadata.acceptance, vec!["cargo test"]);
+        assert_eq!(fm.metadata.meta["points"], serde_json::json!(3));
+
+        let reparsed: TaskFrontmatter =
+            serde_yaml::from_str(&serde_yaml::to_string(&fm).unwrap()).unwrap();
+        assert_eq!(reparsed.metadata, fm.metadata);
+    }
+
+    #[test]
+    fn test_task_metadata_accepts_scalars() {
+        let yaml = "name: t\nestimate: 3\nowner: 42\ntags: [2024]\nchecks:\n  - true\n";
+        let fm: TaskFrontmatter = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(fm.metadata.estimate.as_deref(), Some("3"));
+        assert_eq!(fm.metadata.owner.as_deref(), Some("42"));
+        assert_eq!(fm.metadata.tags, vec!["2024"]);
+        assert_eq!(fm.metadata.acceptance, vec!["true"]);
+    }
+
+    #[test]
+    fn test_task_metadata_omitted_when_empty() {
+        let fm = TaskFrontmatter {
+            name: "test".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(serde_yaml::to_string(&fm).unwrap().trim(), "name: test");
+    }
+
+    #[test]
+    fn test_task_input_metadata_to_markdown() {
+        let json = r#"{"name": "auth", "description": "desc", "tags": ["backend"], "meta": {"ticket": "AUTH-12"}}"#;
+        let input: TaskInput = serde_json::from_str(json).unwrap();
+        let md = input.to_markdown();
+
+        assert!(md.contains("tags:\n- backend"));
+        assert!(md.contains("ticket: AUTH-12"));
+    }
+
+    #[test]
+    fn test_task_metadata_validate() {
+        let metadata = TaskMetadata {
+            tags: vec!["a".to_string(), "a".to_string(), " ".to_string()],
+            owner: Some(String::new()),
+            acceptance: vec!["make test".to_string(), "".to_string()],
+            ..Default::default()
+        };
+        let errors = metadata.validate();
+        assert!(errors.contains(&"duplicate tag 'a'".to_string()));
+        assert!(errors.contains(&"tags contains an empty tag".to_string()));
+        assert!(errors.contains(&"owner is empty".to_string()));
+        assert!(errors.contains(&"acceptance check 2 is empty".to_string()));
+        assert!(TaskMetadata::default().validate().is_empty());
+    }
+
+    #[test]
+    fn test_task_frontmatter_priority() {
+        let fm: TaskFrontmatter = serde_yaml::from_str("name: test\npriority: 2\n").unwrap();
+        assert_eq!(fm.priority, 2);
+
+        // Default priority is not written back
+        let fm = TaskFrontmatter {
+            name: "test".to_string(),
+            ..Default::default()
+        };
+        assert!(!serde_yaml::to_string(&fm).unwrap().contains("priority"));
+    }
+
+    #[test]
+    fn test_task_frontmatter_deserialize_base() {
+        let yaml = "name: test\nbase: release/2.0\n";
+        let fm: TaskFrontmatter = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(fm.base, Some("release/2.0".to_string()));
+    }
+
+    #[test]
+    fn test_task_frontmatter_agent_overrides_config() {
+        let fm: TaskFrontmatter = serde_yaml::from_str("name: test\nagent: shell\n").unwrap();
+        let task = Task {
+            frontmatter: fm,
+            content: String::new(),
+            file_path: "path".to_string(),
+        };
+        assert_eq!(task.agent(AgentKind::Claude), AgentKind::Shell);
+
+        let task = Task {
+            frontmatter: TaskFrontmatter {
+                name: "test".to_string(),
+                ..Default::default()
+            },
+            content: String::new(),
+            file_path: "path".to_string(),
+        };
+        assert_eq!(task.agent(AgentKind::Shell), AgentKind::Shell);
+        assert!(!serde_yaml::to_string(&task.frontmatter).unwrap().contains("agent"));
+    }
+
+    #[test]
+    fn test_task_frontmatter_agent_overrides() {
+        let yaml = "name: test\nstart_args: -p hi\nmodel: opus\nenv:\n  PORT: 3000\n  DEBUG: true\n";
+        let fm: TaskFrontmatter = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(fm.overrides.start_args.as_deref(), Some("-p hi"));
+        assert_eq!(fm.overrides.model.as_deref(), Some("opus"));
+        assert_eq!(fm.overrides.env["PORT"], "3000");
+        assert_eq!(fm.overrides.env["DEBUG"], "true");
+        assert!(fm.overrides.validate().is_empty());
+
+        let yaml = serde_yaml::to_string(&fm).unwrap();
+        assert!(yaml.contains("model: opus"));
+        assert!(!yaml.contains("agent_command"));
+    }
+
+    #[test]
+    fn test_agent_overrides_validate() {
+        let overrides = AgentOverrides {
+            agent_command: Some(" ".to_string()),
+            env: BTreeMap::from([
+                ("OK_1".to_string(), "x".to_string()),
+                ("1BAD".to_string(), "x".to_string()),
+                ("NO-DASH".to_string(), "x".to_string()),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            overrides.validate(),
+            vec![
+                "agent_command is empty",
+                "env name '1BAD' is not a valid variable name",
+                "env name 'NO-DASH' is not a valid variable name",
+            ]
+        );
     }
 
     // ==================== Task Tests ====================
@@ -362,6 +753,7 @@ mod tests {
             frontmatter: TaskFrontmatter {
                 name: "myname".to_string(),
                 depends: vec!["dep1".to_string()],
+                ..Default::default()
             },
             content: "content".to_string(),
             file_path: "path".to_string(),
@@ -0,0 +1,490 @@
+//! Agent backends: how a task's coding agent is launched, resumed, and how
+//! its transcript is found and read.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use crate::constants::{AGENT_LOG_FILE, LOGS_DIR};
+use crate::error::{Result, WtError};
+use crate::models::{AgentKind, Instance, LaunchSettings, MessageFormat};
+
+use super::command::shell_quote;
+use super::git;
+use super::template::TemplateContext;
+use super::tmux;
+use super::transcript::{self, EventKind, TranscriptEvent, TranscriptMetrics};
+
+/// What a backend needs to know to build the launch command for a task
+pub struct LaunchSpec<'a> {
+    pub task: &'a str,
+    pub session_id: &'a str,
+    /// Main repository root (the tmux window runs in the worktree)
+    pub root: &'a Path,
+    /// Variables for `start_args` and `shell_command`
+    pub vars: &'a TemplateContext,
+    /// Summary of an earlier session to continue from (`wt restart --keep-history`).
+    /// Passed alongside the task prompt rather than prepended to it: the
+    /// prompt is part of the user's `start_args` template, so there's no
+    /// reliable place in it to insert text.
+    pub handoff: Option<&'a str>,
+}
+
+pub trait AgentBackend {
+    /// Shell command run in the task's tmux window (before any init_script wrapping)
+    fn launch_command(&self, settings: &LaunchSettings, spec: &LaunchSpec) -> Result<String>;
+
+    /// Shell command that picks up the instance's session again with the
+    /// task's agent command and model, run from its worktree. None if the
+    /// agent cannot resume.
+    fn resume_command(
+        &self,
+        settings: &LaunchSettings,
+        vars: &TemplateContext,
+        instance: &Instance,
+    ) -> Result<Option<String>>;
+
+    /// Transcript file of a task's instance
+    fn find_transcript(&self, task: &str, instance: &Instance) -> Option<PathBuf>;
+
+    /// Transcripts of every session the instance has run, oldest first
+    fn session_transcripts(&self, task: &str, instance: &Instance) -> Vec<PathBuf> {
+        self.find_transcript(task, instance).into_iter().collect()
+    }
+
+    /// Metrics of all of the instance's sessions combined
+    fn task_metrics(&self, task: &str, instance: &Instance) -> Option<TranscriptMetrics> {
+        let sessions = self
+            .session_transcripts(task, instance)
+            .iter()
+            .filter_map(|path| self.parse_transcript(path))
+            .collect();
+        transcript::aggregate(sessions)
+    }
+
+    /// Parse a transcript into metrics
+    fn parse_transcript(&self, path: &Path) -> Option<TranscriptMetrics>;
+
+    /// Last `n` agent messages from a transcript
+    fn last_messages(&self, path: &Path, n: usize) -> Option<Vec<String>>;
+
+    /// Every event of a transcript, in order (by default, its messages as text)
+    fn events(&self, path: &Path) -> Option<Vec<TranscriptEvent>> {
+        let messages = self.last_messages(path, usize::MAX)?;
+        Some(messages.into_iter().map(|m| TranscriptEvent::new(EventKind::Text, m)).collect())
+    }
+
+    /// Text that, near the bottom of the tmux pane, means the agent is
+    /// blocked on a prompt
+    fn prompt_patterns(&self) -> &'static [&'static str];
+
+    /// Line typed into the tmux window to deliver a follow-up message
+    fn frame_message(&self, _settings: &LaunchSettings, text: &str) -> (MessageFormat, String) {
+        (MessageFormat::Text, text.to_string())
+    }
+}
+
+/// Get the backend for an agent kind
+pub fn backend(kind: AgentKind) -> &'static dyn AgentBackend {
+    match kind {
+        AgentKind::Claude => &ClaudeBackend,
+        AgentKind::Shell => &ShellBackend,
+    }
+}
+
+/// Get the backend an instance was started with
+pub fn for_instance(instance: &Instance) -> &'static dyn AgentBackend {
+    backend(instance.agent_kind())
+}
+
+/// Whether a running instance is blocked waiting for the user: its tmux pane
+/// shows a known prompt. A pending tool call in the transcript is not enough
+/// on its own, since long-running tools (builds, test suites) look the same
+pub fn waiting_for_input(instance: &Instance) -> bool {
+    let backend = for_instance(instance);
+    tmux::capture_pane(&instance.tmux_session, &instance.tmux_window)
+        .is_some_and(|pane| shows_prompt(&pane, backend.prompt_patterns()))
+}
+
+/// Lines from the bottom of the pane searched for a prompt
+const PROMPT_LINES: usize = 10;
+
+fn shows_prompt(pane: &str, patterns: &[&str]) -> bool {
+    pane.lines()
+        .rev()
+        .filter(|line| !line.trim().is_empty())
+        .take(PROMPT_LINES)
+        .any(|line| patterns.iter().any(|p| line.contains(p)))
+}
+
+/// Claude Code CLI: `claude_command start_args [--model <model>] --session-id <id>`, JSONL
+/// transcripts under ~/.claude/projects
+pub struct ClaudeBackend;
+
+impl ClaudeBackend {
+    fn command(settings: &LaunchSettings) -> &str {
+        settings.command.as_deref().unwrap_or("claude")
+    }
+
+    fn model_arg(settings: &LaunchSettings) -> String {
+        settings
+            .model
+            .as_ref()
+            .map(|m| format!(" --model {}", m))
+            .unwrap_or_default()
+    }
+}
+
+impl AgentBackend for ClaudeBackend {
+    /// The handoff goes in as `--append-system-prompt`, which reaches the
+    /// session whatever `start_args` does with the task prompt
+    fn launch_command(&self, settings: &LaunchSettings, spec: &LaunchSpec) -> Result<String> {
+        let handoff = spec
+            .handoff
+            .map(|h| format!(" --append-system-prompt {}", shell_quote(h)))
+            .unwrap_or_default();
+        Ok(format!(
+            "{} {}{}{} --session-id {}",
+            spec.vars.render(Self::command(settings))?,
+            spec.vars.render(&settings.start_args)?,
+            Self::model_arg(settings),
+            handoff,
+            spec.session_id
+        ))
+    }
+
+    fn resume_command(
+        &self,
+        settings: &LaunchSettings,
+        vars: &TemplateContext,
+        instance: &Instance,
+    ) -> Result<Option<String>> {
+        let Some(session_id) = &instance.session_id else {
+            return Ok(None);
+        };
+        // Not start_args: they carry the task prompt (and print mode)
+        Ok(Some(format!(
+            "{}{} -r {}",
+            vars.render(Self::command(settings))?,
+            Self::model_arg(settings),
+            session_id
+        )))
+    }
+
+    fn find_transcript(&self, _task: &str, instance: &Instance) -> Option<PathBuf> {
+        transcript::find_transcript_for_instance(instance)
+    }
+
+    /// The sessions wt started (restarts included), plus those linked to
+    /// them, e.g. by `claude -r` or compaction
+    fn session_transcripts(&self, task: &str, instance: &Instance) -> Vec<PathBuf> {
+        let mut seeds: Vec<PathBuf> = instance
+            .previous_sessions
+            .iter()
+            .chain(&instance.session_id)
+            .filter_map(|sid| transcript::transcript_path(&instance.worktree_path, sid))
+            .filter(|p| p.exists())
+            .collect();
+        if seeds.is_empty() {
+            seeds.extend(self.find_transcript(task, instance));
+        }
+        transcript::linked_transcripts(&seeds)
+    }
+
+    fn parse_transcript(&self, path: &Path) -> Option<TranscriptMetrics> {
+        transcript::parse_transcript(path)
+    }
+
+    fn last_messages(&self, path: &Path, n: usize) -> Option<Vec<String>> {
+        transcript::get_last_messages(path, n)
+    }
+
+    fn events(&self, path: &Path) -> Option<Vec<TranscriptEvent>> {
+        transcript::get_events(path)
+    }
+
+    /// With `--input-format=stream-json` Claude reads JSON user messages,
+    /// one per line
+    fn frame_message(&self, settings: &LaunchSettings, text: &str) -> (MessageFormat, String) {
+        let args: Vec<&str> = settings.start_args.split_whitespace().collect();
+        let stream_json = args.contains(&"--input-format=stream-json")
+            || args.windows(2).any(|w| w == ["--input-format", "stream-json"]);
+        if !stream_json {
+            return (MessageFormat::Text, text.to_string());
+        }
+        let message = serde_json::json!({
+            "type": "user",
+            "message": {
+                "role": "user",
+                "content": [{ "type": "text", "text": text }],
+            },
+        });
+        (MessageFormat::StreamJson, message.to_string())
+    }
+
+    fn prompt_patterns(&self) -> &'static [&'static str] {
+        &[
+            "Do you want to proceed?",
+            "Do you want to make this edit",
+            "Do you want to create",
+            "Do you trust the files in this folder?",
+        ]
+    }
+}
+
+/// Any command line (`shell_command`). Its combined output is tee'd into
+/// .wt/logs/<task>/agent.log, which serves as a plain-text transcript.
+pub struct ShellBackend;
+
+impl ShellBackend {
+    fn log_file(root: &Path, task: &str) -> PathBuf {
+        root.join(LOGS_DIR).join(task).join(AGENT_LOG_FILE)
+    }
+}
+
+impl AgentBackend for ShellBackend {
+    fn launch_command(&self, settings: &LaunchSettings, spec: &LaunchSpec) -> Result<String> {
+        let command = settings.command.as_deref().ok_or_else(|| {
+            WtError::InvalidInput(format!(
+                "Task '{}' uses the shell agent but has no command: set shell_command in .wt/config.yaml or agent_command in the task",
+                spec.task
+            ))
+        })?;
+        let log = Self::log_file(spec.root, spec.task);
+        let log_dir = log.parent().unwrap_or(spec.root);
+        let handoff = spec
+            .handoff
+            .map(|h| format!("export WT_HANDOFF={} && ", shell_quote(h)))
+            .unwrap_or_default();
+        Ok(format!(
+            "{}mkdir -p '{}' && ({}) 2>&1 | tee '{}'",
+            handoff,
+            log_dir.display(),
+            spec.vars.render(command)?,
+            log.display()
+        ))
+    }
+
+    fn resume_command(
+        &self,
+        _settings: &LaunchSettings,
+        _vars: &TemplateContext,
+        _instance: &Instance,
+    ) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn find_transcript(&self, task: &str, _instance: &Instance) -> Option<PathBuf> {
+        let root = git::get_repo_root().ok()?;
+        Some(Self::log_file(Path::new(&root), task)).filter(|p| p.exists())
+    }
+
+    fn parse_transcript(&self, path: &Path) -> Option<TranscriptMetrics> {
+        let content = fs::read_to_string(path).ok()?;
+        let metadata = fs::metadata(path).ok()?;
+        let timestamp = |t: std::io::Result<std::time::SystemTime>| t.ok().map(DateTime::<Utc>::from);
+
+        Some(TranscriptMetrics {
+            summary: content
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .map(|line| line.trim().to_string()),
+            started_at: timestamp(metadata.created()),
+            finished_at: timestamp(metadata.modified()),
+            ..Default::default()
+        })
+    }
+
+    fn last_messages(&self, path: &Path, n: usize) -> Option<Vec<String>> {
+        let content = fs::read_to_string(path).ok()?;
+        let lines: Vec<String> = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.to_string())
+            .collect();
+        let start = lines.len().saturating_sub(n);
+        Some(lines[start..].to_vec())
+    }
+
+    fn prompt_patterns(&self) -> &'static [&'static str] {
+        &["(y/n)", "[y/N]", "[Y/n]", "(yes/no)", "Password:", "Press Enter to continue"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::WtConfig;
+    use tempfile::TempDir;
+
+    fn vars() -> TemplateContext {
+        TemplateContext::new("auth", "/repo").with_instance(&instance(None))
+    }
+
+    fn spec<'a>(root: &'a Path, vars: &'a TemplateContext) -> LaunchSpec<'a> {
+        LaunchSpec {
+            task: "auth",
+            session_id: "1234-abcd",
+            root,
+            vars,
+            handoff: None,
+        }
+    }
+
+    fn instance(agent: Option<AgentKind>) -> Instance {
+        Instance {
+            branch: "wt/auth-1234".to_string(),
+            worktree_path: "/repo/.wt/worktrees/auth".to_string(),
+            tmux_session: "wt".to_string(),
+            tmux_window: "auth".to_string(),
+            session_id: Some("1234-abcd".to_string()),
+            base_commit: None,
+            base_branch: None,
+            stack_parents: Vec::new(),
+            agent,
+            previous_sessions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_launch_rejects_unknown_variable() {
+        let settings = WtConfig::from_str("start_args: -p ${tsak}\n").unwrap().launch_settings(None);
+        let (root, vars) = (PathBuf::from("/repo"), vars());
+
+        let err = ClaudeBackend.launch_command(&settings, &spec(&root, &vars)).unwrap_err();
+        assert!(err.to_string().contains("unknown variable"));
+    }
+
+    #[test]
+    fn test_claude_launch_and_resume() {
+        let config = WtConfig::from_str("claude_command: ccc\nstart_args: -p ${task}\n").unwrap();
+        let mut settings = config.launch_settings(None);
+        let (root, vars) = (PathBuf::from("/repo"), vars());
+
+        let cmd = ClaudeBackend.launch_command(&settings, &spec(&root, &vars)).unwrap();
+        assert_eq!(cmd, "ccc -p auth --session-id 1234-abcd");
+
+        settings.model = Some("opus".to_string());
+        let cmd = ClaudeBackend.launch_command(&settings, &spec(&root, &vars)).unwrap();
+        assert_eq!(cmd, "ccc -p auth --model opus --session-id 1234-abcd");
+    }
+
+    #[test]
+    fn test_claude_resume_keeps_model_not_start_args() {
+        let config = WtConfig::from_str("claude_command: ccc\nstart_args: --permission-mode acceptEdits -p ${task}\n").unwrap();
+        let mut settings = config.launch_settings(None);
+        settings.model = Some("opus".to_string());
+
+        let cmd = ClaudeBackend.resume_command(&settings, &vars(), &instance(None)).unwrap();
+        assert_eq!(cmd.as_deref(), Some("ccc --model opus -r 1234-abcd"));
+
+        let fresh = Instance { session_id: None, ..instance(None) };
+        assert_eq!(ClaudeBackend.resume_command(&settings, &vars(), &fresh).unwrap(), None);
+    }
+
+    #[test]
+    fn test_launch_passes_handoff() {
+        let root = PathBuf::from("/repo");
+        let vars = vars();
+        let spec = LaunchSpec {
+            handoff: Some("it's half done"),
+            ..spec(&root, &vars)
+        };
+
+        let config = WtConfig::from_str("claude_command: ccc\nstart_args: -p ${task}\n").unwrap();
+        let cmd = ClaudeBackend.launch_command(&config.launch_settings(None), &spec).unwrap();
+        assert_eq!(
+            cmd,
+            r"ccc -p auth --append-system-prompt 'it'\''s half done' --session-id 1234-abcd"
+        );
+
+        let config = WtConfig::from_str("agent: shell\nshell_command: ./agent.sh\n").unwrap();
+        let cmd = ShellBackend.launch_command(&config.launch_settings(None), &spec).unwrap();
+        assert!(cmd.starts_with(r"export WT_HANDOFF='it'\''s half done' && mkdir -p"), "cmd: {}", cmd);
+    }
+
+    #[test]
+    fn test_shell_launch_tees_output() {
+        let config = WtConfig::from_str("agent: shell\nshell_command: ./agent.sh ${task}\n").unwrap();
+        let settings = config.launch_settings(None);
+        let (root, vars) = (PathBuf::from("/repo"), vars());
+
+        let cmd = ShellBackend.launch_command(&settings, &spec(&root, &vars)).unwrap();
+        assert_eq!(
+            cmd,
+            "mkdir -p '/repo/.wt/logs/auth' && (./agent.sh auth) 2>&1 | tee '/repo/.wt/logs/auth/agent.log'"
+        );
+        assert!(ShellBackend.resume_command(&settings, &vars, &instance(Some(AgentKind::Shell))).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_shell_launch_requires_command() {
+        let settings = WtConfig::from_str("agent: shell\n").unwrap().launch_settings(None);
+        let (root, vars) = (PathBuf::from("/repo"), vars());
+
+        let err = ShellBackend.launch_command(&settings, &spec(&root, &vars)).unwrap_err();
+        assert!(err.to_string().contains("set shell_command"));
+    }
+
+    #[test]
+    fn test_shell_transcript_is_plain_text() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("agent.log");
+        fs::write(&path, "starting\n\nstep 1\nstep 2\n\n").unwrap();
+
+        let metrics = ShellBackend.parse_transcript(&path).unwrap();
+        assert_eq!(metrics.summary.as_deref(), Some("step 2"));
+        assert_eq!(metrics.context_percent(), 0);
+        assert_eq!(
+            ShellBackend.last_messages(&path, 2).unwrap(),
+            vec!["step 1".to_string(), "step 2".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_backend_for_instance() {
+        let settings = WtConfig::from_str("{}").unwrap().launch_settings(None);
+        assert!(for_instance(&instance(None)).resume_command(&settings, &vars(), &instance(None)).unwrap().is_some());
+        let shell = instance(Some(AgentKind::Shell));
+        assert!(for_instance(&shell).resume_command(&settings, &vars(), &shell).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_shows_prompt_near_bottom_of_pane() {
+        let patterns = ClaudeBackend.prompt_patterns();
+        let pane = "Bash command\n  rm -rf dist\n\n Do you want to proceed?\n ❯ 1. Yes\n   2. No\n\n\n";
+        assert!(shows_prompt(pane, patterns));
+
+        // Scrolled far up: an answered prompt no longer counts
+        let old = format!("{}{}", pane, "output\n".repeat(PROMPT_LINES));
+        assert!(!shows_prompt(&old, patterns));
+        assert!(shows_prompt("Overwrite? [y/N] ", ShellBackend.prompt_patterns()));
+    }
+
+    #[test]
+    fn test_long_running_tool_is_not_waiting() {
+        // A tool call still producing output has no prompt at the bottom
+        let pane = "⏺ Bash(cargo test --workspace)\n  ⎿  Compiling wt v0.1.0\n     Running tests/cli.rs\n";
+        assert!(!shows_prompt(pane, ClaudeBackend.prompt_patterns()));
+    }
+
+    #[test]
+    fn test_frame_message_follows_input_format() {
+        let config = WtConfig::from_str("start_args: --input-format stream-json -p hi").unwrap();
+        let (format, line) = ClaudeBackend.frame_message(&config.launch_settings(None), "use \"rg\"\nplease");
+        assert_eq!(format, MessageFormat::StreamJson);
+        assert!(!line.contains('\n'));
+        let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(json["type"], "user");
+        assert_eq!(json["message"]["content"][0]["text"], "use \"rg\"\nplease");
+
+        let config = WtConfig::from_str("start_args: --verbose").unwrap();
+        assert_eq!(
+            ClaudeBackend.frame_message(&config.launch_settings(None), "hi"),
+            (MessageFormat::Text, "hi".to_string())
+        );
+        assert_eq!(ShellBackend.frame_message(&config.launch_settings(None), "y").0, MessageFormat::Text);
+    }
+}
@@ -1,10 +1,16 @@
 //! Command execution utilities for git and tmux operations.
 
+use std::io::Write;
 use std::path::Path;
-use std::process::{Command, Output};
+use std::process::{Command, Output, Stdio};
 
 use crate::error::{Result, WtError};
 
+/// Single-quote a string for bash
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
 /// A helper for running external commands with consistent error handling.
 pub struct CommandRunner {
     program: &'static str,
@@ -64,6 +70,32 @@ impl CommandRunner {
         }
     }
 
+    /// Run a command with `input` on stdin and return stdout as a string.
+    pub fn output_with_stdin(&self, args: &[&str], input: &str) -> Result<String> {
+        let mut child = self
+            .build_command(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| (self.error_mapper)(e.to_string()))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(input.as_bytes())
+                .map_err(|e| (self.error_mapper)(e.to_string()))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| (self.error_mapper)(e.to_string()))?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Err((self.error_mapper)(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
     /// Check if a command succeeds without returning an error.
     pub fn success(&self, args: &[&str]) -> bool {
         self.build_command(args)
@@ -108,6 +140,12 @@ mod tests {
         assert!(runner.success(&["--version"]));
     }
 
+    #[test]
+    fn test_output_with_stdin() {
+        let output = CommandRunner::new("cat").output_with_stdin(&[], "piped").unwrap();
+        assert_eq!(output, "piped");
+    }
+
     #[test]
     fn test_git_runner_failure() {
         let runner = CommandRunner::git();
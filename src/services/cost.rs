@@ -0,0 +1,175 @@
+//! Token usage and cost estimates per task, across all of its agent sessions.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::models::{Instance, ModelPrice};
+
+use super::agent;
+use super::transcript::{self, TokenUsage};
+
+/// Built-in prices in USD per million tokens, keyed by a substring of the
+/// model ID. The longest matching key wins, so specific versions can
+/// override a family.
+const BUILTIN_PRICES: &[(&str, f64, f64)] = &[
+    ("opus", 15.0, 75.0),
+    ("opus-4-5", 5.0, 25.0),
+    ("sonnet", 3.0, 15.0),
+    ("haiku", 0.8, 4.0),
+    ("haiku-4-5", 1.0, 5.0),
+];
+
+/// Token usage of a task, summed over its sessions
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct TaskUsage {
+    /// Transcripts the usage was read from
+    pub sessions: usize,
+    pub by_model: BTreeMap<String, TokenUsage>,
+}
+
+impl TaskUsage {
+    pub fn total(&self) -> TokenUsage {
+        let mut total = TokenUsage::default();
+        for usage in self.by_model.values() {
+            total.add(usage);
+        }
+        total
+    }
+
+    pub fn add(&mut self, other: &TaskUsage) {
+        self.sessions += other.sessions;
+        for (model, usage) in &other.by_model {
+            self.by_model.entry(model.clone()).or_default().add(usage);
+        }
+    }
+}
+
+/// Estimated cost of some usage
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct CostEstimate {
+    pub usd: f64,
+    /// Models with usage but no price (not included in `usd`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unpriced_models: Vec<String>,
+}
+
+impl CostEstimate {
+    /// "$1.23", or "$1.23+" when some usage couldn't be priced
+    pub fn display(&self) -> String {
+        let suffix = if self.unpriced_models.is_empty() { "" } else { "+" };
+        format!("${:.2}{}", self.usd, suffix)
+    }
+}
+
+/// Usage of a task's sessions, read from their transcripts
+pub fn task_usage(task: &str, instance: &Instance) -> TaskUsage {
+    let backend = agent::for_instance(instance);
+    let sessions: Vec<_> = backend
+        .session_transcripts(task, instance)
+        .iter()
+        .filter_map(|path| backend.parse_transcript(path))
+        .collect();
+    TaskUsage {
+        sessions: sessions.len(),
+        by_model: transcript::aggregate(sessions).map(|m| m.usage).unwrap_or_default(),
+    }
+}
+
+/// Price of a model: the longest configured key contained in its ID, then
+/// the longest built-in one
+pub fn price_for(prices: &BTreeMap<String, ModelPrice>, model: &str) -> Option<ModelPrice> {
+    let configured = prices
+        .iter()
+        .filter(|(key, _)| model.contains(key.as_str()))
+        .max_by_key(|(key, _)| key.len())
+        .map(|(_, price)| *price);
+    configured.or_else(|| {
+        BUILTIN_PRICES
+            .iter()
+            .filter(|(key, _, _)| model.contains(key))
+            .max_by_key(|(key, _, _)| key.len())
+            .map(|(_, input, output)| ModelPrice {
+                input: *input,
+                output: *output,
+                cache_read: None,
+                cache_write: None,
+            })
+    })
+}
+
+pub fn estimate(usage: &TaskUsage, prices: &BTreeMap<String, ModelPrice>) -> CostEstimate {
+    let mut estimate = CostEstimate::default();
+    for (model, tokens) in &usage.by_model {
+        match price_for(prices, model) {
+            Some(price) => {
+                let per_token = |tokens: u64, per_million: f64| tokens as f64 * per_million / 1_000_000.0;
+                estimate.usd += per_token(tokens.input, price.input)
+                    + per_token(tokens.output, price.output)
+                    + per_token(tokens.cache_read, price.cache_read.unwrap_or(price.input / 10.0))
+                    + per_token(tokens.cache_creation, price.cache_write.unwrap_or(price.input * 1.25));
+            }
+            None if tokens.total() > 0 => estimate.unpriced_models.push(model.clone()),
+            None => {}
+        }
+    }
+    estimate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(model: &str, input: u64, output: u64, cache_read: u64, cache_creation: u64) -> TaskUsage {
+        TaskUsage {
+            sessions: 1,
+            by_model: BTreeMap::from([(
+                model.to_string(),
+                TokenUsage {
+                    input,
+                    output,
+                    cache_read,
+                    cache_creation,
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_price_for_prefers_longest_match() {
+        let none = BTreeMap::new();
+        assert_eq!(price_for(&none, "claude-opus-4-5-20251101").unwrap().input, 5.0);
+        assert_eq!(price_for(&none, "claude-opus-4-1-20250805").unwrap().input, 15.0);
+        assert!(price_for(&none, "gpt-5").is_none());
+
+        let prices = BTreeMap::from([(
+            "opus".to_string(),
+            ModelPrice {
+                input: 1.0,
+                output: 2.0,
+                cache_read: None,
+                cache_write: None,
+            },
+        )]);
+        // A configured price beats the built-in table
+        assert_eq!(price_for(&prices, "claude-opus-4-5").unwrap().input, 1.0);
+    }
+
+    #[test]
+    fn test_estimate() {
+        let prices = BTreeMap::new();
+        // 1M of each at sonnet prices: 3 + 15 + 0.3 + 3.75
+        let cost = estimate(
+            &usage("claude-sonnet-4-5", 1_000_000, 1_000_000, 1_000_000, 1_000_000),
+            &prices,
+        );
+        assert!((cost.usd - 22.05).abs() < 1e-9, "usd: {}", cost.usd);
+        assert_eq!(cost.display(), "$22.05");
+
+        let mut mixed = usage("claude-haiku-4-5", 1_000_000, 0, 0, 0);
+        mixed.add(&usage("local-model", 10, 10, 0, 0));
+        let cost = estimate(&mixed, &prices);
+        assert_eq!(cost.unpriced_models, vec!["local-model".to_string()]);
+        assert_eq!(cost.display(), "$1.00+");
+    }
+}
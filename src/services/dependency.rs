@@ -1,10 +1,45 @@
 use crate::error::{Result, WtError};
-use crate::models::{TaskStatus, TaskStore};
+use crate::models::{Task, TaskStatus, TaskStore};
 
-pub fn check_dependencies_merged(store: &TaskStore, task_name: &str) -> Result<()> {
+/// Whether a dependency no longer blocks its dependents.
+/// Merged and Archived always count; Done counts too when the dependent stacks.
+pub fn dependency_satisfied(store: &TaskStore, dep_name: &str, stack: bool) -> bool {
+    match store.get_status(dep_name) {
+        TaskStatus::Merged | TaskStatus::Archived => true,
+        TaskStatus::Done => stack,
+        _ => false,
+    }
+}
+
+/// Dependencies that still block a task from starting.
+pub fn blocking_dependencies(store: &TaskStore, task: &Task, config_stack: bool) -> Vec<String> {
+    let stack = task.is_stacked(config_stack);
+    task.depends()
+        .iter()
+        .filter(|dep| !dependency_satisfied(store, dep, stack))
+        .cloned()
+        .collect()
+}
+
+/// Pending tasks whose dependencies no longer block them, in scheduling
+/// (priority) order.
+pub fn ready_tasks(store: &TaskStore, config_stack: bool) -> Vec<String> {
+    store
+        .list_by_priority()
+        .iter()
+        .filter(|task| {
+            store.get_status(task.name()) == TaskStatus::Pending
+                && blocking_dependencies(store, task, config_stack).is_empty()
+        })
+        .map(|task| task.name().to_string())
+        .collect()
+}
+
+pub fn check_dependencies_merged(store: &TaskStore, task_name: &str, config_stack: bool) -> Result<()> {
     let task = store
         .get(task_name)
         .ok_or_else(|| WtError::TaskNotFound(task_name.to_string()))?;
+    let stack = task.is_stacked(config_stack);
 
     for dep_name in task.depends() {
         // Check dependency exists
@@ -12,19 +47,31 @@ pub fn check_dependencies_merged(store: &TaskStore, task_name: &str) -> Result<(
             .get(dep_name)
             .ok_or_else(|| WtError::DependencyNotFound(dep_name.clone()))?;
 
-        // Check dependency status from StatusStore
-        // Both Merged and Archived are considered "completed"
-        let dep_status = store.get_status(dep_name);
-        if dep_status != TaskStatus::Merged && dep_status != TaskStatus::Archived {
-            return Err(WtError::DependencyNotMerged {
-                task: task_name.to_string(),
-                dep: dep_name.clone(),
+        if !dependency_satisfied(store, dep_name, stack) {
+            let (task, dep) = (task_name.to_string(), dep_name.clone());
+            return Err(if stack {
+                WtError::DependencyNotDone { task, dep }
+            } else {
+                WtError::DependencyNotMerged { task, dep }
             });
         }
     }
     Ok(())
 }
 
+/// Done (not yet merged) dependencies a stacked task starts on top of, in `depends` order.
+pub fn stack_parents(store: &TaskStore, task_name: &str, config_stack: bool) -> Vec<String> {
+    match store.get(task_name) {
+        Some(task) if task.is_stacked(config_stack) => task
+            .depends()
+            .iter()
+            .filter(|dep| store.get_status(dep) == TaskStatus::Done)
+            .cloned()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 /// Find all tasks that depend on the given task and are not in Pending state.
 /// Returns a list of (task_name, status) pairs.
 pub fn find_non_pending_dependents(store: &TaskStore, task_name: &str) -> Vec<(String, TaskStatus)> {
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::path::Path;
 use std::time::SystemTime;
 
@@ -15,17 +16,15 @@ pub struct GitMetrics {
     pub has_conflict: bool,
 }
 
-/// Get git statistics for a worktree
-pub fn get_worktree_metrics(worktree_path: &str) -> Option<GitMetrics> {
+/// Get git statistics for a worktree relative to its base branch
+pub fn get_worktree_metrics(worktree_path: &str, base_branch: &str) -> Option<GitMetrics> {
     let path = Path::new(worktree_path);
     if !path.exists() {
         return None;
     }
 
-    let (additions, deletions) = get_diff_stats(worktree_path).unwrap_or((0, 0));
-    let commits = get_commit_count(worktree_path, "main")
-        .or_else(|| get_commit_count(worktree_path, "master"))
-        .unwrap_or(0);
+    let (additions, deletions) = get_diff_stats(worktree_path, base_branch).unwrap_or((0, 0));
+    let commits = get_commit_count(worktree_path, base_branch).unwrap_or(0);
     let has_conflict = has_conflicts(worktree_path);
 
     Some(GitMetrics {
@@ -36,7 +35,8 @@ pub fn get_worktree_metrics(worktree_path: &str) -> Option<GitMetrics> {
     })
 }
 
-pub fn create_worktree(branch: &str, path: &str) -> Result<()> {
+/// Create a worktree on a new branch starting at `start_point` (default: HEAD).
+pub fn create_worktree(branch: &str, path: &str, start_point: Option<&str>) -> Result<()> {
     let worktree_path = Path::new(path);
     if let Some(parent) = worktree_path.parent() {
         if !parent.exists() {
@@ -44,7 +44,9 @@ pub fn create_worktree(branch: &str, path: &str) -> Result<()> {
         }
     }
 
-    CommandRunner::git().run(&["worktree", "add", "-b", branch, path])
+    let mut args = vec!["worktree", "add", "-b", branch, path];
+    args.extend(start_point);
+    CommandRunner::git().run(&args)
 }
 
 pub fn remove_worktree(path: &str) -> Result<()> {
@@ -86,16 +88,13 @@ pub fn find_branches(pattern: &str) -> Vec<String> {
     }
 }
 
-/// Get diff stats (additions, deletions) for a worktree compared to main branch.
+/// Get diff stats (additions, deletions) for a worktree compared to its base branch.
 /// Shows all changes on the branch, including committed ones.
-pub fn get_diff_stats(worktree_path: &str) -> Option<(i32, i32)> {
-    // Try to find the base branch (main or master)
-    let base = get_default_branch(worktree_path).unwrap_or_else(|| "main".to_string());
-
-    // Try committed changes first (main...HEAD)
+pub fn get_diff_stats(worktree_path: &str, base_branch: &str) -> Option<(i32, i32)> {
+    // Try committed changes first (base...HEAD)
     let output = CommandRunner::new("git")
         .current_dir(worktree_path)
-        .output(&["diff", "--shortstat", &format!("{}...HEAD", base)]);
+        .output(&["diff", "--shortstat", &format!("{}...HEAD", base_branch)]);
 
     if let Ok(stdout) = output {
         if let Some(stats) = parse_diff_stats(&stdout) {
@@ -110,8 +109,16 @@ pub fn get_diff_stats(worktree_path: &str) -> Option<(i32, i32)> {
     output.ok().and_then(|s| parse_diff_stats(&s))
 }
 
+/// Resolve the base branch: the configured one if any, else main/master.
+pub fn resolve_base_branch(configured: Option<&str>) -> String {
+    configured
+        .map(|b| b.to_string())
+        .or_else(|| get_default_branch("."))
+        .unwrap_or_else(|| "main".to_string())
+}
+
 /// Get the default branch name (main or master)
-fn get_default_branch(worktree_path: &str) -> Option<String> {
+pub fn get_default_branch(worktree_path: &str) -> Option<String> {
     // Try main first
     let result = CommandRunner::new("git")
         .current_dir(worktree_path)
@@ -171,6 +178,246 @@ pub fn get_commit_count(worktree_path: &str, base_branch: &str) -> Option<i32> {
     }
 }
 
+/// Resolve a revision to a full commit hash.
+pub fn rev_parse(rev: &str, cwd: &str) -> Option<String> {
+    CommandRunner::git()
+        .current_dir(cwd)
+        .output(&["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", rev)])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// How a task branch was found to be integrated into the base branch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MergeKind {
+    /// Branch tip is an ancestor of the base branch (merge commit or fast-forward)
+    Ancestor,
+    /// Every branch commit has a patch-identical commit on base (rebase merge)
+    Rebase,
+    /// The branch as a single squashed patch is on base, or base has the same tree
+    Squash,
+}
+
+impl MergeKind {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            MergeKind::Ancestor => "merge",
+            MergeKind::Rebase => "rebase",
+            MergeKind::Squash => "squash",
+        }
+    }
+}
+
+/// Detect whether `branch` has been merged into `base`.
+///
+/// `fork_point` is the commit the branch was created from. A branch whose tip
+/// is still the fork point has no work of its own and is never reported as
+/// merged, even though it is trivially an ancestor of base. When unknown, the
+/// oldest reflog entry of the branch is used instead.
+pub fn detect_merge(branch: &str, base: &str, fork_point: Option<&str>, cwd: &str) -> Option<MergeKind> {
+    let tip = rev_parse(branch, cwd)?;
+    let base_tip = rev_parse(base, cwd)?;
+    let fork_point = fork_point
+        .map(|s| s.to_string())
+        .or_else(|| branch_creation_commit(branch, cwd))?;
+
+    if tip == fork_point {
+        return None;
+    }
+
+    let merge_base = CommandRunner::git()
+        .current_dir(cwd)
+        .output(&["merge-base", &tip, &base_tip])
+        .ok()
+        .map(|s| s.trim().to_string())?;
+
+    // Regular merge or fast-forward: all branch commits are reachable from base
+    if merge_base == tip {
+        return Some(MergeKind::Ancestor);
+    }
+
+    // Same tree on both sides: squashed and nothing else landed since
+    if tree_of(&tip, cwd)? == tree_of(&base_tip, cwd)? {
+        return Some(MergeKind::Squash);
+    }
+
+    // Rebase merge: every commit has a patch-id equivalent on base
+    if cherry_all_applied(&base_tip, &tip, cwd) {
+        return Some(MergeKind::Rebase);
+    }
+
+    // Squash merge: the branch's whole diff has the patch-id of a commit on
+    // base (computed from diffs only, so nothing is written to the repo)
+    let git = || CommandRunner::git().current_dir(cwd);
+    let branch_diff = git()
+        .output(&["diff", "--no-color", "--no-ext-diff", &merge_base, &tip])
+        .ok()?;
+    let squashed = patch_ids(&branch_diff, cwd).into_iter().next()?;
+    let base_log = git()
+        .output(&[
+            "log",
+            "-p",
+            "--no-color",
+            "--no-ext-diff",
+            "--no-merges",
+            &format!("{}..{}", merge_base, base_tip),
+        ])
+        .ok()?;
+    if patch_ids(&base_log, cwd).contains(&squashed) {
+        return Some(MergeKind::Squash);
+    }
+
+    None
+}
+
+/// `git patch-id --stable` of each commit (or plain diff) in `patch`
+fn patch_ids(patch: &str, cwd: &str) -> Vec<String> {
+    CommandRunner::git()
+        .current_dir(cwd)
+        .output_with_stdin(&["patch-id", "--stable"], patch)
+        .map(|out| {
+            out.lines()
+                .filter_map(|line| line.split_whitespace().next())
+                .map(|id| id.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Commit a branch was created from, taken from its oldest reflog entry.
+fn branch_creation_commit(branch: &str, cwd: &str) -> Option<String> {
+    CommandRunner::git()
+        .current_dir(cwd)
+        .output(&["reflog", "show", "--format=%H", &format!("refs/heads/{}", branch)])
+        .ok()?
+        .lines()
+        .last()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn tree_of(commit: &str, cwd: &str) -> Option<String> {
+    CommandRunner::git()
+        .current_dir(cwd)
+        .output(&["rev-parse", &format!("{}^{{tree}}", commit)])
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+/// `git cherry upstream head` marks commits whose patch is already upstream
+/// with `-`. Returns true if there is at least one commit and all are applied.
+fn cherry_all_applied(upstream: &str, head: &str, cwd: &str) -> bool {
+    match CommandRunner::git()
+        .current_dir(cwd)
+        .output(&["cherry", upstream, head])
+    {
+        Ok(stdout) => {
+            let lines: Vec<&str> = stdout.lines().filter(|l| !l.trim().is_empty()).collect();
+            !lines.is_empty() && lines.iter().all(|l| l.starts_with('-'))
+        }
+        Err(_) => false,
+    }
+}
+
+/// Check whether tracked files in the worktree have uncommitted changes.
+pub fn is_dirty(cwd: &str) -> bool {
+    CommandRunner::git()
+        .current_dir(cwd)
+        .output(&["status", "--porcelain", "--untracked-files=no"])
+        .map(|s| !s.trim().is_empty())
+        .unwrap_or(true)
+}
+
+/// Name of the branch checked out in the worktree (None if detached).
+pub fn current_branch(cwd: &str) -> Option<String> {
+    CommandRunner::git()
+        .current_dir(cwd)
+        .output(&["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Number of commits on `branch` that are not on the checked-out branch.
+pub fn commits_ahead(branch: &str, cwd: &str) -> Option<u32> {
+    CommandRunner::git()
+        .current_dir(cwd)
+        .output(&["rev-list", "--count", &format!("HEAD..{}", branch)])
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+}
+
+/// Merge `branch` into the checked-out branch with a merge commit.
+/// On failure the merge is aborted, leaving the worktree as it was.
+pub fn merge_branch(branch: &str, message: &str, cwd: &str) -> Result<()> {
+    merge_branches(&[branch], message, cwd)
+}
+
+/// Merge one or more branches into the checked-out branch (octopus merge for
+/// several). On failure the merge is aborted.
+pub fn merge_branches(branches: &[&str], message: &str, cwd: &str) -> Result<()> {
+    let git = CommandRunner::git().current_dir(cwd);
+    let mut args = vec!["merge", "--no-ff", "--no-edit", "-m", message];
+    args.extend(branches);
+    git.run(&args).inspect_err(|_| {
+        let _ = git.run(&["merge", "--abort"]);
+    })
+}
+
+pub fn checkout(rev: &str, cwd: &str) -> Result<()> {
+    CommandRunner::git()
+        .current_dir(cwd)
+        .run(&["checkout", "--quiet", rev])
+}
+
+/// Rebase the checked-out branch's commits after `old_base` onto `new_base`.
+/// On failure the rebase is aborted, leaving the branch unchanged.
+pub fn rebase_onto(new_base: &str, old_base: &str, cwd: &str) -> Result<()> {
+    let git = CommandRunner::git().current_dir(cwd);
+    git.run(&["rebase", "--onto", new_base, old_base])
+        .inspect_err(|_| {
+            let _ = git.run(&["rebase", "--abort"]);
+        })
+}
+
+/// Squash `branch` into a single commit on the checked-out branch.
+/// On failure the index and worktree are reset to HEAD.
+pub fn squash_merge_branch(branch: &str, message: &str, cwd: &str) -> Result<()> {
+    let git = CommandRunner::git().current_dir(cwd);
+    git.run(&["merge", "--squash", branch])
+        .and_then(|_| git.run(&["commit", "-m", message]))
+        .inspect_err(|_| {
+            let _ = git.run(&["reset", "--merge"]);
+        })
+}
+
+/// Replay the commits of `branch` on top of the checked-out branch, keeping
+/// history linear (like a rebase merge): merge commits (e.g. from stacked
+/// parents) are dropped and commits already upstream are skipped, as
+/// `git rebase` does. On failure the pick is aborted.
+pub fn cherry_pick_branch(branch: &str, cwd: &str) -> Result<()> {
+    let git = CommandRunner::git().current_dir(cwd);
+    let merges = git.output(&["rev-list", "--merges", &format!("HEAD..{}", branch)])?;
+    let merges: HashSet<&str> = merges.lines().collect();
+    // `git cherry` lists oldest first, "+" for commits not yet upstream
+    let cherry = git.output(&["cherry", "HEAD", branch])?;
+    let picks: Vec<&str> = cherry
+        .lines()
+        .filter_map(|line| line.strip_prefix("+ "))
+        .filter(|commit| !merges.contains(commit))
+        .collect();
+    if picks.is_empty() {
+        return Ok(());
+    }
+
+    let mut args = vec!["cherry-pick"];
+    args.extend(picks);
+    git.run(&args).inspect_err(|_| {
+        let _ = git.run(&["cherry-pick", "--abort"]);
+    })
+}
+
 /// Check if the worktree has merge conflicts.
 pub fn has_conflicts(worktree_path: &str) -> bool {
     // Check for unmerged files via git status
@@ -0,0 +1,141 @@
+//! Lifecycle hooks: user scripts from the config's `hooks:` section, run with
+//! the task's context at fixed points of its lifecycle.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use chrono::Utc;
+
+use crate::constants::{HOOKS_LOG_FILE, LOGS_DIR};
+use crate::error::{Result, WtError};
+use crate::models::{Hook, TaskStore, WtConfig};
+
+use super::git;
+use super::template::TemplateContext;
+
+/// Run a hook if configured. The script is expanded as a template, runs in
+/// `cwd` with the context exported as `WT_*` variables, and its output is
+/// appended to `.wt/logs/<task>/hooks.log`. Fails if the script does.
+pub fn run(config: &WtConfig, hook: Hook, vars: &TemplateContext, cwd: &Path) -> Result<()> {
+    let Some(script) = config.hooks.get(hook) else {
+        return Ok(());
+    };
+    let task = vars.get("task").unwrap_or_default().to_string();
+    let failed = |message: String| WtError::HookFailed {
+        hook: hook.name().to_string(),
+        task: task.clone(),
+        message,
+    };
+
+    let script = vars.render(script)?;
+    let output = Command::new("bash")
+        .arg("-c")
+        .arg(&script)
+        .current_dir(cwd)
+        .envs(vars.env_vars())
+        .env("WT_HOOK", hook.name())
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| failed(e.to_string()))?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    let status = match output.status.code() {
+        Some(code) => format!("exit code {}", code),
+        None => "killed by signal".to_string(),
+    };
+    let repo_root = vars.get("repo_root").unwrap_or_default();
+    append_log(&Path::new(repo_root).join(LOGS_DIR).join(&task), hook, &status, &text);
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(failed(status))
+    }
+}
+
+/// Run a hook for a task that already exists, with its definition and
+/// instance as context. Runs in the worktree if it still exists.
+pub fn run_for_task(store: &TaskStore, hook: Hook, name: &str) -> Result<()> {
+    let Ok(config) = WtConfig::load() else {
+        return Ok(());
+    };
+    if config.hooks.get(hook).is_none() {
+        return Ok(());
+    }
+
+    let repo_root = git::get_repo_root()?;
+    let instance = store.get_instance(name);
+    let vars = TemplateContext::for_task(&config, &repo_root, name, store.get(name), instance);
+    let cwd = instance
+        .map(|i| Path::new(&i.worktree_path))
+        .filter(|p| p.exists())
+        .unwrap_or(Path::new(&repo_root));
+    run(&config, hook, &vars, cwd)
+}
+
+/// Logging is best effort: a hook must not fail because its log can't be written
+fn append_log(dir: &Path, hook: Hook, status: &str, output: &str) {
+    let _ = std::fs::create_dir_all(dir);
+    if let Ok(mut file) = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(HOOKS_LOG_FILE))
+    {
+        let _ = writeln!(file, "=== {} {} ({}) ===", Utc::now().to_rfc3339(), hook.name(), status);
+        let _ = write!(file, "{}", output);
+        if !output.is_empty() && !output.ends_with('\n') {
+            let _ = writeln!(file);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config(hooks: &str) -> WtConfig {
+        WtConfig::from_str(&format!("hooks:\n{}", hooks)).unwrap()
+    }
+
+    fn read_log(root: &Path) -> String {
+        std::fs::read_to_string(root.join(LOGS_DIR).join("auth").join(HOOKS_LOG_FILE)).unwrap()
+    }
+
+    #[test]
+    fn test_unconfigured_hook_is_noop() {
+        let dir = TempDir::new().unwrap();
+        let vars = TemplateContext::new("auth", &dir.path().to_string_lossy());
+        let config = WtConfig::from_str("{}").unwrap();
+
+        assert!(run(&config, Hook::PreStart, &vars, dir.path()).is_ok());
+        assert!(!dir.path().join(LOGS_DIR).exists());
+    }
+
+    #[test]
+    fn test_hook_gets_context_and_is_logged() {
+        let dir = TempDir::new().unwrap();
+        let vars = TemplateContext::new("auth", &dir.path().to_string_lossy());
+        let config = config("  post_start: echo \"$WT_HOOK $WT_TASK ${task}\"\n");
+
+        run(&config, Hook::PostStart, &vars, dir.path()).unwrap();
+
+        let log = read_log(dir.path());
+        assert!(log.contains("post_start (exit code 0)"), "log: {}", log);
+        assert!(log.contains("post_start auth auth"), "log: {}", log);
+    }
+
+    #[test]
+    fn test_failing_hook_returns_error() {
+        let dir = TempDir::new().unwrap();
+        let vars = TemplateContext::new("auth", &dir.path().to_string_lossy());
+        let config = config("  pre_start: echo nope >&2; exit 3\n");
+
+        let err = run(&config, Hook::PreStart, &vars, dir.path()).unwrap_err();
+        assert!(err.to_string().contains("Hook pre_start failed for 'auth': exit code 3"));
+        assert!(read_log(dir.path()).contains("nope"));
+    }
+}
@@ -1,6 +1,11 @@
+pub mod agent;
 pub mod command;
+pub mod cost;
 pub mod dependency;
 pub mod git;
+pub mod hooks;
+pub mod notify;
+pub mod template;
 pub mod tmux;
 pub mod transcript;
 pub mod workspace;
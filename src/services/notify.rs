@@ -0,0 +1,317 @@
+//! Notifications sent by `wt watch` when an agent finishes or goes idle.
+//!
+//! Each sink delivers a [`Notification`] one way: terminal bell,
+//! `notify-send`, a user command or a webhook. Sinks that shell out take the
+//! program to run, so they can be pointed at a stand-in.
+
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::error::{Result, WtError};
+use crate::models::NotifyConfig;
+
+/// What happened to a task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyEvent {
+    /// The agent exited and the task was marked done
+    Finished,
+    /// The agent has been inactive for longer than the idle threshold
+    Idle,
+    /// The agent is blocked on a permission or input prompt
+    Waiting,
+}
+
+impl NotifyEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NotifyEvent::Finished => "finished",
+            NotifyEvent::Idle => "idle",
+            NotifyEvent::Waiting => "waiting",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    pub event: NotifyEvent,
+    pub task: String,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Notification {
+    pub fn new(event: NotifyEvent, task: &str, message: impl Into<String>) -> Self {
+        Self {
+            event,
+            task: task.to_string(),
+            message: message.into(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// One-line title, e.g. "wt: auth finished"
+    pub fn title(&self) -> String {
+        format!("wt: {} {}", self.task, self.event.name())
+    }
+}
+
+pub trait NotifySink {
+    /// Short name used in warnings
+    fn name(&self) -> &'static str;
+
+    fn send(&mut self, notification: &Notification) -> Result<()>;
+}
+
+/// Rings the terminal bell
+pub struct BellSink<W: Write> {
+    out: W,
+}
+
+impl<W: Write> BellSink<W> {
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+}
+
+impl<W: Write> NotifySink for BellSink<W> {
+    fn name(&self) -> &'static str {
+        "bell"
+    }
+
+    fn send(&mut self, _notification: &Notification) -> Result<()> {
+        self.out
+            .write_all(b"\x07")
+            .and_then(|_| self.out.flush())
+            .map_err(|e| WtError::Notify(format!("bell: {}", e)))
+    }
+}
+
+/// Desktop notification via `notify-send <title> <message>`
+pub struct NotifySendSink {
+    program: String,
+}
+
+impl NotifySendSink {
+    pub fn new(program: &str) -> Self {
+        Self {
+            program: program.to_string(),
+        }
+    }
+}
+
+impl NotifySink for NotifySendSink {
+    fn name(&self) -> &'static str {
+        "notify-send"
+    }
+
+    fn send(&mut self, notification: &Notification) -> Result<()> {
+        let mut cmd = Command::new(&self.program);
+        cmd.arg(notification.title()).arg(&notification.message);
+        run(self.name(), &mut cmd, None)
+    }
+}
+
+/// Runs a command through bash with the notification in `WT_*` variables
+pub struct CommandSink {
+    command: String,
+}
+
+impl CommandSink {
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+        }
+    }
+}
+
+impl NotifySink for CommandSink {
+    fn name(&self) -> &'static str {
+        "command"
+    }
+
+    fn send(&mut self, notification: &Notification) -> Result<()> {
+        let mut cmd = Command::new("bash");
+        cmd.arg("-c")
+            .arg(&self.command)
+            .env("WT_EVENT", notification.event.name())
+            .env("WT_TASK", &notification.task)
+            .env("WT_MESSAGE", &notification.message);
+        run(self.name(), &mut cmd, None)
+    }
+}
+
+/// POSTs the notification as JSON to a URL with `curl`
+pub struct WebhookSink {
+    url: String,
+    program: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: &str, program: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            program: program.to_string(),
+        }
+    }
+}
+
+impl NotifySink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn send(&mut self, notification: &Notification) -> Result<()> {
+        let body = serde_json::to_string(notification)?;
+        let mut cmd = Command::new(&self.program);
+        cmd.args(["-sS", "--fail", "--max-time", "10", "-X", "POST"])
+            .args(["-H", "Content-Type: application/json"])
+            .args(["--data-binary", "@-"])
+            .arg(&self.url);
+        run(self.name(), &mut cmd, Some(&body))
+    }
+}
+
+/// Sinks configured in the `notify:` section, or the bell if none is
+pub fn sinks(config: &NotifyConfig) -> Vec<Box<dyn NotifySink>> {
+    let mut sinks: Vec<Box<dyn NotifySink>> = Vec::new();
+    if config.notify_send {
+        sinks.push(Box::new(NotifySendSink::new("notify-send")));
+    }
+    if let Some(command) = &config.command {
+        sinks.push(Box::new(CommandSink::new(command)));
+    }
+    if let Some(url) = &config.webhook {
+        sinks.push(Box::new(WebhookSink::new(url, "curl")));
+    }
+    if config.bell || sinks.is_empty() {
+        sinks.push(Box::new(BellSink::new(io::stdout())));
+    }
+    sinks
+}
+
+fn run(sink: &str, cmd: &mut Command, stdin: Option<&str>) -> Result<()> {
+    let failed = |message: String| WtError::Notify(format!("{}: {}", sink, message));
+
+    let mut child = cmd
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| failed(e.to_string()))?;
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(input.as_bytes()).map_err(|e| failed(e.to_string()))?;
+    }
+    let output = child.wait_with_output().map_err(|e| failed(e.to_string()))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(failed(match (output.status.code(), stderr.trim()) {
+            (Some(code), "") => format!("exit code {}", code),
+            (Some(code), err) => format!("exit code {}: {}", code, err),
+            (None, _) => "killed by signal".to_string(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn notification() -> Notification {
+        Notification::new(NotifyEvent::Idle, "auth", "no activity for 2m")
+    }
+
+    /// Script that records its arguments and stdin in `out`
+    fn stand_in(dir: &Path, out: &Path) -> String {
+        let script = dir.join("stand-in");
+        std::fs::write(
+            &script,
+            format!("#!/bin/sh\nprintf '%s\\n' \"$@\" > '{0}'\ncat >> '{0}'\n", out.display()),
+        )
+        .unwrap();
+        Command::new("chmod").arg("+x").arg(&script).status().unwrap();
+        script.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_bell_writes_bel() {
+        let mut out = Vec::new();
+        BellSink::new(&mut out).send(&notification()).unwrap();
+        assert_eq!(out, b"\x07");
+    }
+
+    #[test]
+    fn test_notify_send_gets_title_and_message() {
+        let dir = TempDir::new().unwrap();
+        let out = dir.path().join("out");
+        let program = stand_in(dir.path(), &out);
+
+        NotifySendSink::new(&program).send(&notification()).unwrap();
+
+        let recorded = std::fs::read_to_string(&out).unwrap();
+        assert_eq!(recorded, "wt: auth idle\nno activity for 2m\n");
+    }
+
+    #[test]
+    fn test_command_gets_env() {
+        let dir = TempDir::new().unwrap();
+        let out = dir.path().join("out");
+        let command = format!("echo \"$WT_EVENT $WT_TASK $WT_MESSAGE\" > '{}'", out.display());
+
+        CommandSink::new(&command).send(&notification()).unwrap();
+
+        let recorded = std::fs::read_to_string(&out).unwrap();
+        assert_eq!(recorded, "idle auth no activity for 2m\n");
+    }
+
+    #[test]
+    fn test_command_failure_is_error() {
+        let err = CommandSink::new("echo broken >&2; exit 4")
+            .send(&notification())
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Notification failed: command: exit code 4: broken");
+    }
+
+    #[test]
+    fn test_webhook_posts_json() {
+        let dir = TempDir::new().unwrap();
+        let out = dir.path().join("out");
+        let program = stand_in(dir.path(), &out);
+
+        WebhookSink::new("http://localhost:9/hook", &program)
+            .send(&notification())
+            .unwrap();
+
+        let recorded = std::fs::read_to_string(&out).unwrap();
+        let (args, body) = recorded.rsplit_once('\n').unwrap();
+        assert!(args.contains("-X\nPOST\n"), "args: {}", args);
+        assert!(args.ends_with("http://localhost:9/hook"), "args: {}", args);
+        let json: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(json["event"], "idle");
+        assert_eq!(json["task"], "auth");
+        assert_eq!(json["message"], "no activity for 2m");
+    }
+
+    #[test]
+    fn test_sinks_default_to_bell() {
+        let names = |config: &NotifyConfig| -> Vec<&'static str> {
+            sinks(config).iter().map(|s| s.name()).collect()
+        };
+
+        assert_eq!(names(&NotifyConfig::default()), vec!["bell"]);
+        let config = NotifyConfig {
+            command: Some("true".to_string()),
+            webhook: Some("http://localhost/".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(names(&config), vec!["command", "webhook"]);
+    }
+}
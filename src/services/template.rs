@@ -0,0 +1,328 @@
+//! Template variables for `start_args`, `shell_command` and scripts.
+//!
+//! `${name}` expands to one of [`VARIABLES`], `${env.NAME}` to an environment
+//! variable (the task's `env` first, then the process environment) and `$${`
+//! to a literal `${`. Anything else with a `$` is left for the shell.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::error::{Result, WtError};
+use crate::models::{Hook, Instance, Task, WtConfig};
+
+/// Variables a template can reference, with what they expand to
+pub const VARIABLES: &[(&str, &str)] = &[
+    ("task", "task name"),
+    ("branch", "task branch"),
+    ("worktree", "absolute worktree path"),
+    ("repo_root", "main repository root"),
+    ("session_id", "agent session ID"),
+    ("base_branch", "branch the task forks from and merges into"),
+    ("depends", "dependencies, space separated"),
+    ("task_file", "absolute path of the task file"),
+];
+
+const ENV_PREFIX: &str = "env.";
+
+enum Part<'a> {
+    Text(&'a str),
+    Var(&'static str),
+    Env(&'a str),
+}
+
+/// Values for one task. Known variables without a value expand to "".
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    values: HashMap<&'static str, String>,
+    env: BTreeMap<String, String>,
+}
+
+impl TemplateContext {
+    pub fn new(task: &str, repo_root: &str) -> Self {
+        Self::default().set("task", task).set("repo_root", repo_root)
+    }
+
+    /// Context of an existing task: its definition and instance where present,
+    /// with the task's effective `env`
+    pub fn for_task(
+        config: &WtConfig,
+        repo_root: &str,
+        name: &str,
+        task: Option<&Task>,
+        instance: Option<&Instance>,
+    ) -> Self {
+        let mut ctx = Self::new(name, repo_root).with_env(&config.launch_settings(task).env);
+        if let Some(task) = task {
+            ctx = ctx.with_task(task);
+        }
+        if let Some(instance) = instance {
+            ctx = ctx.with_instance(instance);
+        }
+        ctx
+    }
+
+    /// Set a variable; `name` must be one of [`VARIABLES`]
+    pub fn set(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        debug_assert!(VARIABLES.iter().any(|(v, _)| *v == name), "unknown variable {}", name);
+        self.values.insert(name, value.into());
+        self
+    }
+
+    /// Dependencies and task file of the task definition
+    pub fn with_task(self, task: &Task) -> Self {
+        let task_file = Path::new(&self.values["repo_root"])
+            .join(&task.file_path)
+            .to_string_lossy()
+            .to_string();
+        self.set("depends", task.depends().join(" "))
+            .set("task_file", task_file)
+    }
+
+    /// Branch, worktree, session and base branch of a started task
+    pub fn with_instance(self, instance: &Instance) -> Self {
+        let ctx = self
+            .set("branch", instance.branch.clone())
+            .set("worktree", instance.worktree_path.clone())
+            .set("session_id", instance.session_id.clone().unwrap_or_default());
+        match &instance.base_branch {
+            Some(base) => ctx.set("base_branch", base.clone()),
+            None => ctx,
+        }
+    }
+
+    /// Environment variables that take precedence over the process environment
+    pub fn with_env(mut self, env: &BTreeMap<String, String>) -> Self {
+        self.env = env.clone();
+        self
+    }
+
+    /// Value of a variable, if set
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// Environment for scripts run with this context: the task's `env` plus
+    /// every variable as `WT_<NAME>` (e.g. `WT_TASK`, `WT_REPO_ROOT`)
+    pub fn env_vars(&self) -> Vec<(String, String)> {
+        let mut vars: Vec<(String, String)> = self.env.clone().into_iter().collect();
+        for (name, _) in VARIABLES {
+            let value = self.get(name).unwrap_or_default().to_string();
+            vars.push((format!("WT_{}", name.to_uppercase()), value));
+        }
+        vars
+    }
+
+    /// Expand a template, failing on unknown variables or unclosed `${`
+    pub fn render(&self, template: &str) -> Result<String> {
+        let mut out = String::with_capacity(template.len());
+        for part in parse(template)? {
+            match part {
+                Part::Text(text) => out.push_str(text),
+                Part::Var(name) => out.push_str(self.get(name).unwrap_or_default()),
+                Part::Env(name) => {
+                    let value = self
+                        .env
+                        .get(name)
+                        .cloned()
+                        .or_else(|| std::env::var(name).ok())
+                        .unwrap_or_default();
+                    out.push_str(&value);
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Check a template without expanding it
+pub fn check(template: &str) -> Result<()> {
+    parse(template).map(|_| ())
+}
+
+fn parse(template: &str) -> Result<Vec<Part<'_>>> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+
+    while let Some(pos) = rest.find("${") {
+        // `$${` is an escaped `${`
+        if rest[..pos].ends_with('$') {
+            parts.push(Part::Text(&rest[..pos - 1]));
+            parts.push(Part::Text("${"));
+            rest = &rest[pos + 2..];
+            continue;
+        }
+
+        parts.push(Part::Text(&rest[..pos]));
+        let after = &rest[pos + 2..];
+        let end = after.find('}').ok_or_else(|| {
+            WtError::Template(format!("unclosed '${{' in '{}'", template))
+        })?;
+        let name = &after[..end];
+
+        if let Some(env) = name.strip_prefix(ENV_PREFIX) {
+            if !is_env_name(env) {
+                return Err(WtError::Template(format!(
+                    "'{}' is not a valid environment variable name",
+                    env
+                )));
+            }
+            parts.push(Part::Env(env));
+        } else {
+            let var = VARIABLES
+                .iter()
+                .map(|(v, _)| *v)
+                .find(|v| *v == name)
+                .ok_or_else(|| WtError::Template(format!("unknown variable '${{{}}}'", name)))?;
+            parts.push(Part::Var(var));
+        }
+        rest = &after[end + 1..];
+    }
+
+    parts.push(Part::Text(rest));
+    Ok(parts)
+}
+
+/// Whether `name` can be used as a shell environment variable
+pub fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Problems with the config's templates, one message each
+pub fn check_config(config: &WtConfig) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (field, value) in [
+        ("claude_command", Some(&config.claude_command)),
+        ("start_args", Some(&config.start_args)),
+        ("shell_command", config.shell_command.as_ref()),
+        ("init_script", config.init_script.as_ref()),
+        ("archive_script", config.archive_script.as_ref()),
+    ] {
+        if let Some(Err(e)) = value.map(|v| check(v)) {
+            errors.push(format!("{}: {}", field, e));
+        }
+    }
+    for hook in Hook::ALL {
+        if let Some(Err(e)) = config.hooks.get(hook).map(check) {
+            errors.push(format!("hooks.{}: {}", hook.name(), e));
+        }
+    }
+    for name in config.env.keys() {
+        if !is_env_name(name) {
+            errors.push(format!("env name '{}' is not a valid variable name", name));
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> TemplateContext {
+        TemplateContext::new("auth", "/repo")
+            .set("branch", "wt/auth-1234")
+            .set("worktree", "/repo/.wt/worktrees/auth")
+    }
+
+    #[test]
+    fn test_render_variables() {
+        assert_eq!(
+            ctx().render("-p @.wt/tasks/${task}.md on ${branch} in ${worktree} (${repo_root})").unwrap(),
+            "-p @.wt/tasks/auth.md on wt/auth-1234 in /repo/.wt/worktrees/auth (/repo)"
+        );
+    }
+
+    #[test]
+    fn test_render_known_but_unset_is_empty() {
+        assert_eq!(ctx().render("[${session_id}]").unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_render_unknown_variable_errors() {
+        let err = ctx().render("${tsak}").unwrap_err();
+        assert!(err.to_string().contains("unknown variable '${tsak}'"));
+        assert!(check("echo ${nope}").is_err());
+    }
+
+    #[test]
+    fn test_render_unclosed_errors() {
+        assert!(ctx().render("echo ${task").unwrap_err().to_string().contains("unclosed"));
+    }
+
+    #[test]
+    fn test_render_escape_and_shell_vars() {
+        assert_eq!(
+            ctx().render("echo $${task} $HOME ${task} $$").unwrap(),
+            "echo ${task} $HOME auth $$"
+        );
+        assert!(check("echo $${whatever}").is_ok());
+    }
+
+    #[test]
+    fn test_render_env() {
+        let env = BTreeMap::from([("PORT".to_string(), "3000".to_string())]);
+        let ctx = ctx().with_env(&env);
+        assert_eq!(ctx.render("${env.PORT}").unwrap(), "3000");
+        assert_eq!(ctx.render("${env.WT_SURELY_UNSET_VAR}").unwrap(), "");
+        assert!(ctx.render("${env.NO-DASH}").is_err());
+    }
+
+    #[test]
+    fn test_env_vars() {
+        let env = BTreeMap::from([("PORT".to_string(), "3000".to_string())]);
+        let vars = ctx().with_env(&env).env_vars();
+
+        assert!(vars.contains(&("PORT".to_string(), "3000".to_string())));
+        assert!(vars.contains(&("WT_TASK".to_string(), "auth".to_string())));
+        assert!(vars.contains(&("WT_REPO_ROOT".to_string(), "/repo".to_string())));
+        assert!(vars.contains(&("WT_SESSION_ID".to_string(), String::new())));
+    }
+
+    #[test]
+    fn test_with_task_and_instance() {
+        let task = crate::models::TaskStore::parse_markdown(
+            "---\nname: api\ndepends: [auth, db]\n---\n\nApi",
+            ".wt/tasks/api.md".to_string(),
+        )
+        .unwrap();
+        let instance = Instance {
+            branch: "wt/api-1".to_string(),
+            worktree_path: "/repo/.wt/worktrees/api".to_string(),
+            tmux_session: "wt".to_string(),
+            tmux_window: "api".to_string(),
+            session_id: Some("sid".to_string()),
+            base_commit: None,
+            base_branch: Some("main".to_string()),
+            stack_parents: Vec::new(),
+            agent: None,
+            previous_sessions: Vec::new(),
+        };
+
+        let ctx = TemplateContext::new("api", "/repo").with_task(&task).with_instance(&instance);
+        assert_eq!(
+            ctx.render("${depends}|${task_file}|${session_id}|${base_branch}").unwrap(),
+            "auth db|/repo/.wt/tasks/api.md|sid|main"
+        );
+    }
+
+    #[test]
+    fn test_check_config() {
+        assert!(check_config(&WtConfig::from_str("{}\n").unwrap()).is_empty());
+
+        let config = WtConfig::from_str(
+            "start_args: -p ${task} ${nope}\narchive_script: rm -rf ${worktree}/node_modules\nenv:\n  BAD-NAME: x\n",
+        )
+        .unwrap();
+        assert_eq!(
+            check_config(&config),
+            vec![
+                "start_args: Invalid template: unknown variable '${nope}'",
+                "env name 'BAD-NAME' is not a valid variable name",
+            ]
+        );
+    }
+}
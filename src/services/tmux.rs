@@ -24,15 +24,23 @@ pub fn create_window(session: &str, window: &str, cwd: &str, command: &str) -> R
     ])?;
 
     // 然后用 send-keys 发送命令，这样 shell 别名也能生效
-    // 使用 -l (literal) 选项确保命令中的空格和特殊字符被正确发送
+    send_text(session, window, command)
+}
+
+/// 粘贴多行文本时使用的 tmux buffer
+const PASTE_BUFFER: &str = "wt-paste";
+
+/// 向窗口输入文本并回车
+pub fn send_text(session: &str, window: &str, text: &str) -> Result<()> {
     let window_target = format!("{}:{}", session, window);
-    CommandRunner::tmux().run(&[
-        "send-keys",
-        "-t",
-        &window_target,
-        "-l",
-        command,
-    ])?;
+    if text.contains('\n') {
+        // 多行文本用 bracketed paste 整体粘贴，否则每个换行都会被当作回车分段提交
+        CommandRunner::tmux().output_with_stdin(&["load-buffer", "-b", PASTE_BUFFER, "-"], text)?;
+        CommandRunner::tmux().run(&["paste-buffer", "-p", "-d", "-b", PASTE_BUFFER, "-t", &window_target])?;
+    } else {
+        // 使用 -l (literal) 选项确保文本中的空格和特殊字符被正确发送
+        CommandRunner::tmux().run(&["send-keys", "-t", &window_target, "-l", text])?;
+    }
     // 单独发送 Enter 键
     CommandRunner::tmux().run(&["send-keys", "-t", &window_target, "Enter"])
 }
@@ -47,6 +55,12 @@ pub fn window_exists(session: &str, window: &str) -> bool {
     CommandRunner::tmux().success(&["select-window", "-t", &target])
 }
 
+/// 窗口当前可见的内容，窗口不存在时返回 None
+pub fn capture_pane(session: &str, window: &str) -> Option<String> {
+    let target = format!("{}:{}", session, window);
+    CommandRunner::tmux().output(&["capture-pane", "-p", "-t", &target]).ok()
+}
+
 /// 如果窗口存在则关闭，返回是否执行了关闭操作
 pub fn kill_window_if_exists(session: &str, window: &str) -> Result<bool> {
     if window_exists(session, window) {
@@ -1,12 +1,17 @@
 //! Transcript service for reading Claude Code session transcripts.
 
-use std::fs::File;
-use std::io::{BufRead, BufReader, Write};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::constants::TOOL_RESULT_CHARS;
 use crate::models::Instance;
 
 /// Metrics extracted from transcript
@@ -18,8 +23,10 @@ pub struct TranscriptMetrics {
     pub output_tokens: u64,
     /// Number of conversation turns
     pub num_turns: u32,
-    /// Context window size
+    /// Context window size (from `model`, see [`context_window_for`])
     pub context_window: u64,
+    /// Model ID of the latest assistant message, or the session init entry
+    pub model: Option<String>,
     /// Final summary/result from last assistant message
     pub summary: Option<String>,
     /// Whether the session completed normally
@@ -28,11 +35,118 @@ pub struct TranscriptMetrics {
     pub started_at: Option<chrono::DateTime<chrono::Utc>>,
     /// Session end timestamp (from last entry)
     pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Duration of the task's earlier sessions (see [`aggregate`])
+    pub previous_duration_secs: i64,
     /// Current tool being used (last tool_use)
     pub current_tool: Option<String>,
+    /// Tool call the transcript ends on, still without a tool_result
+    pub pending_tool: Option<String>,
+    /// Cumulative token usage per model (`unknown` when not recorded)
+    pub usage: BTreeMap<String, TokenUsage>,
+    /// What each assistant message (by message ID) added to the totals, so
+    /// [`aggregate`] counts messages replayed into a later transcript once
+    pub messages: HashMap<String, MessageUsage>,
+}
+
+/// Share of an assistant message in a transcript's totals
+#[derive(Debug, Default, Clone)]
+pub struct MessageUsage {
+    pub model: String,
+    pub usage: TokenUsage,
+    /// Transcript entries (turns) the message was split over
+    pub entries: u32,
+}
+
+/// Token counts, split the way the API bills them
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TokenUsage {
+    pub input: u64,
+    pub output: u64,
+    pub cache_read: u64,
+    pub cache_creation: u64,
+}
+
+impl TokenUsage {
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_read + self.cache_creation
+    }
+
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_read += other.cache_read;
+        self.cache_creation += other.cache_creation;
+    }
+
+    pub fn sub(&mut self, other: &TokenUsage) {
+        self.input = self.input.saturating_sub(other.input);
+        self.output = self.output.saturating_sub(other.output);
+        self.cache_read = self.cache_read.saturating_sub(other.cache_read);
+        self.cache_creation = self.cache_creation.saturating_sub(other.cache_creation);
+    }
+}
+
+/// Context window of models not in any table
+const DEFAULT_CONTEXT_WINDOW: u64 = 200_000;
+
+/// Context window of the long-context variants
+const LONG_CONTEXT_WINDOW: u64 = 1_000_000;
+
+/// Suffix Claude Code adds to the model ID of a long-context (1M) run
+const LONG_CONTEXT_SUFFIX: &str = "[1m]";
+
+/// Built-in context windows, keyed by model family (a substring of the model
+/// ID). The longest matching key wins.
+const BUILTIN_CONTEXT_WINDOWS: &[(&str, u64)] = &[
+    ("claude-opus", 200_000),
+    ("claude-sonnet", 200_000),
+    ("claude-haiku", 200_000),
+    ("claude-3", 200_000),
+    ("claude-2.1", 200_000),
+    ("claude-2", 100_000),
+    ("claude-instant", 100_000),
+];
+
+/// Context window of a model: the longest configured key contained in its ID,
+/// 1M for a `[1m]` run, then the longest built-in family, then 200k
+pub fn context_window_for(model: Option<&str>, overrides: &BTreeMap<String, u64>) -> u64 {
+    let Some(model) = model else {
+        return DEFAULT_CONTEXT_WINDOW;
+    };
+    let longest = |table: &mut dyn Iterator<Item = (&str, u64)>| {
+        table
+            .filter(|(key, _)| model.contains(key))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, window)| window)
+    };
+    longest(&mut overrides.iter().map(|(key, window)| (key.as_str(), *window)))
+        .or_else(|| model.ends_with(LONG_CONTEXT_SUFFIX).then_some(LONG_CONTEXT_WINDOW))
+        .or_else(|| longest(&mut BUILTIN_CONTEXT_WINDOWS.iter().copied()))
+        .unwrap_or(DEFAULT_CONTEXT_WINDOW)
+}
+
+/// Model ID after an assistant reply reports `reported`. The API reports the
+/// base ID of a long-context run, so the `[1m]` of the init model is kept
+/// when the reply comes from the same model.
+fn reported_model(current: Option<&str>, reported: &str) -> String {
+    match current.and_then(|m| m.strip_suffix(LONG_CONTEXT_SUFFIX)) {
+        Some(base) if reported.starts_with(base) => format!("{}{}", reported, LONG_CONTEXT_SUFFIX),
+        _ => reported.to_string(),
+    }
 }
 
 impl TranscriptMetrics {
+    /// Set `context_window` from the model, with config overrides
+    /// (`context_windows:`). The API reports the bare model ID even for
+    /// long-context runs, so a context larger than the table's window means
+    /// the run has the 1M window.
+    pub fn resolve_context_window(&mut self, overrides: &BTreeMap<String, u64>) {
+        self.context_window = context_window_for(self.model.as_deref(), overrides);
+        if self.input_tokens > self.context_window {
+            self.context_window = self.context_window.max(LONG_CONTEXT_WINDOW);
+        }
+    }
+
     /// Calculate context usage percentage
     pub fn context_percent(&self) -> u8 {
         if self.context_window == 0 {
@@ -42,21 +156,52 @@ impl TranscriptMetrics {
         ((used * 100) / self.context_window).min(100) as u8
     }
 
-    /// Calculate session duration in seconds
+    /// Calculate session duration in seconds, including earlier sessions
     pub fn duration_secs(&self) -> Option<i64> {
         match (self.started_at, self.finished_at) {
-            (Some(start), Some(end)) => Some(end.signed_duration_since(start).num_seconds()),
+            (Some(start), Some(end)) => {
+                Some(self.previous_duration_secs + end.signed_duration_since(start).num_seconds())
+            }
             _ => None,
         }
     }
 }
 
+/// Combine the metrics of a task's sessions, oldest first. Turns, tokens and
+/// durations add up (the gaps between sessions don't count), with messages
+/// that appear in several transcripts (replayed by a resume or fork) counted
+/// once; the current state (context, model, tool, summary, timestamps) is the
+/// latest session's.
+pub fn aggregate(mut sessions: Vec<TranscriptMetrics>) -> Option<TranscriptMetrics> {
+    let mut total = sessions.pop()?;
+    let mut seen: HashSet<String> = total.messages.keys().cloned().collect();
+    for earlier in sessions.iter().rev() {
+        total.output_tokens += earlier.output_tokens;
+        total.num_turns += earlier.num_turns;
+        total.previous_duration_secs += earlier.duration_secs().unwrap_or(0);
+        for (model, usage) in &earlier.usage {
+            total.usage.entry(model.clone()).or_default().add(usage);
+        }
+        for (id, message) in &earlier.messages {
+            if seen.insert(id.clone()) {
+                continue;
+            }
+            total.output_tokens = total.output_tokens.saturating_sub(message.usage.output);
+            total.num_turns = total.num_turns.saturating_sub(message.entries);
+            if let Some(usage) = total.usage.get_mut(&message.model) {
+                usage.sub(&message.usage);
+            }
+        }
+    }
+    Some(total)
+}
+
 /// Convert a filesystem path to Claude Code's project directory name.
 ///
 /// Claude Code escapes paths by replacing `/` and `.` with `-`.
 /// Example: `/Users/foo/project/.wt` -> `-Users-foo-project--wt`
 pub fn project_dir_name(path: &str) -> String {
-    path.replace('/', "-").replace('.', "-")
+    path.replace(['/', '.'], "-")
 }
 
 /// Get the Claude Code projects directory.
@@ -109,91 +254,338 @@ pub fn find_latest_transcript(worktree_path: &str) -> Option<PathBuf> {
         .map(|entry| entry.path())
 }
 
-/// Parse a transcript file and extract metrics.
-pub fn parse_transcript(path: &Path) -> Option<TranscriptMetrics> {
-    let file = File::open(path).ok()?;
-    let reader = BufReader::new(file);
-
-    let mut metrics = TranscriptMetrics::default();
-    metrics.context_window = 200_000; // Default
-
-    let mut last_cache_read: u64 = 0;
-    let mut last_input: u64 = 0;
-    let mut total_output: u64 = 0;
-    let mut turn_count: u32 = 0;
-    let mut last_assistant_text: Option<String> = None;
-    let mut first_timestamp: Option<chrono::DateTime<chrono::Utc>> = None;
-    let mut last_timestamp: Option<chrono::DateTime<chrono::Utc>> = None;
-    let mut last_tool: Option<String> = None;
-
-    for line in reader.lines() {
-        let line = line.ok()?;
-        if line.is_empty() {
-            continue;
-        }
+/// Position up to which a transcript has been read. A transcript only ever
+/// grows, so a cursor stays valid until the file is truncated (shorter than
+/// `offset`), rewritten (same size, new mtime) or replaced (new inode).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptCursor {
+    pub offset: u64,
+    pub inode: u64,
+    pub mtime: Option<SystemTime>,
+}
 
-        if let Ok(entry) = serde_json::from_str::<TranscriptEntry>(&line) {
-            // Extract timestamp from every entry
-            if let Some(ts) = &entry.timestamp {
-                if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(ts) {
-                    let utc = parsed.with_timezone(&chrono::Utc);
-                    if first_timestamp.is_none() {
-                        first_timestamp = Some(utc);
-                    }
-                    last_timestamp = Some(utc);
+/// Lines appended to a transcript since a cursor
+struct Appended {
+    lines: Vec<String>,
+    cursor: TranscriptCursor,
+    /// The cursor was missing or stale, so `lines` start at the beginning
+    reset: bool,
+}
+
+/// Read the complete lines after `cursor`, or the whole file if the cursor
+/// no longer applies. A trailing line without a newline is only taken once
+/// it is a whole JSON value, so an entry being written is read next time.
+fn read_appended(path: &Path, cursor: Option<&TranscriptCursor>) -> Option<Appended> {
+    let meta = fs::metadata(path).ok()?;
+    let (len, inode, mtime) = (meta.len(), meta.ino(), meta.modified().ok());
+
+    let start = cursor
+        .filter(|c| c.inode == inode && c.offset <= len && (c.offset < len || c.mtime == mtime))
+        .map(|c| c.offset);
+    let reset = start.is_none();
+    let start = start.unwrap_or(0);
+
+    // Stop at the size we saw, so the cursor's mtime matches what was read
+    let mut bytes = Vec::new();
+    let mut file = File::open(path).ok()?;
+    file.seek(SeekFrom::Start(start)).ok()?;
+    file.take(len - start).read_to_end(&mut bytes).ok()?;
+
+    let mut consumed = bytes.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+    if serde_json::from_slice::<Value>(&bytes[consumed..]).is_ok() {
+        consumed = bytes.len();
+    }
+    let lines = String::from_utf8_lossy(&bytes[..consumed])
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.to_string())
+        .collect();
+
+    Some(Appended {
+        lines,
+        cursor: TranscriptCursor {
+            offset: start + consumed as u64,
+            inode,
+            mtime,
+        },
+        reset,
+    })
+}
+
+/// Everything read from a transcript so far, updated one line at a time
+#[derive(Default)]
+struct TranscriptState {
+    metrics: TranscriptMetrics,
+    last_cache_read: u64,
+    last_input: u64,
+    last_assistant_text: Option<String>,
+    // (tool_use id, tool name) of the last tool call not yet answered
+    pending: Option<(Option<String>, String)>,
+    // A message split over several entries repeats its usage on each
+    counted_messages: HashSet<String>,
+    /// Text of each assistant message (thinking if it has no text)
+    messages: Vec<String>,
+    /// Every event so far (see [`get_events`])
+    events: Vec<TranscriptEvent>,
+    // Tool names by tool_use id, to name the results
+    tools: HashMap<String, String>,
+    links: SessionLinks,
+}
+
+/// References a transcript holds to entries and sessions, which tie a
+/// resumed or compacted session to the one it continues
+#[derive(Default)]
+struct SessionLinks {
+    uuids: HashSet<String>,
+    /// `parentUuid`, `logicalParentUuid` and `leafUuid` values
+    refs: HashSet<String>,
+    session_ids: HashSet<String>,
+}
+
+impl SessionLinks {
+    /// Whether `self` (the transcript of session `id`) and `other` (of
+    /// `other_id`) refer to each other
+    fn linked(&self, id: &str, other: &SessionLinks, other_id: &str) -> bool {
+        !self.refs.is_disjoint(&other.uuids)
+            || !other.refs.is_disjoint(&self.uuids)
+            || self.session_ids.contains(other_id)
+            || other.session_ids.contains(id)
+    }
+}
+
+impl TranscriptState {
+    fn feed(&mut self, line: &str) {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            return;
+        };
+        entry_events(&value, &mut self.tools, &mut self.events);
+        let Ok(entry) = TranscriptEntry::deserialize(value) else {
+            return;
+        };
+        let links = &mut self.links;
+        links.uuids.extend(entry.uuid.clone());
+        links.session_ids.extend(entry.session_id.clone());
+        links.refs.extend(
+            [&entry.parent_uuid, &entry.logical_parent_uuid, &entry.leaf_uuid]
+                .into_iter()
+                .flatten()
+                .cloned(),
+        );
+
+        let metrics = &mut self.metrics;
+
+        // Extract timestamp from every entry
+        if let Some(ts) = &entry.timestamp {
+            if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(ts) {
+                let utc = parsed.with_timezone(&chrono::Utc);
+                if metrics.started_at.is_none() {
+                    metrics.started_at = Some(utc);
                 }
+                metrics.finished_at = Some(utc);
             }
+        }
 
-            match entry.r#type.as_str() {
-                "assistant" => {
-                    if let Some(msg) = entry.message {
-                        // Extract usage info
-                        if let Some(usage) = msg.usage {
-                            last_cache_read = usage.cache_read_input_tokens.unwrap_or(0);
-                            last_input = usage.input_tokens.unwrap_or(0);
-                            total_output += usage.output_tokens.unwrap_or(0);
+        match entry.r#type.as_str() {
+            "assistant" => {
+                if let Some(msg) = entry.message {
+                    // Synthetic entries (e.g. API errors) aren't from a model
+                    if let Some(model) = msg.model.as_ref().filter(|m| !m.starts_with('<')) {
+                        metrics.model = Some(reported_model(metrics.model.as_deref(), model));
+                    }
+
+                    // Extract usage info
+                    if let Some(usage) = msg.usage {
+                        self.last_cache_read = usage.cache_read_input_tokens.unwrap_or(0);
+                        self.last_input = usage.input_tokens.unwrap_or(0);
+                        let first_entry = msg
+                            .id
+                            .as_ref()
+                            .is_none_or(|id| self.counted_messages.insert(id.clone()));
+                        if first_entry {
+                            let model = msg.model.as_deref().unwrap_or("unknown").to_string();
+                            let tokens = TokenUsage {
+                                input: usage.input_tokens.unwrap_or(0),
+                                output: usage.output_tokens.unwrap_or(0),
+                                cache_read: usage.cache_read_input_tokens.unwrap_or(0),
+                                cache_creation: usage.cache_creation_input_tokens.unwrap_or(0),
+                            };
+                            metrics.output_tokens += tokens.output;
+                            metrics.usage.entry(model.clone()).or_default().add(&tokens);
+                            if let Some(id) = &msg.id {
+                                metrics.messages.insert(id.clone(), MessageUsage { model, usage: tokens, entries: 0 });
+                            }
                         }
+                    }
+                    if let Some(message) = msg.id.as_ref().and_then(|id| metrics.messages.get_mut(id)) {
+                        message.entries += 1;
+                    }
 
-                        // Extract text content for summary and tool usage
-                        if let Some(content) = msg.content {
-                            for item in content {
-                                if item.r#type == "text" {
-                                    if let Some(text) = item.text {
-                                        last_assistant_text = Some(text);
-                                    }
-                                } else if item.r#type == "tool_use" {
-                                    if let Some(name) = &item.name {
-                                        last_tool = Some(name.clone());
-                                    }
+                    // Extract text content for summary and tool usage
+                    if let Some(content) = msg.content {
+                        self.pending = None;
+                        for item in &content {
+                            if item.r#type == "text" {
+                                if let Some(text) = &item.text {
+                                    self.last_assistant_text = Some(text.clone());
+                                }
+                            } else if item.r#type == "tool_use" {
+                                if let Some(name) = &item.name {
+                                    metrics.current_tool = Some(name.clone());
+                                    self.pending = Some((item.id.clone(), name.clone()));
                                 }
                             }
                         }
-
-                        turn_count += 1;
+                        if let Some(text) = message_text(&content) {
+                            self.messages.push(text);
+                        }
                     }
+
+                    metrics.num_turns += 1;
                 }
-                "system" => {
-                    // Check for init entry to get context window
-                    if entry.subtype.as_deref() == Some("init") {
-                        // Could extract model info here if needed
-                    }
+            }
+            "user" => {
+                let answered = entry
+                    .message
+                    .and_then(|msg| msg.content)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|item| item.r#type == "tool_result")
+                    .any(|item| match (&self.pending, &item.tool_use_id) {
+                        (Some((Some(id), _)), Some(result_id)) => id == result_id,
+                        _ => true,
+                    });
+                if answered {
+                    self.pending = None;
                 }
-                _ => {}
             }
+            // The model the session was started with, until it replies
+            "system" if entry.subtype.as_deref() == Some("init") && metrics.model.is_none() => {
+                metrics.model = entry.model;
+            }
+            _ => {}
         }
     }
 
-    // Context = cache_read (history) + input (new tokens)
-    metrics.input_tokens = last_cache_read + last_input;
-    metrics.output_tokens = total_output;
-    metrics.num_turns = turn_count;
-    metrics.summary = last_assistant_text;
-    metrics.completed = turn_count > 0; // Consider completed if there's at least one turn
-    metrics.started_at = first_timestamp;
-    metrics.finished_at = last_timestamp;
-    metrics.current_tool = last_tool;
+    fn metrics(&self) -> TranscriptMetrics {
+        let mut metrics = self.metrics.clone();
+        // Context = cache_read (history) + input (new tokens)
+        metrics.input_tokens = self.last_cache_read + self.last_input;
+        metrics.summary = self.last_assistant_text.clone();
+        metrics.completed = metrics.num_turns > 0; // Consider completed if there's at least one turn
+        metrics.pending_tool = self.pending.as_ref().map(|(_, name)| name.clone());
+        metrics.resolve_context_window(&BTreeMap::new());
+        metrics
+    }
+}
 
-    Some(metrics)
+/// Text of an assistant message: its text parts, or its thinking if it has none
+fn message_text(content: &[ContentItem]) -> Option<String> {
+    let parts = |kind: &str| -> Vec<String> {
+        content
+            .iter()
+            .filter(|item| item.r#type == kind)
+            .filter_map(|item| if kind == "text" { item.text.clone() } else { item.thinking.clone() })
+            .collect()
+    };
+    [parts("text"), parts("thinking")]
+        .into_iter()
+        .find(|p| !p.is_empty())
+        .map(|p| p.join("\n"))
+}
+
+/// Parsed transcripts of this process, so repeated reads (the TUI refreshes
+/// every tick) only parse what was appended since the last one
+static TRANSCRIPTS: Mutex<Option<TranscriptCache>> = Mutex::new(None);
+
+type TranscriptCache = HashMap<PathBuf, (TranscriptCursor, TranscriptState)>;
+
+/// Bring the cached state of a transcript up to date (dropping it if the
+/// file can no longer be read)
+fn update(cache: &mut TranscriptCache, path: &Path) {
+    let cached = cache.remove(path);
+    let Some(appended) = read_appended(path, cached.as_ref().map(|(cursor, _)| cursor)) else {
+        return;
+    };
+    let mut state = match cached {
+        Some((_, state)) if !appended.reset => state,
+        _ => TranscriptState::default(),
+    };
+    for line in &appended.lines {
+        state.feed(line);
+    }
+    cache.insert(path.to_path_buf(), (appended.cursor, state));
+}
+
+/// Bring the cached state of a transcript up to date and read from it
+fn with_state<T>(path: &Path, read: impl FnOnce(&TranscriptState) -> T) -> Option<T> {
+    let mut cache = TRANSCRIPTS.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = cache.get_or_insert_with(HashMap::new);
+    update(cache, path);
+    cache.get(path).map(|(_, state)| read(state))
+}
+
+/// Parse a transcript file and extract metrics.
+pub fn parse_transcript(path: &Path) -> Option<TranscriptMetrics> {
+    with_state(path, TranscriptState::metrics)
+}
+
+/// All transcripts of the sessions `seeds` belong to: those in the same
+/// project directories linked to a seed (directly or through each other) by
+/// entry or session references. Oldest (by first timestamp) first.
+pub fn linked_transcripts(seeds: &[PathBuf]) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = seeds.to_vec();
+    let dirs: HashSet<&Path> = seeds.iter().filter_map(|p| p.parent()).collect();
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.extension().is_some_and(|ext| ext == "jsonl") && !candidates.contains(&path) {
+                candidates.push(path);
+            }
+        }
+    }
+
+    let session_id = |path: &Path| path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let mut cache = TRANSCRIPTS.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = cache.get_or_insert_with(HashMap::new);
+    for path in &candidates {
+        update(cache, path);
+    }
+
+    // Walk the links out from the seeds
+    let mut found: Vec<PathBuf> = seeds.iter().filter(|p| cache.contains_key(*p)).cloned().collect();
+    let mut i = 0;
+    while i < found.len() {
+        let current = found[i].clone();
+        for path in &candidates {
+            if found.contains(path) {
+                continue;
+            }
+            let (Some((_, a)), Some((_, b))) = (cache.get(&current), cache.get(path)) else {
+                continue;
+            };
+            if a.links.linked(&session_id(&current), &b.links, &session_id(path)) {
+                found.push(path.clone());
+            }
+        }
+        i += 1;
+    }
+
+    found.sort_by_key(|p| {
+        let started_at = cache.get(p).and_then(|(_, state)| state.metrics.started_at);
+        (started_at, fs::metadata(p).and_then(|m| m.modified()).ok())
+    });
+    found
+}
+
+/// Get the last N assistant messages from a transcript.
+/// Extracts text content first, falls back to thinking content if no text.
+pub fn get_last_messages(path: &Path, n: usize) -> Option<Vec<String>> {
+    with_state(path, |state| {
+        let start = state.messages.len().saturating_sub(n);
+        state.messages[start..].to_vec()
+    })
 }
 
 // Deserialization structs
@@ -207,10 +599,29 @@ struct TranscriptEntry {
     message: Option<TranscriptMessage>,
     #[serde(default)]
     timestamp: Option<String>,
+    /// Set on the `system/init` entry
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    uuid: Option<String>,
+    #[serde(default, rename = "parentUuid")]
+    parent_uuid: Option<String>,
+    /// Entry a compaction boundary continues from
+    #[serde(default, rename = "logicalParentUuid")]
+    logical_parent_uuid: Option<String>,
+    /// Last entry a `summary` entry summarizes
+    #[serde(default, rename = "leafUuid")]
+    leaf_uuid: Option<String>,
+    #[serde(default, rename = "sessionId")]
+    session_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct TranscriptMessage {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
     #[serde(default)]
     usage: Option<Usage>,
     #[serde(default)]
@@ -222,6 +633,7 @@ struct Usage {
     input_tokens: Option<u64>,
     output_tokens: Option<u64>,
     cache_read_input_tokens: Option<u64>,
+    cache_creation_input_tokens: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -233,85 +645,216 @@ struct ContentItem {
     thinking: Option<String>,
     #[serde(default)]
     name: Option<String>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    tool_use_id: Option<String>,
 }
 
-/// Get the last N assistant messages from a transcript.
-/// Extracts text content first, falls back to thinking content if no text.
-pub fn get_last_messages(path: &Path, n: usize) -> Option<Vec<String>> {
-    let file = File::open(path).ok()?;
-    let reader = BufReader::new(file);
+/// Kind of a [`TranscriptEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// Assistant text
+    Text,
+    Thinking,
+    /// Tool call with its input
+    ToolUse,
+    /// Tool output (truncated)
+    ToolResult,
+    /// Prompt typed or sent to the agent
+    User,
+    /// Session events: init, compaction, summaries
+    System,
+}
 
-    let mut messages: Vec<String> = Vec::new();
+impl EventKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::Text => "text",
+            EventKind::Thinking => "thinking",
+            EventKind::ToolUse => "tool_use",
+            EventKind::ToolResult => "tool_result",
+            EventKind::User => "user",
+            EventKind::System => "system",
+        }
+    }
+}
 
-    for line in reader.lines() {
-        let line = line.ok()?;
-        if line.is_empty() {
-            continue;
+/// One step of a session, as shown by `wt tail --types/--format`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranscriptEvent {
+    #[serde(rename = "type")]
+    pub kind: EventKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    /// Tool name (tool_use, tool_result)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Tool input (tool_use)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<Value>,
+    /// Text, thinking, prompt, tool output or system message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// The tool reported an error (tool_result)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_error: Option<bool>,
+    /// `text` was cut to `TOOL_RESULT_CHARS`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+impl TranscriptEvent {
+    pub fn new(kind: EventKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            timestamp: None,
+            tool: None,
+            input: None,
+            text: Some(text.into()),
+            is_error: None,
+            truncated: false,
         }
+    }
+}
 
-        if let Ok(entry) = serde_json::from_str::<TranscriptEntry>(&line) {
-            if entry.r#type == "assistant" {
-                if let Some(msg) = entry.message {
-                    if let Some(content) = msg.content {
-                        // Try to get text content first
-                        let text_parts: Vec<String> = content
-                            .iter()
-                            .filter(|item| item.r#type == "text")
-                            .filter_map(|item| item.text.clone())
-                            .collect();
-
-                        if !text_parts.is_empty() {
-                            messages.push(text_parts.join("\n"));
-                        } else {
-                            // Fall back to thinking content
-                            let thinking_parts: Vec<String> = content
-                                .iter()
-                                .filter(|item| item.r#type == "thinking")
-                                .filter_map(|item| item.thinking.clone())
-                                .collect();
-
-                            if !thinking_parts.is_empty() {
-                                messages.push(thinking_parts.join("\n"));
-                            }
+/// Every event of a transcript, in order
+pub fn get_events(path: &Path) -> Option<Vec<TranscriptEvent>> {
+    with_state(path, |state| state.events.clone())
+}
+
+fn entry_events(entry: &Value, tools: &mut HashMap<String, String>, events: &mut Vec<TranscriptEvent>) {
+    let timestamp = entry
+        .get("timestamp")
+        .and_then(|t| t.as_str())
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&chrono::Utc));
+    let str_field = |value: &Value, key: &str| value.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+    let mut push = |mut event: TranscriptEvent| {
+        event.timestamp = timestamp;
+        events.push(event);
+    };
+
+    let content = entry.pointer("/message/content");
+    match entry.get("type").and_then(|t| t.as_str()) {
+        Some("assistant") => {
+            for item in content.and_then(|c| c.as_array()).into_iter().flatten() {
+                match item.get("type").and_then(|t| t.as_str()) {
+                    Some("text") => push(TranscriptEvent::new(EventKind::Text, str_field(item, "text").unwrap_or_default())),
+                    Some("thinking") => push(TranscriptEvent::new(
+                        EventKind::Thinking,
+                        str_field(item, "thinking").unwrap_or_default(),
+                    )),
+                    Some("tool_use") => {
+                        let name = str_field(item, "name").unwrap_or_default();
+                        if let Some(id) = str_field(item, "id") {
+                            tools.insert(id, name.clone());
                         }
+                        push(TranscriptEvent {
+                            tool: Some(name),
+                            input: item.get("input").cloned(),
+                            text: None,
+                            ..TranscriptEvent::new(EventKind::ToolUse, "")
+                        });
                     }
+                    _ => {}
                 }
             }
         }
+        // Meta entries are context Claude Code adds, not prompts
+        Some("user") if entry.get("isMeta").and_then(|m| m.as_bool()) != Some(true) => match content {
+            Some(Value::String(prompt)) => push(TranscriptEvent::new(EventKind::User, prompt.clone())),
+            Some(Value::Array(items)) => {
+                for item in items {
+                    match item.get("type").and_then(|t| t.as_str()) {
+                        Some("text") => push(TranscriptEvent::new(EventKind::User, str_field(item, "text").unwrap_or_default())),
+                        Some("tool_result") => {
+                            let output = item.get("content").map(result_text).unwrap_or_default();
+                            let truncated = output.chars().count() > TOOL_RESULT_CHARS;
+                            let text = if truncated {
+                                output.chars().take(TOOL_RESULT_CHARS).collect::<String>() + "…"
+                            } else {
+                                output
+                            };
+                            push(TranscriptEvent {
+                                tool: str_field(item, "tool_use_id").and_then(|id| tools.get(&id).cloned()),
+                                is_error: Some(item.get("is_error").and_then(|e| e.as_bool()).unwrap_or(false)),
+                                truncated,
+                                ..TranscriptEvent::new(EventKind::ToolResult, text)
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        },
+        Some("system") => {
+            let text = str_field(entry, "content")
+                .or_else(|| str_field(entry, "subtype"))
+                .unwrap_or_default();
+            push(TranscriptEvent::new(EventKind::System, text));
+        }
+        Some("summary") => push(TranscriptEvent::new(EventKind::System, str_field(entry, "summary").unwrap_or_default())),
+        _ => {}
+    }
+}
+
+/// Output of a tool result: a string, or the text parts of a content list
+fn result_text(content: &Value) -> String {
+    match content {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
     }
+}
 
-    // Return last N messages
-    let start = messages.len().saturating_sub(n);
-    Some(messages[start..].to_vec())
+/// Saved next to a log by [`extract_to_log`], so the next run only appends
+/// what the transcript gained
+#[derive(Debug, Serialize, Deserialize)]
+struct LogCursor {
+    cursor: TranscriptCursor,
+    /// Entries in the log
+    count: usize,
+    exclude_types: Vec<String>,
+    exclude_fields: Vec<String>,
 }
 
 /// Extract filtered transcript to a log file.
-/// Returns the number of entries written.
+/// Returns the number of entries in the log.
+///
+/// Only entries appended since the last extraction are filtered and added;
+/// the log is rewritten when it is missing, the filters changed, or the
+/// transcript was truncated or replaced.
 pub fn extract_to_log(
     transcript_path: &Path,
     log_path: &Path,
     exclude_types: &[String],
     exclude_fields: &[String],
 ) -> Option<usize> {
-    let file = File::open(transcript_path).ok()?;
-    let reader = BufReader::new(file);
+    let cursor_path = log_path.with_extension("cursor");
+    let previous = fs::read_to_string(&cursor_path)
+        .ok()
+        .and_then(|s| serde_json::from_str::<LogCursor>(&s).ok())
+        .filter(|c| c.exclude_types == exclude_types && c.exclude_fields == exclude_fields && log_path.exists());
 
-    // Ensure parent directory exists
-    if let Some(parent) = log_path.parent() {
-        std::fs::create_dir_all(parent).ok()?;
-    }
-
-    let mut output = File::create(log_path).ok()?;
-    let mut count = 0;
-
-    for line in reader.lines() {
-        let line = line.ok()?;
-        if line.is_empty() {
-            continue;
-        }
+    let appended = read_appended(transcript_path, previous.as_ref().map(|c| &c.cursor))?;
+    let append = previous.is_some() && !appended.reset;
+    let mut count = match &previous {
+        Some(c) if append => c.count,
+        _ => 0,
+    };
 
+    // Filter everything before writing, so a bad line leaves the log as it was
+    let mut filtered = String::new();
+    for line in &appended.lines {
         // Parse as generic JSON
-        let mut json: Value = serde_json::from_str(&line).ok()?;
+        let mut json: Value = serde_json::from_str(line).ok()?;
 
         // Check if type should be excluded
         if let Some(entry_type) = json.get("type").and_then(|v| v.as_str()) {
@@ -323,11 +866,31 @@ pub fn extract_to_log(
         // Remove excluded fields recursively
         remove_fields(&mut json, exclude_fields);
 
-        // Write filtered entry
-        writeln!(output, "{}", serde_json::to_string(&json).ok()?).ok()?;
+        filtered.push_str(&serde_json::to_string(&json).ok()?);
+        filtered.push('\n');
         count += 1;
     }
 
+    // Ensure parent directory exists
+    if let Some(parent) = log_path.parent() {
+        fs::create_dir_all(parent).ok()?;
+    }
+
+    let mut output = if append {
+        OpenOptions::new().append(true).open(log_path).ok()?
+    } else {
+        File::create(log_path).ok()?
+    };
+    output.write_all(filtered.as_bytes()).ok()?;
+
+    let saved = LogCursor {
+        cursor: appended.cursor,
+        count,
+        exclude_types: exclude_types.to_vec(),
+        exclude_fields: exclude_fields.to_vec(),
+    };
+    fs::write(&cursor_path, serde_json::to_string(&saved).ok()?).ok()?;
+
     Some(count)
 }
 
@@ -390,6 +953,124 @@ mod tests {
         assert_eq!(metrics.context_percent(), 30);
     }
 
+    fn parse(lines: &[&str]) -> TranscriptMetrics {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        parse_transcript(&path).unwrap()
+    }
+
+    const TOOL_USE: &str = r#"{"type":"assistant","message":{"content":[{"type":"tool_use","id":"toolu_1","name":"Bash"}]}}"#;
+
+    #[test]
+    fn test_pending_tool_without_result() {
+        let metrics = parse(&[TOOL_USE]);
+        assert_eq!(metrics.pending_tool.as_deref(), Some("Bash"));
+        assert_eq!(metrics.current_tool.as_deref(), Some("Bash"));
+    }
+
+    #[test]
+    fn test_tool_result_clears_pending_tool() {
+        let metrics = parse(&[
+            TOOL_USE,
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"toolu_1"}]}}"#,
+        ]);
+        assert_eq!(metrics.pending_tool, None);
+
+        // A result for another call doesn't answer this one
+        let metrics = parse(&[
+            TOOL_USE,
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"toolu_0"}]}}"#,
+        ]);
+        assert_eq!(metrics.pending_tool.as_deref(), Some("Bash"));
+    }
+
+    #[test]
+    fn test_later_text_clears_pending_tool() {
+        let metrics = parse(&[
+            TOOL_USE,
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"done"}]}}"#,
+        ]);
+        assert_eq!(metrics.pending_tool, None);
+    }
+
+    #[test]
+    fn test_usage_per_model_counts_each_message_once() {
+        let metrics = parse(&[
+            r#"{"type":"assistant","message":{"id":"m1","model":"claude-opus-4-5","usage":{"input_tokens":10,"output_tokens":5,"cache_read_input_tokens":100,"cache_creation_input_tokens":20},"content":[{"type":"thinking","thinking":"hm"}]}}"#,
+            r#"{"type":"assistant","message":{"id":"m1","model":"claude-opus-4-5","usage":{"input_tokens":10,"output_tokens":5,"cache_read_input_tokens":100,"cache_creation_input_tokens":20},"content":[{"type":"text","text":"hi"}]}}"#,
+            r#"{"type":"assistant","message":{"id":"m2","model":"claude-haiku-4-5","usage":{"input_tokens":3,"output_tokens":2},"content":[]}}"#,
+        ]);
+
+        assert_eq!(
+            metrics.usage["claude-opus-4-5"],
+            TokenUsage { input: 10, output: 5, cache_read: 100, cache_creation: 20 }
+        );
+        assert_eq!(metrics.usage["claude-haiku-4-5"].total(), 5);
+        assert_eq!(metrics.output_tokens, 7);
+    }
+
+    #[test]
+    fn test_model_from_init_then_assistant() {
+        let init = r#"{"type":"system","subtype":"init","model":"claude-opus-4-5"}"#;
+        assert_eq!(parse(&[init]).model.as_deref(), Some("claude-opus-4-5"));
+
+        let metrics = parse(&[
+            init,
+            r#"{"type":"assistant","message":{"model":"claude-sonnet-4-5-20250929","content":[]}}"#,
+            r#"{"type":"assistant","message":{"model":"<synthetic>","content":[]}}"#,
+        ]);
+        assert_eq!(metrics.model.as_deref(), Some("claude-sonnet-4-5-20250929"));
+        assert_eq!(metrics.context_window, 200_000);
+    }
+
+    #[test]
+    fn test_long_context_init_model_survives_assistant_reply() {
+        let metrics = parse(&[
+            r#"{"type":"system","subtype":"init","model":"claude-sonnet-4-5[1m]"}"#,
+            r#"{"type":"assistant","message":{"id":"m1","model":"claude-sonnet-4-5-20250929","usage":{"input_tokens":10,"output_tokens":1},"content":[]}}"#,
+        ]);
+        assert_eq!(metrics.model.as_deref(), Some("claude-sonnet-4-5-20250929[1m]"));
+        assert_eq!(metrics.context_window, 1_000_000);
+        // Usage stays keyed by the reported ID
+        assert!(metrics.usage.contains_key("claude-sonnet-4-5-20250929"));
+
+        // Switching to another model drops it
+        let metrics = parse(&[
+            r#"{"type":"system","subtype":"init","model":"claude-sonnet-4-5[1m]"}"#,
+            r#"{"type":"assistant","message":{"model":"claude-opus-4-5","content":[]}}"#,
+        ]);
+        assert_eq!(metrics.model.as_deref(), Some("claude-opus-4-5"));
+        assert_eq!(metrics.context_window, 200_000);
+    }
+
+    #[test]
+    fn test_context_window_for() {
+        let none = BTreeMap::new();
+        assert_eq!(context_window_for(None, &none), 200_000);
+        assert_eq!(context_window_for(Some("claude-sonnet-4-5"), &none), 200_000);
+        assert_eq!(context_window_for(Some("claude-sonnet-4-5[1m]"), &none), 1_000_000);
+        assert_eq!(context_window_for(Some("claude-2.0"), &none), 100_000);
+        assert_eq!(context_window_for(Some("claude-2.1"), &none), 200_000);
+
+        let overrides = BTreeMap::from([
+            ("sonnet".to_string(), 500_000),
+            ("sonnet-4-5".to_string(), 1_000_000),
+        ]);
+        assert_eq!(context_window_for(Some("claude-sonnet-4-5-20250929"), &overrides), 1_000_000);
+        assert_eq!(context_window_for(Some("claude-sonnet-4"), &overrides), 500_000);
+        assert_eq!(context_window_for(Some("claude-haiku-4-5"), &overrides), 200_000);
+    }
+
+    #[test]
+    fn test_context_larger_than_window_means_long_context() {
+        let metrics = parse(&[
+            r#"{"type":"assistant","message":{"id":"m1","model":"claude-sonnet-4-5","usage":{"input_tokens":10,"output_tokens":0,"cache_read_input_tokens":300000},"content":[]}}"#,
+        ]);
+        assert_eq!(metrics.context_window, 1_000_000);
+        assert_eq!(metrics.context_percent(), 30);
+    }
+
     #[test]
     fn test_context_percent_zero_window() {
         let metrics = TranscriptMetrics {
@@ -400,4 +1081,221 @@ mod tests {
         };
         assert_eq!(metrics.context_percent(), 0);
     }
+
+    fn reply(id: &str, text: &str) -> String {
+        format!(
+            r#"{{"type":"assistant","message":{{"id":"{}","usage":{{"output_tokens":10}},"content":[{{"type":"text","text":"{}"}}]}}}}"#,
+            id, text
+        )
+    }
+
+    fn append(path: &Path, text: &str) {
+        let mut file = OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn test_parse_reads_appended_lines() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        fs::write(&path, format!("{}\n", reply("m1", "one"))).unwrap();
+        assert_eq!(parse_transcript(&path).unwrap().output_tokens, 10);
+
+        append(&path, &format!("{}\n", reply("m2", "two")));
+        let metrics = parse_transcript(&path).unwrap();
+        assert_eq!(metrics.output_tokens, 20);
+        assert_eq!(metrics.num_turns, 2);
+        assert_eq!(metrics.summary.as_deref(), Some("two"));
+        assert_eq!(get_last_messages(&path, 5).unwrap(), vec!["one", "two"]);
+    }
+
+    #[test]
+    fn test_partial_line_is_read_once_complete() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let line = reply("m1", "one");
+        let (head, tail) = line.split_at(20);
+        fs::write(&path, head).unwrap();
+        assert_eq!(parse_transcript(&path).unwrap().num_turns, 0);
+
+        append(&path, &format!("{}\n", tail));
+        assert_eq!(parse_transcript(&path).unwrap().num_turns, 1);
+    }
+
+    #[test]
+    fn test_parse_after_truncation() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        fs::write(&path, format!("{}\n{}\n", reply("m1", "one"), reply("m2", "two"))).unwrap();
+        assert_eq!(parse_transcript(&path).unwrap().num_turns, 2);
+
+        // Same inode, shorter than the cursor
+        fs::write(&path, format!("{}\n", reply("m3", "three"))).unwrap();
+        let metrics = parse_transcript(&path).unwrap();
+        assert_eq!(metrics.num_turns, 1);
+        assert_eq!(metrics.output_tokens, 10);
+        assert_eq!(get_last_messages(&path, 5).unwrap(), vec!["three"]);
+    }
+
+    #[test]
+    fn test_parse_after_rotation() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        fs::write(&path, format!("{}\n", reply("m1", "one"))).unwrap();
+        assert_eq!(parse_transcript(&path).unwrap().summary.as_deref(), Some("one"));
+
+        // A new file moved into place, longer than the old one
+        let rotated = dir.path().join("new.jsonl");
+        fs::write(&rotated, format!("{}\n{}\n", reply("m2", "two"), reply("m3", "three"))).unwrap();
+        fs::rename(&rotated, &path).unwrap();
+        let metrics = parse_transcript(&path).unwrap();
+        assert_eq!(metrics.num_turns, 2);
+        assert_eq!(metrics.summary.as_deref(), Some("three"));
+    }
+
+    #[test]
+    fn test_extract_to_log_appends_new_entries() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let log = dir.path().join("logs/session.jsonl");
+        let exclude = vec!["system".to_string()];
+        fs::write(&path, format!("{}\n{{\"type\":\"system\"}}\n", reply("m1", "one"))).unwrap();
+
+        assert_eq!(extract_to_log(&path, &log, &exclude, &[]), Some(1));
+        append(&path, &format!("{}\n", reply("m2", "two")));
+        assert_eq!(extract_to_log(&path, &log, &exclude, &[]), Some(2));
+        assert_eq!(fs::read_to_string(&log).unwrap().lines().count(), 2);
+
+        // Changed filters rewrite the log
+        assert_eq!(extract_to_log(&path, &log, &[], &[]), Some(3));
+        assert_eq!(fs::read_to_string(&log).unwrap().lines().count(), 3);
+
+        // So does a truncated transcript
+        fs::write(&path, format!("{}\n", reply("m3", "three"))).unwrap();
+        assert_eq!(extract_to_log(&path, &log, &[], &[]), Some(1));
+        assert!(fs::read_to_string(&log).unwrap().contains("three"));
+    }
+
+    #[test]
+    fn test_linked_transcripts_follow_references() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let first = dir.path().join("s1.jsonl");
+        let resumed = dir.path().join("s2.jsonl");
+        let compacted = dir.path().join("s3.jsonl");
+        let unrelated = dir.path().join("s4.jsonl");
+        fs::write(&first, "{\"type\":\"user\",\"uuid\":\"a\",\"sessionId\":\"s1\"}\n").unwrap();
+        // Resumed session continues from the first one's last entry
+        fs::write(
+            &resumed,
+            "{\"type\":\"user\",\"uuid\":\"b\",\"parentUuid\":\"a\",\"sessionId\":\"s2\"}\n",
+        )
+        .unwrap();
+        // Compacted session names the resumed one
+        fs::write(&compacted, "{\"type\":\"user\",\"uuid\":\"c\",\"sessionId\":\"s2\"}\n").unwrap();
+        fs::write(&unrelated, "{\"type\":\"user\",\"uuid\":\"d\",\"sessionId\":\"s4\"}\n").unwrap();
+
+        let mut found = linked_transcripts(std::slice::from_ref(&first));
+        found.sort();
+        assert_eq!(found, vec![first, resumed, compacted]);
+    }
+
+    #[test]
+    fn test_aggregate_sums_sessions() {
+        let at = |s: &str| Some(chrono::DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&chrono::Utc));
+        let earlier = TranscriptMetrics {
+            output_tokens: 100,
+            num_turns: 3,
+            started_at: at("2025-01-01T00:00:00Z"),
+            finished_at: at("2025-01-01T00:10:00Z"),
+            summary: Some("old".to_string()),
+            ..Default::default()
+        };
+        let latest = TranscriptMetrics {
+            output_tokens: 50,
+            num_turns: 2,
+            started_at: at("2025-01-02T00:00:00Z"),
+            finished_at: at("2025-01-02T00:05:00Z"),
+            summary: Some("new".to_string()),
+            ..Default::default()
+        };
+
+        let total = aggregate(vec![earlier, latest]).unwrap();
+        assert_eq!(total.output_tokens, 150);
+        assert_eq!(total.num_turns, 5);
+        assert_eq!(total.duration_secs(), Some(900));
+        assert_eq!(total.summary.as_deref(), Some("new"));
+        assert!(aggregate(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_aggregate_counts_replayed_messages_once() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let assistant = |uuid: &str, id: &str, output: u64| {
+            format!(
+                r#"{{"type":"assistant","uuid":"{}","message":{{"id":"{}","model":"claude-sonnet-4-5","content":[{{"type":"text","text":"hi"}}],"usage":{{"input_tokens":10,"output_tokens":{}}}}}}}"#,
+                uuid, id, output
+            )
+        };
+        let first = dir.path().join("first.jsonl");
+        fs::write(&first, format!("{}\n{}\n", assistant("u1", "msg_1", 100), assistant("u2", "msg_2", 20))).unwrap();
+        // Resumed session replays msg_2 before continuing
+        let second = dir.path().join("second.jsonl");
+        fs::write(&second, format!("{}\n{}\n", assistant("u2", "msg_2", 20), assistant("u3", "msg_3", 5))).unwrap();
+
+        let sessions = [&first, &second].iter().filter_map(|p| parse_transcript(p)).collect();
+        let total = aggregate(sessions).unwrap();
+        assert_eq!(total.output_tokens, 125);
+        assert_eq!(total.num_turns, 3);
+        let usage = &total.usage["claude-sonnet-4-5"];
+        assert_eq!((usage.input, usage.output), (30, 125));
+    }
+
+    #[test]
+    fn test_get_events() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let long = "x".repeat(TOOL_RESULT_CHARS + 10);
+        let lines = [
+            r#"{"type":"system","subtype":"init","timestamp":"2025-01-01T00:00:00Z"}"#.to_string(),
+            r#"{"type":"user","message":{"role":"user","content":"fix the bug"}}"#.to_string(),
+            r#"{"type":"user","isMeta":true,"message":{"role":"user","content":"caveat"}}"#.to_string(),
+            r#"{"type":"assistant","message":{"content":[{"type":"thinking","thinking":"hm"},{"type":"tool_use","id":"t1","name":"Bash","input":{"command":"ls"}}]}}"#.to_string(),
+            format!(
+                r#"{{"type":"user","message":{{"content":[{{"type":"tool_result","tool_use_id":"t1","content":[{{"type":"text","text":"{}"}}]}}]}}}}"#,
+                long
+            ),
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"done"}]}}"#.to_string(),
+        ];
+        fs::write(&path, lines.join("\n")).unwrap();
+
+        let events = get_events(&path).unwrap();
+        let kinds: Vec<EventKind> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                EventKind::System,
+                EventKind::User,
+                EventKind::Thinking,
+                EventKind::ToolUse,
+                EventKind::ToolResult,
+                EventKind::Text
+            ]
+        );
+        assert_eq!(events[0].text.as_deref(), Some("init"));
+        assert!(events[0].timestamp.is_some());
+        assert_eq!(events[1].text.as_deref(), Some("fix the bug"));
+        assert_eq!(events[3].input, Some(serde_json::json!({"command": "ls"})));
+        let result = &events[4];
+        assert_eq!(result.tool.as_deref(), Some("Bash"));
+        assert_eq!(result.is_error, Some(false));
+        assert!(result.truncated);
+        assert_eq!(result.text.as_ref().unwrap().chars().count(), TOOL_RESULT_CHARS + 1);
+
+        // Appended entries extend the cached events
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, r#"{{"type":"assistant","message":{{"content":[{{"type":"text","text":"more"}}]}}}}"#).unwrap();
+        let events = get_events(&path).unwrap();
+        assert_eq!(events.len(), 7);
+        assert_eq!(events[6].text.as_deref(), Some("more"));
+    }
 }
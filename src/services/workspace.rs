@@ -1,9 +1,12 @@
 //! Workspace initialization utilities for worktree setup.
 
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::time::Instant;
 
+use crate::constants::CHECK_OUTPUT_LINES;
 use crate::error::{Result, WtError};
+use crate::models::CheckResult;
 
 /// Helper for initializing a worktree workspace.
 pub struct WorkspaceInitializer<'a> {
@@ -99,6 +102,43 @@ impl<'a> WorkspaceInitializer<'a> {
 
         Ok(())
     }
+
+    /// Run an acceptance check in the worktree directory, capturing its output.
+    ///
+    /// A command that cannot be spawned counts as a failed check.
+    pub fn run_check(&self, command: &str) -> CheckResult {
+        let started = Instant::now();
+        let output = Command::new("bash")
+            .arg("-c")
+            .arg(command)
+            .current_dir(self.worktree_path)
+            .stdin(Stdio::null())
+            .output();
+        let duration_ms = started.elapsed().as_millis() as u64;
+
+        let (passed, exit_code, text) = match output {
+            Ok(out) => {
+                let mut text = String::from_utf8_lossy(&out.stdout).into_owned();
+                text.push_str(&String::from_utf8_lossy(&out.stderr));
+                (out.status.success(), out.status.code(), text)
+            }
+            Err(e) => (false, None, e.to_string()),
+        };
+
+        CheckResult {
+            command: command.to_string(),
+            passed,
+            exit_code,
+            duration_ms,
+            output: tail_lines(&text, CHECK_OUTPUT_LINES),
+        }
+    }
+}
+
+/// Last `n` non-empty lines of `text`
+fn tail_lines(text: &str, n: usize) -> String {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[lines.len().saturating_sub(n)..].join("\n")
 }
 
 #[cfg(test)]
@@ -180,6 +220,36 @@ mod tests {
         assert!(dest_dir.path().join("test.txt").exists());
     }
 
+    #[test]
+    fn test_run_check_captures_result() {
+        let dest_dir = TempDir::new().unwrap();
+        let src_dir = TempDir::new().unwrap();
+        std::fs::write(dest_dir.path().join("marker"), "").unwrap();
+
+        let init = WorkspaceInitializer::new(
+            dest_dir.path().to_str().unwrap(),
+            src_dir.path(),
+        );
+
+        // Runs in the worktree
+        let result = init.run_check("test -f marker && echo ok");
+        assert!(result.passed);
+        assert_eq!(result.exit_code, Some(0));
+        assert_eq!(result.output, "ok");
+
+        let result = init.run_check("echo broken >&2; exit 3");
+        assert!(!result.passed);
+        assert_eq!(result.exit_code, Some(3));
+        assert_eq!(result.output, "broken");
+    }
+
+    #[test]
+    fn test_tail_lines() {
+        assert_eq!(tail_lines("a\n\nb\nc\n", 2), "b\nc");
+        assert_eq!(tail_lines("a\n", 5), "a");
+        assert_eq!(tail_lines("", 5), "");
+    }
+
     #[test]
     fn test_run_init_script_failure() {
         let dest_dir = TempDir::new().unwrap();
@@ -4,9 +4,10 @@ use std::time::SystemTime;
 
 use crate::constants::IDLE_THRESHOLD_SECS;
 use crate::display::format_duration;
-use crate::error::Result;
-use crate::models::{TaskStatus, TaskStore, WtConfig};
-use crate::services::{git, tmux, transcript};
+use crate::error::{Result, WtError};
+use crate::models::{Actor, SentMessage, TaskStatus, TaskStore, WtConfig};
+use crate::services::template::TemplateContext;
+use crate::services::{agent, cost, git, tmux};
 
 /// Action to perform after TUI exits or during TUI
 #[derive(Debug, Clone)]
@@ -21,10 +22,14 @@ pub enum TuiAction {
     ShowResume {
         worktree: String,
         session_id: String,
-        claude_command: String,
+        command: String,
     },
     /// Tail a task's transcript
     Tail { name: String },
+    /// Run `wt done` on a task (its checks print their progress)
+    Done { name: String },
+    /// Resume a Done task's agent session (tmux window closed)
+    Resume { name: String },
 }
 
 /// Task with computed metrics for display
@@ -35,24 +40,35 @@ pub struct TaskDisplay {
     pub status: TaskStatus,
     pub duration: Option<String>,
     pub context_percent: u8,
+    /// Model ID from the transcript
+    pub model: Option<String>,
     pub additions: i32,
     pub deletions: i32,
     pub active: bool,
     pub tmux_alive: bool,
+    /// Agent is blocked on a permission or input prompt
+    pub waiting_for_input: bool,
     pub worktree_path: Option<String>,
     pub tmux_session: Option<String>,
     pub tmux_window: Option<String>,
     pub session_id: Option<String>,
+    /// Agent resume command (None if the agent cannot resume)
+    pub resume_command: Option<String>,
     pub commit_count: i32,
     pub has_conflict: bool,
     pub current_tool: Option<String>,
+    /// Estimated cost across all of the task's sessions
+    pub cost_usd: f64,
 }
 
 /// Application state
 pub struct App {
     pub tasks: Vec<TaskDisplay>,
     pub selected: usize,
-    #[allow(dead_code)]
+    /// Error from the last key action, shown in the footer until the next key
+    pub message: Option<String>,
+    /// Text being typed for `s` (send), shown in the footer while composing
+    pub input: Option<String>,
     config: Option<WtConfig>,
 }
 
@@ -63,6 +79,8 @@ impl App {
         let mut app = Self {
             tasks: Vec::new(),
             selected: 0,
+            message: None,
+            input: None,
             config,
         };
         app.refresh()?;
@@ -71,27 +89,27 @@ impl App {
 
     /// Refresh task data from disk
     pub fn refresh(&mut self) -> Result<()> {
-        use std::collections::HashMap;
-
         let mut store = TaskStore::load()?;
         let mut tasks = Vec::new();
-        let mut status_changed = false;
+
+        // Detect branches merged outside wt (e.g. PR landed) before displaying
+        let config_base = self.config.as_ref().and_then(|c| c.base_branch.clone());
+        let prices = self.config.as_ref().map(|c| c.prices.clone()).unwrap_or_default();
+        let context_windows = self.config.as_ref().map(|c| c.context_windows.clone()).unwrap_or_default();
+        let checks = self.config.as_ref().map(|c| c.checks.clone()).unwrap_or_default();
+        if self.config.as_ref().is_some_and(|c| c.auto_sync) {
+            let _ = crate::commands::sync::sync_merged(&mut store, config_base.as_deref(), true);
+        }
 
         // Collect task names first to avoid borrow conflict
         let task_names: Vec<String> = store.list().iter().map(|t| t.name().to_string()).collect();
 
-        // Build name -> index mapping (1-based)
-        let index_map: HashMap<&str, usize> = task_names
-            .iter()
-            .enumerate()
-            .map(|(i, name)| (name.as_str(), i + 1))
-            .collect();
+        let index_map = store.index_map();
+        let repo_root = git::get_repo_root().unwrap_or_else(|_| ".".to_string());
 
         for task_name in &task_names {
             // Auto-mark as Done if Running but tmux window is closed
-            if store.auto_mark_done_if_needed(task_name)? {
-                status_changed = true;
-            }
+            store.auto_mark_done_if_needed(task_name, &checks)?;
 
             let status = store.get_status(task_name);
 
@@ -113,10 +131,13 @@ impl App {
 
             let final_status = status;
 
-            // Parse transcript for metrics (duration, context, etc.)
-            let transcript_metrics = instance
-                .and_then(transcript::find_transcript_for_instance)
-                .and_then(|p| transcript::parse_transcript(&p));
+            // Parse transcripts for metrics (duration, context, etc.), summed over the sessions
+            let mut transcript_metrics =
+                instance.and_then(|inst| agent::for_instance(inst).task_metrics(task_name, inst));
+
+            if let Some(m) = transcript_metrics.as_mut().filter(|m| m.model.is_some()) {
+                m.resolve_context_window(&context_windows);
+            }
 
             // Duration from transcript timestamps
             let duration = transcript_metrics
@@ -125,9 +146,10 @@ impl App {
                 .map(format_duration);
 
             // Git metrics (additions, deletions, commits, conflict)
+            let base_branch = store.base_branch(task_name, config_base.as_deref());
             let git_metrics = worktree_path
                 .as_deref()
-                .and_then(git::get_worktree_metrics);
+                .and_then(|path| git::get_worktree_metrics(path, &base_branch));
             let (additions, deletions) = git_metrics
                 .as_ref()
                 .map(|m| (m.additions, m.deletions))
@@ -153,6 +175,13 @@ impl App {
                 .map(|m| m.context_percent())
                 .unwrap_or(0);
 
+            // Blocked on a prompt (only checked while the window is alive)
+            let waiting_for_input = final_status == TaskStatus::Running
+                && tmux_alive
+                && instance.is_some_and(agent::waiting_for_input);
+
+            let model = transcript_metrics.as_ref().and_then(|m| m.model.clone());
+
             // Current tool from transcript
             let current_tool = transcript_metrics
                 .as_ref()
@@ -164,6 +193,21 @@ impl App {
                 .map(|m| (m.commits, m.has_conflict))
                 .unwrap_or((0, false));
 
+            // Command to pick the agent session up again once its window is gone
+            let resume_command = instance.zip(self.config.as_ref()).and_then(|(inst, config)| {
+                let vars = TemplateContext::for_task(config, &repo_root, task_name, Some(task), Some(inst));
+                let settings = config.launch_settings(Some(task));
+                agent::for_instance(inst).resume_command(&settings, &vars, inst).ok().flatten()
+            });
+
+            // Token cost across every session of the task
+            let cost_usd = instance
+                .map(|inst| {
+                    let usage = cost::task_usage(task_name, inst);
+                    cost::estimate(&usage, &prices).usd
+                })
+                .unwrap_or(0.0);
+
             // Get tmux and session info
             let (tmux_session, tmux_window, session_id) = instance
                 .map(|i| {
@@ -181,25 +225,24 @@ impl App {
                 status: final_status,
                 duration,
                 context_percent,
+                model,
                 additions,
                 deletions,
                 active,
                 tmux_alive,
+                waiting_for_input,
                 worktree_path,
                 tmux_session,
                 tmux_window,
                 session_id,
+                resume_command,
                 commit_count,
                 has_conflict,
                 current_tool,
+                cost_usd,
             });
         }
 
-        // Save status if any task was auto-marked as Done
-        if status_changed {
-            store.save_status()?;
-        }
-
         self.tasks = tasks;
 
         // Adjust selection if out of bounds
@@ -236,6 +279,23 @@ impl App {
             .unwrap_or(false)
     }
 
+    /// Check if a message can be sent to the selected task (Running, tmux alive)
+    pub fn can_send(&self) -> bool {
+        self.selected_task()
+            .map(|t| t.status == TaskStatus::Running && t.tmux_alive)
+            .unwrap_or(false)
+    }
+
+    /// Send a message to the selected task's agent
+    pub fn send_message(&mut self, text: &str) -> Result<SentMessage> {
+        let task = self
+            .selected_task()
+            .ok_or_else(|| WtError::InvalidInput("No task selected".to_string()))?;
+        let name = task.name.clone();
+        let mut store = TaskStore::load()?;
+        crate::commands::send::send_message(&mut store, &name, text, Actor::Tui)
+    }
+
     /// Check if selected task can be marked as merged (Done status)
     pub fn can_mark_merged(&self) -> bool {
         self.selected_task()
@@ -243,26 +303,31 @@ impl App {
             .unwrap_or(false)
     }
 
-    /// Mark selected task as done (closes tmux if still running)
-    pub fn mark_done(&mut self) -> Result<()> {
+    /// Mark selected task as done after its acceptance checks pass
+    /// (closes tmux if still running)
+    pub fn mark_done(&mut self, force: bool) -> Result<()> {
         if let Some(task) = self.selected_task() {
             if task.status == TaskStatus::Running {
                 let name = task.name.clone();
-
-                // Close tmux window if still alive
-                if let (Some(session), Some(window)) = (&task.tmux_session, &task.tmux_window) {
-                    tmux::kill_window_if_exists(session, window).ok();
-                }
-
                 let mut store = TaskStore::load()?;
-                store.set_status(&name, TaskStatus::Done);
-                store.save_status()?;
+                let result = crate::commands::done::mark_done(&mut store, &name, force, Actor::Tui, true);
                 self.refresh()?;
+                result?;
             }
         }
         Ok(())
     }
 
+    /// Get action to mark the selected task done. The acceptance checks can
+    /// take minutes, so they run outside the TUI like `wt done`.
+    pub fn done_action(&self) -> Option<TuiAction> {
+        self.selected_task()
+            .filter(|task| task.status == TaskStatus::Running)
+            .map(|task| TuiAction::Done {
+                name: task.name.clone(),
+            })
+    }
+
     /// Mark selected task as merged
     pub fn mark_merged(&mut self) -> Result<()> {
         if let Some(task) = self.selected_task() {
@@ -301,7 +366,8 @@ impl App {
 
     /// Get action for Enter key on selected task
     /// - Inside tmux + window exists: attach to it
-    /// - Inside tmux + window closed: show resume command
+    /// - Window closed, task Done: resume the agent session
+    /// - Window closed otherwise: show resume command
     /// - Outside tmux: show tmux attach command
     pub fn enter_action(&self) -> Option<TuiAction> {
         let task = self.selected_task()?;
@@ -310,12 +376,6 @@ impl App {
         let session = task.tmux_session.as_ref()?;
         let window = task.tmux_window.as_ref()?;
 
-        let claude_command = self
-            .config
-            .as_ref()
-            .map(|c| c.claude_command.clone())
-            .unwrap_or_else(|| "claude".to_string());
-
         if task.tmux_alive {
             if self.is_in_tmux() {
                 // Inside tmux: switch to target window
@@ -330,18 +390,31 @@ impl App {
                     window: window.clone(),
                 })
             }
+        } else if task.status == TaskStatus::Done && task.resume_command.is_some() {
+            Some(TuiAction::Resume {
+                name: task.name.clone(),
+            })
         } else {
             // Tmux window closed, show resume command
             let worktree = task.worktree_path.as_ref()?;
             let session_id = task.session_id.as_ref()?;
+            let command = task.resume_command.as_ref()?;
             Some(TuiAction::ShowResume {
                 worktree: worktree.clone(),
                 session_id: session_id.clone(),
-                claude_command,
+                command: command.clone(),
             })
         }
     }
 
+    /// Resume a Done task's agent session in a new tmux window
+    pub fn resume(&mut self, name: &str) -> Result<()> {
+        let mut store = TaskStore::load()?;
+        let result = crate::commands::resume::resume_task(&mut store, name, None, Actor::Tui);
+        self.refresh()?;
+        result.map(|_| ())
+    }
+
     /// Get action to tail selected task's transcript
     pub fn tail_action(&self) -> Option<TuiAction> {
         self.selected_task().and_then(|task| {
@@ -84,6 +84,27 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<TuiA
                 message: e.to_string(),
             })? {
                 if key.kind == KeyEventKind::Press {
+                    app.message = None;
+
+                    // Composing a message for `s`: keys edit the input box
+                    if let Some(input) = app.input.as_mut() {
+                        match key.code {
+                            KeyCode::Esc => app.input = None,
+                            KeyCode::Backspace => {
+                                input.pop();
+                            }
+                            KeyCode::Char(c) => input.push(c),
+                            KeyCode::Enter => {
+                                let text = app.input.take().unwrap_or_default();
+                                if let Err(e) = app.send_message(&text) {
+                                    app.message = e.to_string().lines().next().map(str::to_string);
+                                }
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
                     match key.code {
                         // Quit
                         KeyCode::Char('q') | KeyCode::Esc => {
@@ -94,9 +115,19 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<TuiA
                         KeyCode::Up | KeyCode::Char('k') => app.previous(),
                         KeyCode::Down | KeyCode::Char('j') => app.next(),
 
-                        // Enter: switch/attach tmux or show resume command
+                        // Enter: switch/attach tmux, resuming a Done task's session first
                         KeyCode::Enter => {
-                            if let Some(action) = app.enter_action() {
+                            let mut action = app.enter_action();
+                            if let Some(TuiAction::Resume { name }) = &action {
+                                action = match app.resume(name) {
+                                    Ok(()) => app.enter_action(),
+                                    Err(e) => {
+                                        app.message = e.to_string().lines().next().map(str::to_string);
+                                        None
+                                    }
+                                };
+                            }
+                            if let Some(action) = action {
                                 match &action {
                                     TuiAction::SwitchTmuxWindow { session, window } => {
                                         // Inside tmux: temporarily leave TUI to switch window
@@ -140,25 +171,26 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<TuiA
                             }
                         }
 
-                        // Mark as done (Running + tmux exited)
+                        // Send a message to the agent (Running + tmux alive)
+                        KeyCode::Char('s') if app.can_send() => {
+                            app.input = Some(String::new());
+                        }
+
+                        // Mark as done (Running), refused if checks fail
                         KeyCode::Char('d') => {
-                            if app.can_mark_done() {
-                                app.mark_done()?;
+                            if let Some(action) = app.done_action() {
+                                return Ok(action);
                             }
                         }
 
                         // Mark as merged (Done only)
-                        KeyCode::Char('m') => {
-                            if app.can_mark_merged() {
-                                app.mark_merged()?;
-                            }
+                        KeyCode::Char('m') if app.can_mark_merged() => {
+                            app.mark_merged()?;
                         }
 
                         // Archive (Merged only)
-                        KeyCode::Char('a') => {
-                            if app.can_archive() {
-                                app.archive()?;
-                            }
+                        KeyCode::Char('a') if app.can_archive() => {
+                            app.archive()?;
                         }
 
                         _ => {}
@@ -53,6 +53,11 @@ fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
         spans.push(Span::raw(" · "));
         spans.push(Span::styled(format!("{} merged", merged), Style::default().fg(Color::Magenta)));
     }
+    let cost: f64 = app.tasks.iter().map(|t| t.cost_usd).sum();
+    if cost > 0.0 {
+        spans.push(Span::raw(" · "));
+        spans.push(Span::styled(format!("${:.2}", cost), Style::default().fg(Color::Yellow)));
+    }
     let text = Line::from(spans);
 
     frame.render_widget(Paragraph::new(text), area);
@@ -176,9 +181,31 @@ fn format_task_line(task: &TaskDisplay, selected: bool, _width: usize) -> Line<'
         ));
     }
 
+    // Model, e.g. "sonnet-4-5" for claude-sonnet-4-5-20250929
+    if let Some(model) = &task.model {
+        spans.push(Span::styled(
+            format!(" {}", short_model_name(model)),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
     Line::from(spans)
 }
 
+fn short_model_name(model: &str) -> String {
+    let name = model.strip_prefix("claude-").unwrap_or(model);
+    let (name, variant) = match name.find('[') {
+        Some(i) => name.split_at(i),
+        None => (name, ""),
+    };
+    // Drop a trailing release date
+    let name = match name.rsplit_once('-') {
+        Some((base, date)) if date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit()) => base,
+        _ => name,
+    };
+    truncate(&format!("{}{}", name, variant), 16)
+}
+
 fn format_tool_name(tool: &str) -> String {
     // Shorten common tool names for display
     let short = match tool {
@@ -211,6 +238,8 @@ fn get_status_icon(task: &TaskDisplay) -> (&'static str, Color) {
         TaskStatus::Running => {
             if !task.tmux_alive {
                 ("⚠", Color::Yellow)
+            } else if task.waiting_for_input {
+                ("?", Color::Magenta)
             } else if task.active {
                 ("●", Color::Green)
             } else {
@@ -229,6 +258,27 @@ fn draw_footer(frame: &mut Frame, area: Rect, app: &App) {
         Rect::new(area.x, area.y, area.width, 1),
     );
 
+    // Input box for `s` replaces the keybindings while composing
+    if let (true, Some(input), Some(task)) = (area.height > 1, &app.input, app.selected_task()) {
+        let line = Line::from(vec![
+            Span::styled(format!(" send to {}: ", task.name), Style::default().fg(Color::Yellow)),
+            Span::raw(input.as_str()),
+            Span::styled("▏", Style::default().fg(Color::Yellow)),
+            Span::styled("  ⏎ send  esc cancel", Style::default().fg(Color::DarkGray)),
+        ]);
+        frame.render_widget(Paragraph::new(line), Rect::new(area.x, area.y + 1, area.width, 1));
+        return;
+    }
+
+    // Error from the last action replaces the keybindings until the next key
+    if let (true, Some(message)) = (area.height > 1, &app.message) {
+        frame.render_widget(
+            Paragraph::new(format!(" {}", message)).style(Style::default().fg(Color::Red)),
+            Rect::new(area.x, area.y + 1, area.width, 1),
+        );
+        return;
+    }
+
     // Keybindings - context sensitive
     if area.height > 1 {
         let help_area = Rect::new(area.x, area.y + 1, area.width, 1);
@@ -249,6 +299,11 @@ fn draw_footer(frame: &mut Frame, area: Rect, app: &App) {
                 spans.push(Span::raw(" tail  "));
             }
 
+            if app.can_send() {
+                spans.push(Span::styled("s", Style::default().fg(Color::Yellow)));
+                spans.push(Span::raw(" send  "));
+            }
+
             if task.status == TaskStatus::Running {
                 // Running: d (done)
                 spans.push(Span::styled("d", Style::default().fg(Color::Yellow)));
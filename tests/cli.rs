@@ -44,3 +44,31 @@ mod tail;
 mod logs;
 #[path = "cli/completions.rs"]
 mod completions;
+#[path = "cli/concurrency.rs"]
+mod concurrency;
+#[path = "cli/history.rs"]
+mod history;
+#[path = "cli/sync.rs"]
+mod sync;
+#[path = "cli/merge.rs"]
+mod merge;
+#[path = "cli/base_branch.rs"]
+mod base_branch;
+#[path = "cli/stack.rs"]
+mod stack;
+#[path = "cli/run.rs"]
+mod run;
+#[path = "cli/show.rs"]
+mod show;
+#[path = "cli/hooks.rs"]
+mod hooks;
+#[path = "cli/watch.rs"]
+mod watch;
+#[path = "cli/send.rs"]
+mod send;
+#[path = "cli/resume.rs"]
+mod resume;
+#[path = "cli/restart.rs"]
+mod restart;
+#[path = "cli/cost.rs"]
+mod cost;
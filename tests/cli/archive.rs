@@ -119,3 +119,36 @@ fn test_archive_already_archived_is_idempotent() {
         output
     );
 }
+
+// ==================== Archive Script ====================
+
+#[test]
+fn test_archive_script_expands_template_variables() {
+    let dir = setup_test_repo();
+    let worktree = dir.path().join(".wt/worktrees/api");
+    std::fs::create_dir_all(&worktree).unwrap();
+    std::fs::write(
+        dir.path().join(".wt/config.yaml"),
+        "start_args: -p test\ntmux_session: test-wt\narchive_script: echo \"${task} ${branch} ${depends} \\$${literal}\" > ${repo_root}/archived.txt\n",
+    )
+    .unwrap();
+    create_task_file(dir.path(), "auth", &[]);
+    create_task_file(dir.path(), "api", &["auth"]);
+    set_task_status_with_instance(
+        dir.path(),
+        "api",
+        "merged",
+        Some(serde_json::json!({
+            "branch": "wt/api-1234",
+            "worktree_path": worktree.to_string_lossy(),
+            "tmux_session": "test-wt",
+            "tmux_window": "api"
+        })),
+    );
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["archive", "api"]);
+    assert!(ok, "stderr: {}", stderr);
+
+    let out = std::fs::read_to_string(dir.path().join("archived.txt")).unwrap();
+    assert_eq!(out.trim(), "api wt/api-1234 auth ${literal}");
+}
@@ -0,0 +1,108 @@
+//! CLI tests for configurable base branch (config `base_branch`, frontmatter `base`)
+
+use super::*;
+
+fn set_config_base(dir: &std::path::Path, base: &str) {
+    fs::write(
+        dir.join(".wt/config.yaml"),
+        format!("start_args: -p test\ntmux_session: test-wt\nbase_branch: {}\n", base),
+    )
+    .unwrap();
+}
+
+fn set_task_base(dir: &std::path::Path, name: &str, base: &str) {
+    fs::write(
+        dir.join(format!(".wt/tasks/{}.md", name)),
+        format!("---\nname: {}\nbase: {}\n---\n\nTask {}", name, base, name),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_start_with_missing_base_branch_fails() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "pending")]);
+    set_config_base(dir.path(), "develop");
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["start", "auth"]);
+    assert!(!ok);
+    assert!(stderr.contains("Base branch 'develop' not found"), "stderr: {}", stderr);
+
+    // Nothing was created
+    assert!(!dir.path().join(".wt/worktrees/auth").exists());
+    assert_eq!(
+        get_task_from_status(dir.path(), "auth").unwrap()["status"],
+        "pending"
+    );
+}
+
+#[test]
+fn test_status_json_reports_base_branch() {
+    let dir = setup_test_repo();
+    run_git(dir.path(), &["branch", "develop"]);
+    run_git(dir.path(), &["branch", "release/2.0"]);
+    set_config_base(dir.path(), "develop");
+    setup_task_branch(dir.path(), "auth", "done", 1);
+    setup_task_branch(dir.path(), "api", "done", 1);
+    set_task_base(dir.path(), "api", "release/2.0");
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["status", "--json"]);
+    assert!(ok, "stderr: {}", stderr);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let tasks = json["tasks"].as_array().unwrap();
+    let base_of = |name: &str| {
+        tasks
+            .iter()
+            .find(|t| t["name"] == name)
+            .map(|t| t["base_branch"].clone())
+            .unwrap()
+    };
+
+    assert_eq!(base_of("auth"), "develop");
+    assert_eq!(base_of("api"), "release/2.0");
+}
+
+#[test]
+fn test_sync_uses_configured_base_branch() {
+    let dir = setup_test_repo();
+    let main = run_git(dir.path(), &["rev-parse", "--abbrev-ref", "HEAD"]);
+    setup_task_branch(dir.path(), "auth", "done", 1);
+
+    // Merge into develop only
+    run_git(dir.path(), &["checkout", "-q", "-b", "develop"]);
+    run_git(dir.path(), &["merge", "-q", "--no-ff", "-m", "Merge auth", "wt/auth"]);
+    run_git(dir.path(), &["checkout", "-q", &main]);
+
+    // Against main the task is not merged
+    run_wt(dir.path(), &["sync"]);
+    assert_eq!(
+        get_task_from_status(dir.path(), "auth").unwrap()["status"],
+        "done"
+    );
+
+    set_config_base(dir.path(), "develop");
+    let (ok, stdout, _) = run_wt(dir.path(), &["sync"]);
+    assert!(ok);
+    assert!(stdout.contains("auth: merged"), "stdout: {}", stdout);
+    assert_eq!(
+        get_task_from_status(dir.path(), "auth").unwrap()["status"],
+        "merged"
+    );
+}
+
+#[test]
+fn test_merge_into_task_base_branch() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "done", 1);
+    set_task_base(dir.path(), "auth", "develop");
+    run_git(dir.path(), &["branch", "develop"]);
+
+    // Main worktree is on main, not the task's base
+    let (ok, _, stderr) = run_wt(dir.path(), &["merge", "auth"]);
+    assert!(!ok);
+    assert!(stderr.contains("'develop' checked out"), "stderr: {}", stderr);
+
+    run_git(dir.path(), &["checkout", "-q", "develop"]);
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["merge", "auth"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("into develop"), "stdout: {}", stdout);
+}
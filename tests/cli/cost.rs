@@ -0,0 +1,98 @@
+//! CLI tests for `wt cost`
+
+use super::*;
+use std::path::Path;
+
+fn write_transcript(
+    home: &Path,
+    worktree: &str,
+    session_id: &str,
+    message_id: &str,
+    input: u64,
+    output: u64,
+) {
+    let dir = home
+        .join(".claude/projects")
+        .join(worktree.replace(['/', '.'], "-"));
+    std::fs::create_dir_all(&dir).unwrap();
+    let entry = serde_json::json!({
+        "type": "assistant",
+        "timestamp": "2025-01-01T00:00:00Z",
+        "message": {
+            "id": message_id,
+            "model": "claude-sonnet-4-5",
+            "usage": {
+                "input_tokens": input,
+                "output_tokens": output,
+                "cache_read_input_tokens": 0,
+                "cache_creation_input_tokens": 0
+            },
+            "content": [{"type": "text", "text": "done"}]
+        }
+    });
+    std::fs::write(
+        dir.join(format!("{}.jsonl", session_id)),
+        format!("{}\n", entry),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_cost_sums_all_sessions() {
+    let dir = setup_test_repo();
+    let home = tempfile::tempdir().unwrap();
+    create_task_file(dir.path(), "auth", &[]);
+    let worktree = dir
+        .path()
+        .join(".wt/worktrees/auth")
+        .to_string_lossy()
+        .to_string();
+    set_task_status_with_instance(
+        dir.path(),
+        "auth",
+        "done",
+        Some(serde_json::json!({
+            "branch": "wt/auth",
+            "worktree_path": worktree,
+            "tmux_session": "test-wt",
+            "tmux_window": "auth",
+            "session_id": "second",
+            "previous_sessions": ["first"]
+        })),
+    );
+    write_transcript(home.path(), &worktree, "first", "m1", 1_000_000, 0);
+    write_transcript(home.path(), &worktree, "second", "m2", 0, 1_000_000);
+
+    let (ok, stdout, stderr) =
+        run_wt_with_home(dir.path(), home.path(), &["cost", "auth", "--json"]);
+
+    assert!(ok, "stderr: {}", stderr);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let task = &json["tasks"][0];
+    assert_eq!(task["name"], "auth");
+    assert_eq!(task["sessions"], 2);
+    assert_eq!(task["usage"]["input"], 1_000_000);
+    assert_eq!(task["usage"]["output"], 1_000_000);
+    // Sonnet: $3 per million input + $15 per million output
+    assert_eq!(json["total"]["cost"]["usd"], 18.0);
+}
+
+#[test]
+fn test_cost_requires_started_task() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "pending")]);
+
+    let (ok, _stdout, stderr) = run_wt(dir.path(), &["cost", "auth"]);
+
+    assert!(!ok);
+    assert!(stderr.contains("auth"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_cost_without_started_tasks() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "pending")]);
+
+    let (ok, stdout, _stderr) = run_wt(dir.path(), &["cost"]);
+
+    assert!(ok);
+    assert!(stdout.contains("No started tasks."), "stdout: {}", stdout);
+}
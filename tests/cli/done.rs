@@ -28,3 +28,159 @@ fn test_done_already_done() {
     assert!(!ok);
     assert!(stderr.contains("no running") || stderr.contains("instance") || stderr.contains("Invalid state"));
 }
+
+// ==================== Acceptance checks ====================
+
+fn set_config_checks(dir: &std::path::Path, checks: &[&str]) {
+    let list: String = checks.iter().map(|c| format!("  - {}\n", c)).collect();
+    fs::write(
+        dir.join(".wt/config.yaml"),
+        format!("start_args: -p test\ntmux_session: test-wt\nchecks:\n{}", list),
+    )
+    .unwrap();
+}
+
+/// Running task with a plain directory as its worktree
+fn setup_running_task(dir: &std::path::Path, name: &str, acceptance: &[&str]) -> std::path::PathBuf {
+    let worktree = dir.join(".wt/worktrees").join(name);
+    fs::create_dir_all(&worktree).unwrap();
+    let list: String = acceptance.iter().map(|c| format!("  - {}\n", c)).collect();
+    let acceptance_yaml = if acceptance.is_empty() { String::new() } else { format!("acceptance:\n{}", list) };
+    fs::create_dir_all(dir.join(".wt/tasks")).unwrap();
+    fs::write(
+        dir.join(".wt/tasks").join(format!("{}.md", name)),
+        format!("---\nname: {}\n{}---\n\nTask {}", name, acceptance_yaml, name),
+    )
+    .unwrap();
+    set_task_status_with_instance(
+        dir,
+        name,
+        "running",
+        Some(serde_json::json!({
+            "branch": format!("wt/{}", name),
+            "worktree_path": worktree.to_string_lossy(),
+            "tmux_session": "test-wt",
+            "tmux_window": name
+        })),
+    );
+    worktree
+}
+
+fn status_entry(dir: &std::path::Path, name: &str) -> serde_json::Value {
+    let content = fs::read_to_string(dir.join(".wt/status.json")).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    json["tasks"][name].clone()
+}
+
+#[test]
+fn test_done_runs_checks_in_worktree() {
+    let dir = setup_test_repo();
+    let worktree = setup_running_task(dir.path(), "auth", &[]);
+    fs::write(worktree.join("built"), "").unwrap();
+    set_config_checks(dir.path(), &["test -f built"]);
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["done", "auth"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("✓"), "stdout: {}", stdout);
+
+    let entry = status_entry(dir.path(), "auth");
+    assert_eq!(entry["status"], "done");
+    assert_eq!(entry["checks"]["passed"], true);
+    assert_eq!(entry["checks"]["results"][0]["command"], "test -f built");
+}
+
+#[test]
+fn test_done_refused_when_check_fails() {
+    let dir = setup_test_repo();
+    setup_running_task(dir.path(), "auth", &[]);
+    set_config_checks(dir.path(), &["exit 0", "echo 'lint error' && exit 2"]);
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["done", "auth"]);
+    assert!(!ok);
+    assert!(stderr.contains("Acceptance checks failed for 'auth'"), "stderr: {}", stderr);
+    assert!(stderr.contains("--force"));
+
+    // Still running, with the failure recorded
+    let entry = status_entry(dir.path(), "auth");
+    assert_eq!(entry["status"], "running");
+    assert_eq!(entry["checks"]["passed"], false);
+    assert_eq!(entry["checks"]["results"][1]["exit_code"], 2);
+    assert_eq!(entry["checks"]["results"][1]["output"], "lint error");
+}
+
+#[test]
+fn test_done_force_overrides_failing_checks() {
+    let dir = setup_test_repo();
+    setup_running_task(dir.path(), "auth", &[]);
+    set_config_checks(dir.path(), &["false"]);
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["done", "auth", "--force"]);
+    assert!(ok, "stderr: {}", stderr);
+
+    let (_, stdout, _) = run_wt(dir.path(), &["status", "--json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let task = &json["tasks"][0];
+    assert_eq!(task["status"], "done");
+    assert_eq!(task["checks"]["passed"], false);
+    assert_eq!(task["checks"]["forced"], true);
+}
+
+#[test]
+fn test_done_task_acceptance_replaces_config_checks() {
+    let dir = setup_test_repo();
+    setup_running_task(dir.path(), "auth", &["exit 0"]);
+    set_config_checks(dir.path(), &["false"]);
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["done", "auth"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert_eq!(status_entry(dir.path(), "auth")["checks"]["results"][0]["command"], "exit 0");
+}
+
+#[test]
+fn test_done_checks_require_worktree() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "running")]);
+    set_config_checks(dir.path(), &["exit 0"]);
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["done", "auth"]);
+    assert!(!ok);
+    assert!(stderr.contains("worktree"), "stderr: {}", stderr);
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["done", "auth", "--force"]);
+    assert!(ok, "stderr: {}", stderr);
+}
+
+#[test]
+fn test_exited_task_with_checks_is_not_auto_marked_done() {
+    let dir = setup_test_repo();
+    setup_running_task(dir.path(), "auth", &["false"]);
+
+    // tmux window is gone, but the checks haven't run
+    run_wt(dir.path(), &["status", "--json"]);
+    assert_eq!(status_entry(dir.path(), "auth")["status"], "running");
+}
+
+#[test]
+fn test_run_checks_exited_task_before_marking_done() {
+    let dir = setup_test_repo();
+    let worktree = setup_running_task(dir.path(), "auth", &["test -f built"]);
+    fs::write(worktree.join("built"), "").unwrap();
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["run", "--interval", "0"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("'auth' finished (done)"), "stdout: {}", stdout);
+
+    let entry = status_entry(dir.path(), "auth");
+    assert_eq!(entry["status"], "done");
+    assert_eq!(entry["checks"]["passed"], true);
+}
+
+#[test]
+fn test_run_stops_when_exited_task_fails_checks() {
+    let dir = setup_test_repo();
+    setup_running_task(dir.path(), "auth", &["false"]);
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["run", "--interval", "0"]);
+    assert!(!ok);
+    assert!(stderr.contains("'auth' failed"), "stderr: {}", stderr);
+    assert_eq!(status_entry(dir.path(), "auth")["status"], "running");
+}
@@ -0,0 +1,109 @@
+//! CLI tests for wt history command
+
+use crate::common::*;
+use serde_json::json;
+
+fn history_events(dir: &std::path::Path, args: &[&str]) -> Vec<serde_json::Value> {
+    let mut full_args = vec!["history", "--json"];
+    full_args.extend_from_slice(args);
+    let (ok, stdout, stderr) = run_wt(dir, &full_args);
+    assert!(ok, "stderr: {}", stderr);
+    let output: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    output["events"].as_array().unwrap().clone()
+}
+
+#[test]
+fn test_history_empty() {
+    let dir = setup_test_repo();
+
+    let (ok, stdout, _) = run_wt(dir.path(), &["history"]);
+
+    assert!(ok);
+    assert!(stdout.contains("No events recorded"));
+}
+
+#[test]
+fn test_history_records_cli_transitions() {
+    let dir = setup_repo_with_tasks(&[("task1", &[], "running")]);
+
+    run_wt(dir.path(), &["done", "task1"]);
+    run_wt(dir.path(), &["merged", "task1"]);
+
+    let events = history_events(dir.path(), &[]);
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["task"], "task1");
+    assert_eq!(events[0]["actor"], "cli");
+    assert_eq!(events[0]["from"], "running");
+    assert_eq!(events[0]["to"], "done");
+    assert_eq!(events[1]["from"], "done");
+    assert_eq!(events[1]["to"], "merged");
+    assert!(events[1]["timestamp"].is_string());
+}
+
+#[test]
+fn test_history_records_reset() {
+    let dir = setup_repo_with_tasks(&[("task1", &[], "done")]);
+
+    run_wt(dir.path(), &["reset", "task1"]);
+
+    let events = history_events(dir.path(), &[]);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["from"], "done");
+    assert_eq!(events[0]["to"], "pending");
+}
+
+#[test]
+fn test_history_records_auto_mark_done() {
+    let dir = setup_test_repo();
+    create_task_file(dir.path(), "task1", &[]);
+    set_task_status_with_instance(
+        dir.path(),
+        "task1",
+        "running",
+        Some(json!({
+            "branch": "wt/task1",
+            "worktree_path": "/tmp/nonexistent",
+            "tmux_session": "nonexistent-session-12345",
+            "tmux_window": "task1"
+        })),
+    );
+
+    run_wt(dir.path(), &["status", "--json"]);
+
+    let events = history_events(dir.path(), &[]);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["actor"], "auto");
+    assert_eq!(events[0]["to"], "done");
+}
+
+#[test]
+fn test_history_filters_by_task() {
+    let dir = setup_repo_with_tasks(&[("task1", &[], "running"), ("task2", &[], "running")]);
+
+    run_wt(dir.path(), &["done", "task1"]);
+    run_wt(dir.path(), &["done", "task2"]);
+
+    let events = history_events(dir.path(), &["task2"]);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["task"], "task2");
+
+    // Index resolves like other commands (task1 = 1)
+    let events = history_events(dir.path(), &["1"]);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["task"], "task1");
+}
+
+#[test]
+fn test_history_human_output() {
+    let dir = setup_repo_with_tasks(&[("task1", &[], "running")]);
+
+    run_wt(dir.path(), &["done", "task1"]);
+    run_wt(dir.path(), &["merged", "task1"]);
+
+    let (ok, stdout, _) = run_wt(dir.path(), &["history", "task1"]);
+
+    assert!(ok);
+    assert!(stdout.contains("task1"));
+    assert!(stdout.contains("(cli)"));
+    assert!(stdout.contains("after"));
+}
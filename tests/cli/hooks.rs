@@ -0,0 +1,113 @@
+//! CLI tests for lifecycle hooks
+
+use std::path::{Path, PathBuf};
+
+use super::*;
+
+fn set_hooks(dir: &Path, hooks: &str) {
+    fs::write(
+        dir.join(".wt/config.yaml"),
+        format!("start_args: -p test\ntmux_session: test-wt\nhooks:\n{}", hooks),
+    )
+    .unwrap();
+}
+
+/// Task in `status` with a plain directory as its worktree
+fn setup_task_with_worktree(dir: &Path, name: &str, status: &str) -> PathBuf {
+    let worktree = dir.join(".wt/worktrees").join(name);
+    fs::create_dir_all(&worktree).unwrap();
+    create_task_file(dir, name, &[]);
+    set_task_status_with_instance(
+        dir,
+        name,
+        status,
+        Some(serde_json::json!({
+            "branch": format!("wt/{}", name),
+            "worktree_path": worktree.to_string_lossy(),
+            "tmux_session": "test-wt",
+            "tmux_window": name
+        })),
+    );
+    worktree
+}
+
+fn hooks_log(dir: &Path, name: &str) -> String {
+    fs::read_to_string(dir.join(".wt/logs").join(name).join("hooks.log")).unwrap_or_default()
+}
+
+fn status_of(dir: &Path, name: &str) -> serde_json::Value {
+    get_task_from_status(dir, name).unwrap()["status"].clone()
+}
+
+#[test]
+fn test_failing_pre_start_aborts_start() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "pending")]);
+    set_hooks(dir.path(), "  pre_start: echo \"no ticket for $WT_TASK\"; exit 1\n");
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["start", "auth"]);
+
+    assert!(!ok);
+    assert!(stderr.contains("Hook pre_start failed for 'auth': exit code 1"), "stderr: {}", stderr);
+    assert!(!dir.path().join(".wt/worktrees/auth").exists());
+    assert_eq!(status_of(dir.path(), "auth"), "pending");
+    let log = hooks_log(dir.path(), "auth");
+    assert!(log.contains("pre_start (exit code 1)"), "log: {}", log);
+    assert!(log.contains("no ticket for auth"), "log: {}", log);
+}
+
+#[test]
+fn test_on_done_hook_vetoes_unless_forced() {
+    let dir = setup_test_repo();
+    setup_task_with_worktree(dir.path(), "auth", "running");
+    set_hooks(dir.path(), "  on_done: exit 2\n");
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["done", "auth"]);
+    assert!(!ok);
+    assert!(stderr.contains("Hook on_done failed"), "stderr: {}", stderr);
+    assert_eq!(status_of(dir.path(), "auth"), "running");
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["done", "auth", "--force"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stderr.contains("Warning: Hook on_done failed"), "stderr: {}", stderr);
+    assert_eq!(status_of(dir.path(), "auth"), "done");
+}
+
+#[test]
+fn test_on_done_hook_runs_in_worktree() {
+    let dir = setup_test_repo();
+    let worktree = setup_task_with_worktree(dir.path(), "auth", "running");
+    set_hooks(dir.path(), "  on_done: echo \"$WT_HOOK $WT_BRANCH\" > done.txt\n");
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["done", "auth"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert_eq!(
+        fs::read_to_string(worktree.join("done.txt")).unwrap().trim(),
+        "on_done wt/auth"
+    );
+}
+
+#[test]
+fn test_failing_on_merged_hook_only_warns() {
+    let dir = setup_test_repo();
+    setup_task_with_worktree(dir.path(), "auth", "done");
+    set_hooks(dir.path(), "  on_merged: exit 1\n");
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["merged", "auth"]);
+
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stderr.contains("Warning: Hook on_merged failed"), "stderr: {}", stderr);
+    assert_eq!(status_of(dir.path(), "auth"), "merged");
+}
+
+#[test]
+fn test_failing_pre_archive_aborts_archive() {
+    let dir = setup_test_repo();
+    let worktree = setup_task_with_worktree(dir.path(), "auth", "merged");
+    set_hooks(dir.path(), "  pre_archive: exit 1\n");
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["archive", "auth"]);
+    assert!(!ok);
+    assert!(stderr.contains("Hook pre_archive failed"), "stderr: {}", stderr);
+    assert_eq!(status_of(dir.path(), "auth"), "merged");
+    assert!(worktree.exists());
+}
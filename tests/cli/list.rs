@@ -137,3 +137,191 @@ fn test_list_json_all_statuses() {
     assert!(statuses.contains(&"done"));
     assert!(statuses.contains(&"merged"));
 }
+
+// ==================== Stable IDs ====================
+
+fn list_indexes(dir: &std::path::Path) -> std::collections::HashMap<String, u64> {
+    let (_, stdout, _) = run_wt(dir, &["list", "--json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    json["tasks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| (t["name"].as_str().unwrap().to_string(), t["index"].as_u64().unwrap()))
+        .collect()
+}
+
+#[test]
+fn test_list_ids_stable_when_tasks_added() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "pending"), ("db", &[], "done")]);
+
+    // Existing tasks are numbered in name order, as before
+    let ids = list_indexes(dir.path());
+    assert_eq!(ids["auth"], 1);
+    assert_eq!(ids["db"], 2);
+
+    // A task that sorts first gets the next ID instead of renumbering the rest
+    let (ok, stdout, _) = run_wt(
+        dir.path(),
+        &["create", "--json", r#"{"name": "api", "depends": [], "description": "API"}"#],
+    );
+    assert!(ok);
+    assert!(stdout.contains("ID:   3"), "stdout: {}", stdout);
+
+    let ids = list_indexes(dir.path());
+    assert_eq!(ids["api"], 3);
+    assert_eq!(ids["auth"], 1);
+    assert_eq!(ids["db"], 2);
+
+    // IDs are accepted wherever a task name is
+    let (ok, _, stderr) = run_wt(dir.path(), &["merged", "2"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert_eq!(get_task_from_status(dir.path(), "db").unwrap()["status"], "merged");
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["merged", "9"]);
+    assert!(!ok);
+    assert!(stderr.contains("No task with ID 9"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_list_ids_survive_reset() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "pending"), ("db", &[], "done")]);
+    list_indexes(dir.path());
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["reset", "db"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert_eq!(list_indexes(dir.path())["db"], 2);
+}
+
+#[test]
+fn test_ids_saved_when_first_seen() {
+    let dir = setup_test_repo();
+    create_task_file(dir.path(), "db", &[]);
+    assert_eq!(list_indexes(dir.path())["db"], 1);
+
+    // A hand-written task that sorts first doesn't take over the shown ID
+    create_task_file(dir.path(), "auth", &[]);
+    let ids = list_indexes(dir.path());
+    assert_eq!((ids["db"], ids["auth"]), (1, 2));
+    let status: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.path().join(".wt/status.json")).unwrap()).unwrap();
+    assert_eq!(status["ids"]["auth"], 2);
+}
+
+#[test]
+fn test_legacy_index_flag_overrides_config() {
+    let dir = setup_repo_with_tasks(&[("db", &[], "pending")]);
+    list_indexes(dir.path());
+    create_task_file(dir.path(), "auth", &[]);
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["--legacy-index", "list", "--json"]);
+    assert!(ok, "stderr: {}", stderr);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["tasks"][0]["name"], "auth");
+    assert_eq!(json["tasks"][0]["index"], 1);
+
+    fs::write(
+        dir.path().join(".wt/config.yaml"),
+        "start_args: -p test\ntmux_session: test-wt\nlegacy_index: true\n",
+    )
+    .unwrap();
+    let (_, stdout, _) = run_wt(dir.path(), &["list", "--json", "--legacy-index=false"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["tasks"][0]["index"], 2);
+}
+
+#[test]
+fn test_list_legacy_index_uses_positions() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "pending"), ("db", &[], "pending")]);
+    list_indexes(dir.path());
+    create_task_file(dir.path(), "api", &[]);
+    fs::write(
+        dir.path().join(".wt/config.yaml"),
+        "start_args: -p test\ntmux_session: test-wt\nlegacy_index: true\n",
+    )
+    .unwrap();
+
+    let ids = list_indexes(dir.path());
+    assert_eq!(ids["api"], 1);
+    assert_eq!(ids["auth"], 2);
+    assert_eq!(ids["db"], 3);
+}
+
+// ==================== Metadata ====================
+
+const AUTH_JSON: &str = r#"{"name": "auth", "description": "Auth", "tags": ["backend", "security"], "owner": "alice", "estimate": "2h", "acceptance": ["cargo test"], "meta": {"ticket": "AUTH-12", "points": 3}}"#;
+
+#[test]
+fn test_list_json_roundtrips_metadata() {
+    let dir = setup_test_repo();
+    let (ok, _, stderr) = run_wt(dir.path(), &["create", "--json", AUTH_JSON]);
+    assert!(ok, "stderr: {}", stderr);
+
+    let (_, stdout, _) = run_wt(dir.path(), &["list", "--json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let task = &json["tasks"][0];
+    assert_eq!(task["tags"], serde_json::json!(["backend", "security"]));
+    assert_eq!(task["owner"], "alice");
+    assert_eq!(task["estimate"], "2h");
+    assert_eq!(task["acceptance"], serde_json::json!(["cargo test"]));
+    assert_eq!(task["meta"], serde_json::json!({"ticket": "AUTH-12", "points": 3}));
+
+    let (_, stdout, _) = run_wt(dir.path(), &["next", "--json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["ready"][0]["owner"], "alice");
+
+    set_task_status(dir.path(), "auth", "running");
+    let (_, stdout, _) = run_wt(dir.path(), &["status", "--json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["tasks"][0]["meta"]["ticket"], "AUTH-12");
+}
+
+#[test]
+fn test_list_filter_by_tag_and_owner() {
+    let dir = setup_test_repo();
+    run_wt(dir.path(), &["create", "--json", AUTH_JSON]);
+    run_wt(
+        dir.path(),
+        &["create", "--json", r#"{"name": "ui", "description": "UI", "tags": ["frontend"], "owner": "bob"}"#],
+    );
+
+    let (ok, stdout, _) = run_wt(dir.path(), &["list", "--tag", "backend"]);
+    assert!(ok);
+    assert!(stdout.contains("auth"));
+    assert!(!stdout.contains("ui"), "stdout: {}", stdout);
+
+    let (_, stdout, _) = run_wt(dir.path(), &["list", "--json", "--owner", "bob"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let names: Vec<&str> = json["tasks"].as_array().unwrap().iter().map(|t| t["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["ui"]);
+
+    let (ok, stdout, _) = run_wt(dir.path(), &["list", "--tag", "backend", "--owner", "bob"]);
+    assert!(ok);
+    assert!(stdout.contains("No tasks match"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_create_rejects_invalid_metadata() {
+    let dir = setup_test_repo();
+    let (ok, _, stderr) = run_wt(
+        dir.path(),
+        &["create", "--json", r#"{"name": "auth", "description": "Auth", "tags": ["x", "x"]}"#],
+    );
+    assert!(!ok);
+    assert!(stderr.contains("duplicate tag 'x'"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_validate_reports_invalid_metadata() {
+    let dir = setup_test_repo();
+    fs::create_dir_all(dir.path().join(".wt/tasks")).unwrap();
+    fs::write(
+        dir.path().join(".wt/tasks/auth.md"),
+        "---\nname: auth\nacceptance:\n  - ''\n---\n\nTask auth",
+    )
+    .unwrap();
+
+    let (ok, stdout, _) = run_wt(dir.path(), &["validate"]);
+    assert!(ok);
+    assert!(stdout.contains("acceptance check 1 is empty"), "stdout: {}", stdout);
+}
@@ -120,7 +120,7 @@ fn test_logs_skips_tasks_without_instance() {
 fn test_logs_ignores_archived_tasks() {
     let dir = setup_repo_with_tasks(&[("task1", &[], "archived")]);
 
-    let (ok, stdout, _) = run_wt(dir.path(), &["logs"]);
+    let (ok, _, _) = run_wt(dir.path(), &["logs"]);
 
     assert!(ok);
     // Archived tasks should not cause errors
@@ -0,0 +1,207 @@
+//! CLI tests for wt merge command
+
+use super::*;
+
+fn head(dir: &std::path::Path) -> String {
+    run_git(dir, &["rev-parse", "HEAD"])
+}
+
+fn is_clean(dir: &std::path::Path) -> bool {
+    run_git(dir, &["status", "--porcelain", "--untracked-files=no"]).is_empty()
+}
+
+#[test]
+fn test_merge_default_strategy_creates_merge_commit() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "done", 2);
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["merge", "auth"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("marked as merged"));
+
+    // Merge commit has two parents and the generated message
+    let parents = run_git(dir.path(), &["rev-list", "--parents", "-n", "1", "HEAD"]);
+    assert_eq!(parents.split_whitespace().count(), 3);
+    let message = run_git(dir.path(), &["log", "-1", "--format=%B"]);
+    assert!(message.starts_with("auth: Task auth"), "message: {}", message);
+    assert!(message.contains("Branch: wt/auth"));
+
+    assert!(dir.path().join("auth-1.txt").exists());
+    let task = get_task_from_status(dir.path(), "auth").unwrap();
+    assert_eq!(task["status"], "merged");
+    assert!(task["instance"].is_object());
+}
+
+#[test]
+fn test_merge_squash_strategy() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "done", 3);
+    let before = head(dir.path());
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["merge", "auth", "--strategy", "squash"]);
+    assert!(ok, "stderr: {}", stderr);
+
+    // Exactly one new single-parent commit
+    let count = run_git(dir.path(), &["rev-list", "--count", &format!("{}..HEAD", before)]);
+    assert_eq!(count, "1");
+    let parents = run_git(dir.path(), &["rev-list", "--parents", "-n", "1", "HEAD"]);
+    assert_eq!(parents.split_whitespace().count(), 2);
+    assert!(dir.path().join("auth-2.txt").exists());
+    assert_eq!(
+        get_task_from_status(dir.path(), "auth").unwrap()["status"],
+        "merged"
+    );
+}
+
+#[test]
+fn test_merge_rebase_strategy_keeps_history_linear() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "running", 2);
+    commit_file(dir.path(), "other.txt", "other", "other work");
+    let before = head(dir.path());
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["merge", "auth", "--strategy", "rebase"]);
+    assert!(ok, "stderr: {}", stderr);
+
+    let count = run_git(dir.path(), &["rev-list", "--count", &format!("{}..HEAD", before)]);
+    assert_eq!(count, "2");
+    let merges = run_git(dir.path(), &["rev-list", "--merges", "HEAD"]);
+    assert!(merges.is_empty());
+    assert_eq!(
+        get_task_from_status(dir.path(), "auth").unwrap()["status"],
+        "merged"
+    );
+}
+
+#[test]
+fn test_merge_rebase_strategy_drops_merge_commits() {
+    let dir = setup_test_repo();
+    // A stacked parent merged into the task branch, and since landed
+    setup_task_branch(dir.path(), "db", "merged", 1);
+    setup_task_branch(dir.path(), "auth", "running", 1);
+    run_git(dir.path(), &["checkout", "-q", "wt/auth"]);
+    run_git(dir.path(), &["merge", "-q", "--no-ff", "-m", "Merge wt/db into auth", "wt/db"]);
+    commit_file(dir.path(), "auth-late.txt", "late", "auth late commit");
+    run_git(dir.path(), &["checkout", "-q", "-"]);
+    run_git(dir.path(), &["merge", "-q", "--no-ff", "-m", "Merge db", "wt/db"]);
+    assert!(!run_git(dir.path(), &["rev-list", "--merges", "HEAD..wt/auth"]).is_empty());
+    let before = head(dir.path());
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["merge", "auth", "--strategy", "rebase"]);
+    assert!(ok, "stderr: {}", stderr);
+
+    // Only auth's own two commits are replayed, db's is already upstream
+    let count = run_git(dir.path(), &["rev-list", "--count", &format!("{}..HEAD", before)]);
+    assert_eq!(count, "2");
+    let merges = run_git(dir.path(), &["rev-list", "--merges", &format!("{}..HEAD", before)]);
+    assert!(merges.is_empty());
+    assert!(dir.path().join("auth-0.txt").exists());
+    assert!(dir.path().join("auth-late.txt").exists());
+    assert!(is_clean(dir.path()));
+}
+
+#[test]
+fn test_merge_refuses_dirty_main_worktree() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "done", 1);
+    fs::write(dir.path().join("README.md"), "# Changed").unwrap();
+    let before = head(dir.path());
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["merge", "auth"]);
+    assert!(!ok);
+    assert!(stderr.contains("uncommitted changes"), "stderr: {}", stderr);
+
+    // Nothing touched: local change kept, no commit, status unchanged
+    assert_eq!(fs::read_to_string(dir.path().join("README.md")).unwrap(), "# Changed");
+    assert_eq!(head(dir.path()), before);
+    assert_eq!(
+        get_task_from_status(dir.path(), "auth").unwrap()["status"],
+        "done"
+    );
+}
+
+#[test]
+fn test_merge_conflict_leaves_repo_clean() {
+    for strategy in ["merge", "squash", "rebase"] {
+        let dir = setup_test_repo();
+        let base = run_git(dir.path(), &["rev-parse", "--abbrev-ref", "HEAD"]);
+        run_git(dir.path(), &["checkout", "-q", "-b", "wt/auth"]);
+        commit_file(dir.path(), "README.md", "# From task", "task edit");
+        run_git(dir.path(), &["checkout", "-q", &base]);
+        commit_file(dir.path(), "README.md", "# From base", "base edit");
+        create_task_file(dir.path(), "auth", &[]);
+        set_task_status_with_instance(
+            dir.path(),
+            "auth",
+            "done",
+            Some(serde_json::json!({
+                "branch": "wt/auth",
+                "worktree_path": dir.path().join(".wt/worktrees/auth").to_string_lossy(),
+                "tmux_session": "test-wt",
+                "tmux_window": "auth"
+            })),
+        );
+        let before = head(dir.path());
+
+        let (ok, _, stderr) = run_wt(dir.path(), &["merge", "auth", "--strategy", strategy]);
+        assert!(!ok, "{} should fail on conflict", strategy);
+        assert!(stderr.contains("aborted"), "{}: {}", strategy, stderr);
+
+        assert!(is_clean(dir.path()), "{} left the repo dirty", strategy);
+        assert_eq!(head(dir.path()), before, "{} moved HEAD", strategy);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("README.md")).unwrap(),
+            "# From base"
+        );
+        assert_eq!(
+            get_task_from_status(dir.path(), "auth").unwrap()["status"],
+            "done"
+        );
+    }
+}
+
+#[test]
+fn test_merge_requires_base_checked_out() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "done", 1);
+    run_git(dir.path(), &["checkout", "-q", "-b", "feature"]);
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["merge", "auth"]);
+    assert!(!ok);
+    assert!(stderr.contains("checked out"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_merge_branch_without_commits() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "done", 0);
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["merge", "auth"]);
+    assert!(!ok);
+    assert!(stderr.contains("no commits"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_merge_pending_task() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "pending")]);
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["merge", "auth"]);
+    assert!(!ok);
+    assert!(stderr.contains("Invalid state transition"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_merge_records_event() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "done", 1);
+
+    let (ok, _, _) = run_wt(dir.path(), &["merge", "auth"]);
+    assert!(ok);
+
+    let (_, stdout, _) = run_wt(dir.path(), &["history", "auth", "--json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let events = json["events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["actor"], "cli");
+    assert_eq!(events[0]["to"], "merged");
+}
@@ -162,3 +162,34 @@ fn test_next_json_multiple_blockers() {
     let waiting_for = json["blocked"][0]["waiting_for"].as_array().unwrap();
     assert_eq!(waiting_for.len(), 2);
 }
+
+// ==================== Ordering ====================
+
+#[test]
+fn test_next_json_orders_by_priority_then_critical_path() {
+    let dir = setup_repo_with_tasks(&[
+        ("aaa", &[], "pending"),
+        ("base", &[], "pending"),
+        ("top", &["base"], "pending"),
+    ]);
+    fs::write(
+        dir.path().join(".wt/tasks/zzz.md"),
+        "---\nname: zzz\npriority: 5\n---\n\nTask zzz",
+    )
+    .unwrap();
+
+    let (ok, stdout, _) = run_wt(dir.path(), &["next", "--json"]);
+    assert!(ok);
+
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let ready: Vec<(&str, u64)> = json["ready"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| (v["name"].as_str().unwrap(), v["index"].as_u64().unwrap()))
+        .collect();
+
+    // Ordered by priority, then by how much work waits downstream; indexes stay alphabetical
+    assert_eq!(ready, vec![("zzz", 4), ("base", 2), ("aaa", 1)]);
+    assert_eq!(json["ready"][0]["priority"], 5);
+}
@@ -0,0 +1,43 @@
+//! CLI tests for `wt restart`
+
+use super::*;
+
+#[test]
+fn test_restart_requires_started_task() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "pending")]);
+
+    let (ok, _stdout, stderr) = run_wt(dir.path(), &["restart", "auth"]);
+
+    assert!(!ok);
+    assert!(
+        stderr.contains("Cannot restart 'auth': task is pending (need running or done)"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_restart_requires_worktree() {
+    let dir = setup_test_repo();
+    create_task_file(dir.path(), "auth", &[]);
+    set_task_status_with_instance(
+        dir.path(),
+        "auth",
+        "done",
+        Some(serde_json::json!({
+            "branch": "wt/auth-1234",
+            "worktree_path": dir.path().join(".wt/worktrees/auth").to_string_lossy(),
+            "tmux_session": "test-wt",
+            "tmux_window": "auth",
+            "session_id": "1234-abcd"
+        })),
+    );
+
+    let (ok, _stdout, stderr) = run_wt(dir.path(), &["restart", "auth", "--keep-history"]);
+
+    assert!(!ok);
+    assert!(stderr.contains("worktree no longer exists"), "stderr: {}", stderr);
+    let state = get_task_from_status(dir.path(), "auth").unwrap();
+    assert_eq!(state["status"], "done");
+    assert_eq!(state["instance"]["session_id"], "1234-abcd");
+}
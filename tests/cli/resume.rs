@@ -0,0 +1,57 @@
+//! CLI tests for `wt resume`
+
+use std::path::Path;
+
+use super::*;
+
+fn setup_done_task(dir: &Path, name: &str, agent: Option<&str>) {
+    let worktree = dir.join(".wt/worktrees").join(name);
+    fs::create_dir_all(&worktree).unwrap();
+    create_task_file(dir, name, &[]);
+    let mut instance = serde_json::json!({
+        "branch": format!("wt/{}", name),
+        "worktree_path": worktree.to_string_lossy(),
+        "tmux_session": "test-wt",
+        "tmux_window": name,
+        "session_id": "1234-abcd"
+    });
+    if let Some(agent) = agent {
+        instance["agent"] = serde_json::json!(agent);
+    }
+    set_task_status_with_instance(dir, name, "done", Some(instance));
+}
+
+#[test]
+fn test_resume_requires_done_task() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "pending")]);
+
+    let (ok, _stdout, stderr) = run_wt(dir.path(), &["resume", "auth"]);
+
+    assert!(!ok);
+    assert!(stderr.contains("Cannot resume 'auth': task is pending (need done)"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_resume_requires_worktree() {
+    let dir = setup_test_repo();
+    setup_done_task(dir.path(), "auth", None);
+    fs::remove_dir_all(dir.path().join(".wt/worktrees/auth")).unwrap();
+
+    let (ok, _stdout, stderr) = run_wt(dir.path(), &["resume", "auth"]);
+
+    assert!(!ok);
+    assert!(stderr.contains("worktree no longer exists"), "stderr: {}", stderr);
+    assert_eq!(get_task_from_status(dir.path(), "auth").unwrap()["status"], "done");
+}
+
+#[test]
+fn test_resume_shell_agent_is_rejected() {
+    let dir = setup_test_repo();
+    setup_done_task(dir.path(), "auth", Some("shell"));
+
+    let (ok, _stdout, stderr) = run_wt(dir.path(), &["resume", "auth", "--prompt", "continue"]);
+
+    assert!(!ok);
+    assert!(stderr.contains("the shell agent has no session to resume"), "stderr: {}", stderr);
+    assert_eq!(get_task_from_status(dir.path(), "auth").unwrap()["status"], "done");
+}
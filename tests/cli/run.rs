@@ -0,0 +1,59 @@
+//! CLI tests for wt run scheduler and max_parallel
+
+use super::*;
+
+fn set_max_parallel(dir: &std::path::Path, max: usize) {
+    fs::write(
+        dir.join(".wt/config.yaml"),
+        format!("start_args: -p test\ntmux_session: test-wt\nmax_parallel: {}\n", max),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_run_exits_when_all_tasks_finished() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "merged"), ("api", &["auth"], "archived")]);
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["run", "--interval", "0"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("All tasks finished"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_run_detects_merged_branch_as_completion() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "running", 1);
+    run_git(dir.path(), &["merge", "-q", "--no-ff", "-m", "Merge auth", "wt/auth"]);
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["run", "--interval", "0"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("'auth' merged"), "stdout: {}", stdout);
+    assert_eq!(
+        get_task_from_status(dir.path(), "auth").unwrap()["status"],
+        "merged"
+    );
+}
+
+#[test]
+fn test_run_stops_when_graph_cannot_progress() {
+    let dir = setup_repo_with_tasks(&[("api", &["missing"], "pending")]);
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["run", "--interval", "0"]);
+    assert!(!ok);
+    assert!(stderr.contains("can never start"), "stderr: {}", stderr);
+    assert!(stderr.contains("api (waiting for missing)"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_start_all_respects_max_parallel() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "running"), ("api", &[], "pending")]);
+    set_max_parallel(dir.path(), 1);
+
+    let (ok, stdout, _) = run_wt(dir.path(), &["start", "--all"]);
+    assert!(ok);
+    assert!(stdout.contains("max_parallel: 1"), "stdout: {}", stdout);
+    assert_eq!(
+        get_task_from_status(dir.path(), "api").unwrap()["status"],
+        "pending"
+    );
+}
@@ -0,0 +1,73 @@
+//! CLI tests for `wt send` and `status --action send`
+
+use super::*;
+
+#[test]
+fn test_send_requires_running_task() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "pending")]);
+
+    let (ok, _stdout, stderr) = run_wt(dir.path(), &["send", "auth", "keep going"]);
+
+    assert!(!ok);
+    assert!(stderr.contains("Cannot send to 'auth': task is pending (need running)"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_send_rejects_closed_window() {
+    let dir = setup_test_repo();
+    create_task_file(dir.path(), "auth", &[]);
+    set_task_status_with_instance(
+        dir.path(),
+        "auth",
+        "running",
+        Some(serde_json::json!({
+            "branch": "wt/auth",
+            "worktree_path": dir.path().join(".wt/worktrees/auth").to_string_lossy(),
+            "tmux_session": "test-wt",
+            "tmux_window": "auth"
+        })),
+    );
+
+    let (ok, _stdout, stderr) = run_wt(dir.path(), &["send", "auth", "keep going"]);
+
+    assert!(!ok);
+    assert!(stderr.contains("is closed"), "stderr: {}", stderr);
+    let state = get_task_from_status(dir.path(), "auth").unwrap();
+    assert!(state.get("messages").is_none());
+}
+
+#[test]
+fn test_send_rejects_empty_message() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "running")]);
+
+    let (ok, _stdout, stderr) = run_wt(dir.path(), &["send", "auth", "  "]);
+
+    assert!(!ok);
+    assert!(stderr.contains("Message is empty"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_status_action_send_reports_json_error() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "done")]);
+
+    let (ok, stdout, _stderr) = run_wt(
+        dir.path(),
+        &["status", "--action", "send", "--task", "auth", "--message", "hi"],
+    );
+
+    assert!(!ok);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["action"], "send");
+    assert_eq!(json["success"], false);
+    assert!(json["error"].as_str().unwrap().contains("need running"));
+}
+
+#[test]
+fn test_status_action_list_includes_send() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "done")]);
+
+    let (_ok, stdout, _stderr) = run_wt(dir.path(), &["status", "--action", "list", "--task", "auth"]);
+
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(json["unavailable_actions"]["send"].is_string());
+}
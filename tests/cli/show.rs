@@ -0,0 +1,87 @@
+//! CLI tests for wt show and per-task launch overrides
+
+use super::*;
+
+fn write_config(dir: &std::path::Path, extra: &str) {
+    fs::write(
+        dir.join(".wt/config.yaml"),
+        format!("start_args: -p test\ntmux_session: test-wt\n{}", extra),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_show_json_uses_config_without_overrides() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "pending")]);
+    write_config(dir.path(), "init_script: npm i\nenv:\n  PORT: '3000'\n");
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["show", "auth", "--json"]);
+    assert!(ok, "stderr: {}", stderr);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+
+    assert_eq!(json["name"], "auth");
+    assert_eq!(json["status"], "pending");
+    assert_eq!(json["launch"]["agent"], "claude");
+    assert_eq!(json["launch"]["command"], "claude");
+    assert_eq!(json["launch"]["start_args"], "-p test");
+    assert_eq!(json["launch"]["init_script"], "npm i");
+    assert_eq!(json["launch"]["env"]["PORT"], "3000");
+    assert!(json["launch"].get("overridden").is_none());
+}
+
+#[test]
+fn test_show_task_overrides_merge_over_config() {
+    let dir = setup_test_repo();
+    write_config(dir.path(), "env:\n  PORT: '3000'\n  MODE: dev\n");
+    create_task_file(dir.path(), "big", &[]);
+    fs::write(
+        dir.path().join(".wt/tasks/big.md"),
+        "---\nname: big\nagent_command: claude --yolo\nstart_args: -p big\nmodel: opus\ninit_script: make deps\nenv:\n  MODE: ci\n---\n\nBig task",
+    )
+    .unwrap();
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["show", "big", "--json"]);
+    assert!(ok, "stderr: {}", stderr);
+    let launch = &serde_json::from_str::<serde_json::Value>(&stdout).unwrap()["launch"];
+
+    assert_eq!(launch["command"], "claude --yolo");
+    assert_eq!(launch["start_args"], "-p big");
+    assert_eq!(launch["model"], "opus");
+    assert_eq!(launch["init_script"], "make deps");
+    assert_eq!(launch["env"]["PORT"], "3000");
+    assert_eq!(launch["env"]["MODE"], "ci");
+    assert_eq!(
+        launch["overridden"],
+        serde_json::json!(["command", "start_args", "model", "env", "init_script"])
+    );
+
+    let (ok, stdout, _) = run_wt(dir.path(), &["show", "big"]);
+    assert!(ok);
+    assert!(stdout.contains("Model:      opus"), "stdout: {}", stdout);
+    assert!(stdout.contains("MODE=ci"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_show_nonexistent_task() {
+    let dir = setup_test_repo();
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["show", "missing"]);
+    assert!(!ok);
+    assert!(stderr.contains("not found"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_validate_reports_bad_overrides() {
+    let dir = setup_test_repo();
+    create_task_file(dir.path(), "bad", &[]);
+    fs::write(
+        dir.path().join(".wt/tasks/bad.md"),
+        "---\nname: bad\nagent_command: ''\nenv:\n  NO-DASH: x\n---\n\nBad task",
+    )
+    .unwrap();
+
+    let (ok, stdout, _) = run_wt(dir.path(), &["validate"]);
+    assert!(ok);
+    assert!(stdout.contains("agent_command is empty"), "stdout: {}", stdout);
+    assert!(stdout.contains("env name 'NO-DASH'"), "stdout: {}", stdout);
+}
@@ -0,0 +1,221 @@
+//! CLI tests for stacked task branches (`stack: true`) and wt restack
+
+use std::path::{Path, PathBuf};
+
+use super::*;
+
+fn enable_stack(dir: &Path) {
+    fs::write(
+        dir.join(".wt/config.yaml"),
+        "start_args: -p test\ntmux_session: test-wt\nstack: true\n",
+    )
+    .unwrap();
+}
+
+/// Create a real worktree for a task on `wt/<name>` starting at `start`, with
+/// one commit of its own, and register it as a stacked instance.
+fn add_task_worktree(dir: &Path, name: &str, start: &str, parents: &[&str], status: &str) -> PathBuf {
+    let worktree = dir.join(".wt/worktrees").join(name);
+    let branch = format!("wt/{}", name);
+    run_git(
+        dir,
+        &["worktree", "add", "-q", "-b", &branch, &worktree.to_string_lossy(), start],
+    );
+    let fork_point = run_git(&worktree, &["rev-parse", "HEAD"]);
+    commit_file(&worktree, &format!("{}.txt", name), name, &format!("{} work", name));
+
+    create_task_file(dir, name, parents);
+    set_task_status_with_instance(
+        dir,
+        name,
+        status,
+        Some(serde_json::json!({
+            "branch": branch,
+            "worktree_path": worktree.to_string_lossy(),
+            "tmux_session": "test-wt",
+            "tmux_window": name,
+            "base_commit": fork_point,
+            "stack_parents": parents,
+        })),
+    );
+    worktree
+}
+
+fn branch_has_file(dir: &Path, branch: &str, file: &str) -> bool {
+    run_git(dir, &["ls-tree", "--name-only", branch]).lines().any(|l| l == file)
+}
+
+// ==================== Readiness ====================
+
+#[test]
+fn test_next_stack_mode_unblocks_on_done() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "done"), ("api", &["auth"], "pending")]);
+
+    let (_, stdout, _) = run_wt(dir.path(), &["next", "--json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["blocked"][0]["name"], "api");
+
+    enable_stack(dir.path());
+    let (_, stdout, _) = run_wt(dir.path(), &["next", "--json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["ready"][0]["name"], "api");
+}
+
+#[test]
+fn test_next_frontmatter_stack_overrides_config() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "done")]);
+    fs::write(
+        dir.path().join(".wt/tasks/api.md"),
+        "---\nname: api\ndepends:\n  - auth\nstack: true\n---\n\nTask api",
+    )
+    .unwrap();
+
+    let (_, stdout, _) = run_wt(dir.path(), &["next", "--json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["ready"][0]["name"], "api");
+}
+
+#[test]
+fn test_start_stacked_requires_done_dependency() {
+    let dir = setup_repo_with_tasks(&[("auth", &[], "running"), ("api", &["auth"], "pending")]);
+    enable_stack(dir.path());
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["start", "api"]);
+    assert!(!ok);
+    assert!(stderr.contains("is not done or merged"), "stderr: {}", stderr);
+}
+
+// ==================== Restack ====================
+
+#[test]
+fn test_restack_picks_up_upstream_changes() {
+    let dir = setup_test_repo();
+    let auth = add_task_worktree(dir.path(), "auth", "HEAD", &[], "done");
+    add_task_worktree(dir.path(), "api", "wt/auth", &["auth"], "running");
+
+    // Upstream gets more work after api was stacked on it
+    commit_file(&auth, "auth-fix.txt", "fix", "auth fix");
+    assert!(!branch_has_file(dir.path(), "wt/api", "auth-fix.txt"));
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["restack", "api"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("api: rebased onto wt/auth"), "stdout: {}", stdout);
+
+    assert!(branch_has_file(dir.path(), "wt/api", "auth-fix.txt"));
+    assert!(branch_has_file(dir.path(), "wt/api", "api.txt"));
+    let task = get_task_from_status(dir.path(), "api").unwrap();
+    assert_eq!(
+        task["instance"]["base_commit"],
+        run_git(dir.path(), &["rev-parse", "wt/auth"])
+    );
+}
+
+#[test]
+fn test_restack_onto_base_after_upstream_merged() {
+    let dir = setup_test_repo();
+    add_task_worktree(dir.path(), "auth", "HEAD", &[], "done");
+    add_task_worktree(dir.path(), "api", "wt/auth", &["auth"], "done");
+
+    // Upstream lands as a squash, then is marked merged
+    run_git(dir.path(), &["merge", "-q", "--squash", "wt/auth"]);
+    run_git(dir.path(), &["commit", "-q", "-m", "Squashed auth"]);
+    set_task_status_with_instance(dir.path(), "auth", "merged", None);
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["restack", "api"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("api: rebased"), "stdout: {}", stdout);
+
+    // Only api's own commit is left on top of base
+    let base = run_git(dir.path(), &["rev-parse", "--abbrev-ref", "HEAD"]);
+    let ahead = run_git(dir.path(), &["rev-list", "--count", &format!("{}..wt/api", base)]);
+    assert_eq!(ahead, "1");
+    let task = get_task_from_status(dir.path(), "api").unwrap();
+    assert!(task["instance"].get("stack_parents").is_none());
+}
+
+#[test]
+fn test_restack_updates_whole_stack() {
+    let dir = setup_test_repo();
+    let auth = add_task_worktree(dir.path(), "auth", "HEAD", &[], "done");
+    add_task_worktree(dir.path(), "api", "wt/auth", &["auth"], "done");
+    add_task_worktree(dir.path(), "ui", "wt/api", &["api"], "running");
+
+    commit_file(&auth, "auth-fix.txt", "fix", "auth fix");
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["restack", "api"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("api: rebased"));
+    assert!(stdout.contains("ui: rebased onto wt/api"), "stdout: {}", stdout);
+
+    assert!(branch_has_file(dir.path(), "wt/ui", "auth-fix.txt"));
+    assert!(branch_has_file(dir.path(), "wt/ui", "api.txt"));
+    assert!(branch_has_file(dir.path(), "wt/ui", "ui.txt"));
+}
+
+#[test]
+fn test_restack_multiple_upstreams() {
+    let dir = setup_test_repo();
+    let auth = add_task_worktree(dir.path(), "auth", "HEAD", &[], "done");
+    add_task_worktree(dir.path(), "db", "HEAD", &[], "done");
+
+    // Stack api on both, as start would: fork from auth and merge db in
+    add_task_worktree(dir.path(), "api", "wt/auth", &["auth", "db"], "running");
+    let api = dir.path().join(".wt/worktrees/api");
+    run_git(&api, &["reset", "-q", "--hard", "HEAD~1"]);
+    run_git(&api, &["merge", "-q", "--no-ff", "-m", "Stack api", "wt/db"]);
+    let fork_point = run_git(&api, &["rev-parse", "HEAD"]);
+    commit_file(&api, "api.txt", "api", "api work");
+    let mut instance = get_task_from_status(dir.path(), "api").unwrap()["instance"].clone();
+    instance["base_commit"] = serde_json::json!(fork_point);
+    set_task_status_with_instance(dir.path(), "api", "running", Some(instance));
+
+    commit_file(&auth, "auth-fix.txt", "fix", "auth fix");
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["restack", "api"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("rebased onto wt/auth, wt/db"), "stdout: {}", stdout);
+
+    for file in ["auth-fix.txt", "auth.txt", "db.txt", "api.txt"] {
+        assert!(branch_has_file(dir.path(), "wt/api", file), "missing {}", file);
+    }
+    // Back on the task branch, not left detached
+    assert_eq!(run_git(&api, &["rev-parse", "--abbrev-ref", "HEAD"]), "wt/api");
+}
+
+#[test]
+fn test_restack_conflict_leaves_branch_unchanged() {
+    let dir = setup_test_repo();
+    let auth = add_task_worktree(dir.path(), "auth", "HEAD", &[], "done");
+    let api = add_task_worktree(dir.path(), "api", "wt/auth", &["auth"], "running");
+    commit_file(&api, "shared.txt", "from api", "api edit");
+    commit_file(&auth, "shared.txt", "from auth", "auth edit");
+    let before = run_git(dir.path(), &["rev-parse", "wt/api"]);
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["restack", "api"]);
+    assert!(!ok);
+    assert!(stderr.contains("aborted"), "stderr: {}", stderr);
+
+    assert_eq!(run_git(dir.path(), &["rev-parse", "wt/api"]), before);
+    assert!(run_git(&api, &["status", "--porcelain", "--untracked-files=no"]).is_empty());
+}
+
+#[test]
+fn test_restack_not_stacked() {
+    let dir = setup_test_repo();
+    add_task_worktree(dir.path(), "auth", "HEAD", &[], "done");
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["restack", "auth"]);
+    assert!(!ok);
+    assert!(stderr.contains("not stacked"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_merge_refuses_unmerged_stack_parent() {
+    let dir = setup_test_repo();
+    add_task_worktree(dir.path(), "auth", "HEAD", &[], "done");
+    add_task_worktree(dir.path(), "api", "wt/auth", &["auth"], "done");
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["merge", "api"]);
+    assert!(!ok);
+    assert!(stderr.contains("stacked on 'auth'"), "stderr: {}", stderr);
+}
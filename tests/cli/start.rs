@@ -31,3 +31,21 @@ fn test_start_already_running() {
     assert!(!ok);
     assert!(stderr.contains("already") || stderr.contains("running"));
 }
+
+#[test]
+fn test_start_shell_agent_requires_shell_command() {
+    let dir = setup_test_repo();
+    create_task_file(dir.path(), "task", &[]);
+    fs::write(
+        dir.path().join(".wt/tasks/task.md"),
+        "---\nname: task\nagent: shell\n---\n\nTask task",
+    )
+    .unwrap();
+
+    let (ok, _, stderr) = run_wt(dir.path(), &["start", "task"]);
+
+    assert!(!ok);
+    assert!(stderr.contains("set shell_command"), "stderr: {}", stderr);
+    // Nothing was created for the failed start
+    assert!(!dir.path().join(".wt/worktrees/task").exists());
+}
@@ -0,0 +1,121 @@
+//! CLI tests for wt sync command (automatic merge detection)
+
+use super::*;
+
+#[test]
+fn test_sync_detects_regular_merge() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "done", 2);
+    run_git(dir.path(), &["merge", "-q", "--no-ff", "-m", "Merge auth", "wt/auth"]);
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["sync"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("auth: merged (merge)"), "stdout: {}", stdout);
+
+    let task = get_task_from_status(dir.path(), "auth").unwrap();
+    assert_eq!(task["status"], "merged");
+    // Instance is kept for archive
+    assert!(task["instance"].is_object());
+}
+
+#[test]
+fn test_sync_detects_squash_merge() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "done", 2);
+    // Unrelated work lands on base first so the trees differ
+    commit_file(dir.path(), "other.txt", "other", "other work");
+    run_git(dir.path(), &["merge", "-q", "--squash", "wt/auth"]);
+    run_git(dir.path(), &["commit", "-q", "-m", "Squashed auth"]);
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["sync"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("auth: merged (squash)"), "stdout: {}", stdout);
+    assert_eq!(
+        get_task_from_status(dir.path(), "auth").unwrap()["status"],
+        "merged"
+    );
+}
+
+#[test]
+fn test_sync_detects_rebase_merge() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "running", 2);
+    commit_file(dir.path(), "other.txt", "other", "other work");
+    let base = run_git(dir.path(), &["rev-parse", "--abbrev-ref", "HEAD"]);
+    let commits = run_git(dir.path(), &["rev-list", "--reverse", &format!("{}..wt/auth", base)]);
+    for commit in commits.lines() {
+        run_git(dir.path(), &["cherry-pick", commit]);
+    }
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["sync"]);
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("auth: merged (rebase)"), "stdout: {}", stdout);
+    assert_eq!(
+        get_task_from_status(dir.path(), "auth").unwrap()["status"],
+        "merged"
+    );
+}
+
+#[test]
+fn test_sync_leaves_unmerged_task() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "done", 1);
+    commit_file(dir.path(), "other.txt", "other", "other work");
+
+    let (ok, stdout, _) = run_wt(dir.path(), &["sync"]);
+    assert!(ok);
+    assert!(stdout.contains("No merged tasks detected"));
+    assert_eq!(
+        get_task_from_status(dir.path(), "auth").unwrap()["status"],
+        "done"
+    );
+}
+
+#[test]
+fn test_sync_ignores_branch_without_commits() {
+    let dir = setup_test_repo();
+    // Branch tip is still an ancestor of base, but no work was done on it
+    setup_task_branch(dir.path(), "auth", "running", 0);
+
+    let (ok, _, _) = run_wt(dir.path(), &["sync"]);
+    assert!(ok);
+    assert_eq!(
+        get_task_from_status(dir.path(), "auth").unwrap()["status"],
+        "running"
+    );
+}
+
+#[test]
+fn test_sync_unblocks_dependents() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "done", 1);
+    create_task_file(dir.path(), "api", &["auth"]);
+    run_git(dir.path(), &["merge", "-q", "wt/auth"]);
+
+    let (_, stdout, _) = run_wt(dir.path(), &["next"]);
+    assert!(stdout.contains("Blocked"));
+
+    let (ok, _, _) = run_wt(dir.path(), &["sync"]);
+    assert!(ok);
+
+    let (_, stdout, _) = run_wt(dir.path(), &["next"]);
+    assert!(stdout.contains("Ready"), "stdout: {}", stdout);
+    assert!(!stdout.contains("Blocked"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_sync_records_auto_event() {
+    let dir = setup_test_repo();
+    setup_task_branch(dir.path(), "auth", "done", 1);
+    run_git(dir.path(), &["merge", "-q", "wt/auth"]);
+
+    run_wt(dir.path(), &["sync"]);
+
+    let (ok, stdout, _) = run_wt(dir.path(), &["history", "auth", "--json"]);
+    assert!(ok);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let events = json["events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["actor"], "auto");
+    assert_eq!(events[0]["to"], "merged");
+}
@@ -191,3 +191,189 @@ fn test_tail_scratch_task() {
         stderr
     );
 }
+
+// ==================== Shell Agent ====================
+
+#[test]
+fn test_tail_shell_agent_reads_output_log() {
+    let dir = setup_test_repo();
+    let worktree = dir.path().join(".wt/worktrees/task1");
+    std::fs::create_dir_all(&worktree).unwrap();
+
+    create_task_file(dir.path(), "task1", &[]);
+    set_task_status_with_instance(
+        dir.path(),
+        "task1",
+        "done",
+        Some(json!({
+            "branch": "wt/task1",
+            "worktree_path": worktree.to_string_lossy(),
+            "tmux_session": "test-session",
+            "tmux_window": "task1",
+            "agent": "shell"
+        })),
+    );
+    std::fs::create_dir_all(dir.path().join(".wt/logs/task1")).unwrap();
+    std::fs::write(
+        dir.path().join(".wt/logs/task1/agent.log"),
+        "building\n\ntests passed\nall done\n",
+    )
+    .unwrap();
+
+    let (ok, stdout, stderr) = run_wt(dir.path(), &["tail", "task1", "-n", "2"]);
+
+    assert!(ok, "stderr: {}", stderr);
+    let messages: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(messages[0]["content"], "tests passed");
+    assert_eq!(messages[1]["content"], "all done");
+}
+
+// ==================== Multiple Sessions ====================
+
+#[test]
+fn test_tail_reaches_into_resumed_from_session() {
+    let dir = setup_test_repo();
+    let home = tempfile::tempdir().unwrap();
+    create_task_file(dir.path(), "task1", &[]);
+    let worktree = dir.path().join(".wt/worktrees/task1");
+    std::fs::create_dir_all(&worktree).unwrap();
+    let worktree = worktree.to_string_lossy().to_string();
+    set_task_status_with_instance(
+        dir.path(),
+        "task1",
+        "done",
+        Some(json!({
+            "branch": "wt/task1",
+            "worktree_path": worktree,
+            "tmux_session": "test-wt",
+            "tmux_window": "task1",
+            "session_id": "second"
+        })),
+    );
+
+    // "second" was resumed from "first", which wt never recorded
+    let project = home.path().join(".claude/projects").join(worktree.replace(['/', '.'], "-"));
+    std::fs::create_dir_all(&project).unwrap();
+    let reply = |uuid: &str, parent: Option<&str>, text: &str, timestamp: &str| {
+        json!({
+            "type": "assistant",
+            "timestamp": timestamp,
+            "uuid": uuid,
+            "parentUuid": parent,
+            "message": {"content": [{"type": "text", "text": text}]}
+        })
+        .to_string()
+    };
+    std::fs::write(project.join("first.jsonl"), reply("a", None, "one", "2025-01-01T00:00:00Z") + "\n").unwrap();
+    std::fs::write(project.join("second.jsonl"), reply("b", Some("a"), "two", "2025-01-02T00:00:00Z") + "\n").unwrap();
+
+    let (ok, stdout, stderr) = run_wt_with_home(dir.path(), home.path(), &["tail", "task1", "-n", "2"]);
+
+    assert!(ok, "stderr: {}", stderr);
+    let messages: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(messages[0]["content"], "one");
+    assert_eq!(messages[1]["content"], "two");
+}
+
+// ==================== Event Stream ====================
+
+/// Task "task1" with one Claude session under `home`
+fn setup_session(lines: &[serde_json::Value]) -> (tempfile::TempDir, tempfile::TempDir) {
+    let dir = setup_test_repo();
+    let home = tempfile::tempdir().unwrap();
+    create_task_file(dir.path(), "task1", &[]);
+    let worktree = dir.path().join(".wt/worktrees/task1");
+    std::fs::create_dir_all(&worktree).unwrap();
+    let worktree = worktree.to_string_lossy().to_string();
+    set_task_status_with_instance(
+        dir.path(),
+        "task1",
+        "running",
+        Some(json!({
+            "branch": "wt/task1",
+            "worktree_path": worktree,
+            "tmux_session": "test-wt",
+            "tmux_window": "task1",
+            "session_id": "s1"
+        })),
+    );
+    let project = home.path().join(".claude/projects").join(worktree.replace(['/', '.'], "-"));
+    std::fs::create_dir_all(&project).unwrap();
+    let content: String = lines.iter().map(|l| format!("{}\n", l)).collect();
+    std::fs::write(project.join("s1.jsonl"), content).unwrap();
+    (dir, home)
+}
+
+fn session_lines() -> Vec<serde_json::Value> {
+    vec![
+        json!({"type": "user", "timestamp": "2025-01-01T00:00:00Z", "message": {"content": "fix it"}}),
+        json!({"type": "assistant", "timestamp": "2025-01-01T00:00:01Z", "message": {"content": [
+            {"type": "tool_use", "id": "t1", "name": "Bash", "input": {"command": "cargo test"}}
+        ]}}),
+        json!({"type": "user", "timestamp": "2025-01-01T00:00:02Z", "message": {"content": [
+            {"type": "tool_result", "tool_use_id": "t1", "content": "ok", "is_error": false}
+        ]}}),
+        json!({"type": "assistant", "timestamp": "2025-01-01T00:00:03Z", "message": {"content": [
+            {"type": "text", "text": "Fixed."}
+        ]}}),
+    ]
+}
+
+#[test]
+fn test_tail_default_output_unchanged() {
+    let (dir, home) = setup_session(&session_lines());
+
+    let (ok, stdout, stderr) = run_wt_with_home(dir.path(), home.path(), &["tail", "task1"]);
+
+    assert!(ok, "stderr: {}", stderr);
+    let output: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(output, json!([{"role": "assistant", "content": "Fixed."}]));
+}
+
+#[test]
+fn test_tail_types_filter_events() {
+    let (dir, home) = setup_session(&session_lines());
+
+    let (ok, stdout, stderr) = run_wt_with_home(
+        dir.path(),
+        home.path(),
+        &["tail", "task1", "--types", "tool_use,tool_result"],
+    );
+
+    assert!(ok, "stderr: {}", stderr);
+    let events: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let events = events.as_array().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["type"], "tool_use");
+    assert_eq!(events[0]["tool"], "Bash");
+    assert_eq!(events[0]["input"]["command"], "cargo test");
+    assert_eq!(events[1]["type"], "tool_result");
+    assert_eq!(events[1]["text"], "ok");
+}
+
+#[test]
+fn test_tail_since_and_markdown() {
+    let (dir, home) = setup_session(&session_lines());
+
+    let (ok, stdout, stderr) = run_wt_with_home(
+        dir.path(),
+        home.path(),
+        &["tail", "task1", "--since", "2025-01-01T00:00:02Z", "--format", "markdown"],
+    );
+
+    assert!(ok, "stderr: {}", stderr);
+    assert!(stdout.contains("### tool_result: Bash"), "stdout: {}", stdout);
+    assert!(stdout.contains("### text"), "stdout: {}", stdout);
+    assert!(stdout.contains("Fixed."), "stdout: {}", stdout);
+    assert!(!stdout.contains("fix it"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_tail_rejects_invalid_since() {
+    let (dir, home) = setup_session(&session_lines());
+
+    let (ok, _stdout, stderr) = run_wt_with_home(dir.path(), home.path(), &["tail", "task1", "--since", "soon"]);
+
+    assert!(!ok);
+    assert!(stderr.contains("Invalid --since 'soon'"), "stderr: {}", stderr);
+}
@@ -55,3 +55,29 @@ fn test_validate_detects_cycle() {
 
     assert!(stdout.contains("circular") || stdout.contains("error"));
 }
+
+#[test]
+fn test_validate_reports_unknown_template_variables() {
+    let dir = setup_test_repo();
+    fs::write(
+        dir.path().join(".wt/config.yaml"),
+        "start_args: -p ${tsak}\ntmux_session: test-wt\ninit_script: echo $${escaped} ${env.HOME}\n",
+    )
+    .unwrap();
+    create_task_file(dir.path(), "auth", &[]);
+    fs::write(
+        dir.path().join(".wt/tasks/auth.md"),
+        "---\nname: auth\ninit_script: make ${target\n---\n\nAuth",
+    )
+    .unwrap();
+
+    let (_, stdout, _) = run_wt(dir.path(), &["validate"]);
+
+    assert!(
+        stdout.contains("config: start_args: Invalid template: unknown variable '${tsak}'"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(!stdout.contains("config: init_script"), "stdout: {}", stdout);
+    assert!(stdout.contains("auth: init_script: Invalid template: unclosed"), "stdout: {}", stdout);
+}
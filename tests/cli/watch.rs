@@ -0,0 +1,62 @@
+//! CLI tests for `wt watch`
+
+use std::path::Path;
+
+use super::*;
+
+fn set_notify(dir: &Path, notify: &str) {
+    fs::write(
+        dir.join(".wt/config.yaml"),
+        format!("start_args: -p test\ntmux_session: test-wt\nnotify:\n{}", notify),
+    )
+    .unwrap();
+}
+
+fn setup_running_task(dir: &Path, name: &str) {
+    let worktree = dir.join(".wt/worktrees").join(name);
+    fs::create_dir_all(&worktree).unwrap();
+    create_task_file(dir, name, &[]);
+    set_task_status_with_instance(
+        dir,
+        name,
+        "running",
+        Some(serde_json::json!({
+            "branch": format!("wt/{}", name),
+            "worktree_path": worktree.to_string_lossy(),
+            "tmux_session": "test-wt",
+            "tmux_window": name
+        })),
+    );
+}
+
+#[test]
+fn test_watch_notifies_when_agent_exits() {
+    let dir = setup_test_repo();
+    let out = dir.path().join("notified.txt");
+    set_notify(
+        dir.path(),
+        &format!("  command: echo \"$WT_EVENT $WT_TASK\" >> '{}'\n", out.display()),
+    );
+    setup_running_task(dir.path(), "auth");
+
+    let (success, _, stderr) = run_wt(dir.path(), &["watch", "--once"]);
+    assert!(success, "stderr: {}", stderr);
+
+    // No tmux window: the agent is gone, so the task is done and reported once
+    assert_eq!(get_task_from_status(dir.path(), "auth").unwrap()["status"], "done");
+    assert_eq!(fs::read_to_string(&out).unwrap(), "finished auth\n");
+
+    run_wt(dir.path(), &["watch", "--once"]);
+    assert_eq!(fs::read_to_string(&out).unwrap(), "finished auth\n");
+}
+
+#[test]
+fn test_watch_sink_failure_is_warning() {
+    let dir = setup_test_repo();
+    set_notify(dir.path(), "  command: exit 7\n");
+    setup_running_task(dir.path(), "auth");
+
+    let (success, _, stderr) = run_wt(dir.path(), &["watch", "--once"]);
+    assert!(success);
+    assert!(stderr.contains("Notification failed: command: exit code 7"), "stderr: {}", stderr);
+}
@@ -2,6 +2,8 @@
 //!
 //! This module provides common helpers for CLI and integration tests.
 
+#![allow(dead_code)]
+
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
@@ -23,6 +25,22 @@ pub fn run_wt(dir: &Path, args: &[&str]) -> (bool, String, String) {
     )
 }
 
+/// Run wt with HOME pointed at `home`, so agent transcripts are read from there
+pub fn run_wt_with_home(dir: &Path, home: &Path, args: &[&str]) -> (bool, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_wt"))
+        .current_dir(dir)
+        .env("HOME", home)
+        .args(args)
+        .output()
+        .expect("Failed to execute wt");
+
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
 /// Setup a minimal test git repo with wt config
 pub fn setup_test_repo() -> TempDir {
     let dir = tempfile::tempdir().unwrap();
@@ -263,3 +281,56 @@ pub fn assert_wt_error(dir: &Path, args: &[&str], expected_stderr: &str) {
         expected_stderr, stderr
     );
 }
+
+/// Run git in `dir`, asserting success, and return trimmed stdout
+pub fn run_git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("Failed to run git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Write a file and commit it
+pub fn commit_file(dir: &Path, file: &str, content: &str, message: &str) {
+    fs::write(dir.join(file), content).unwrap();
+    run_git(dir, &["add", file]);
+    run_git(dir, &["commit", "-m", message]);
+}
+
+/// Create a task branch with `commits` commits on it and register it as the
+/// task's instance.
+pub fn setup_task_branch(dir: &Path, name: &str, status: &str, commits: usize) {
+    let base = run_git(dir, &["rev-parse", "--abbrev-ref", "HEAD"]);
+    let branch = format!("wt/{}", name);
+    run_git(dir, &["checkout", "-q", "-b", &branch]);
+    for i in 0..commits {
+        commit_file(
+            dir,
+            &format!("{}-{}.txt", name, i),
+            &format!("{} {}", name, i),
+            &format!("{} commit {}", name, i),
+        );
+    }
+    run_git(dir, &["checkout", "-q", &base]);
+
+    create_task_file(dir, name, &[]);
+    set_task_status_with_instance(
+        dir,
+        name,
+        status,
+        Some(serde_json::json!({
+            "branch": branch,
+            "worktree_path": dir.join(".wt/worktrees").join(name).to_string_lossy(),
+            "tmux_session": "test-wt",
+            "tmux_window": name
+        })),
+    );
+}
@@ -5,6 +5,7 @@ fn make_task(name: &str, depends: Vec<&str>) -> Task {
         frontmatter: TaskFrontmatter {
             name: name.to_string(),
             depends: depends.into_iter().map(String::from).collect(),
+            ..Default::default()
         },
         content: String::new(),
         file_path: format!(".wt/tasks/{}.md", name),
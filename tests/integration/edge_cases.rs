@@ -242,7 +242,8 @@ fn test_task_file_mismatched_name() {
 
     // Behavior depends on implementation - document it
     // Some implementations use filename, some use frontmatter name
-    assert!(ok || true, "Should handle name mismatch somehow");
+    // Should handle name mismatch somehow
+    let _ = ok;
 }
 
 // ==================== Config Edge Cases ====================
@@ -264,11 +265,10 @@ fn test_corrupted_config_yaml() {
     )
     .unwrap();
 
-    let (ok, stdout, stderr) = run_wt(dir.path(), &["list"]);
+    let (ok, _, stderr) = run_wt(dir.path(), &["list"]);
 
     // Current behavior: serde_yaml may be lenient or strict
     // Document actual behavior
-    let output = format!("{}{}", stdout, stderr);
     if !ok {
         assert!(
             stderr.contains("config") || stderr.contains("YAML") || stderr.contains("parse") || stderr.contains("Invalid"),
@@ -295,7 +295,8 @@ fn test_empty_config_yaml() {
     let (ok, _, _) = run_wt(dir.path(), &["list"]);
 
     // Empty config should use defaults or fail gracefully
-    assert!(ok || true, "Should handle empty config");
+    // Should handle empty config
+    let _ = ok;
 }
 
 // ==================== Status Consistency ====================
@@ -315,7 +316,8 @@ fn test_status_entry_without_task_file() {
 
     // List might or might not show orphaned entries
     // This documents the behavior
-    assert!(ok || true, "Should handle orphaned status entries");
+    // Should handle orphaned status entries
+    let _ = ok;
 }
 
 #[test]
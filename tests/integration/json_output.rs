@@ -10,6 +10,7 @@ fn make_task(name: &str, depends: Vec<&str>) -> Task {
         frontmatter: TaskFrontmatter {
             name: name.to_string(),
             depends: depends.into_iter().map(String::from).collect(),
+            ..Default::default()
         },
         content: format!("Content for {}", name),
         file_path: format!(".wt/tasks/{}.md", name),
@@ -91,7 +92,7 @@ fn test_list_json_schema_with_tasks() {
         "api".to_string(),
         make_task("api", vec!["auth"]),
     );
-    store.set_status("auth", TaskStatus::Running);
+    store.status.set_status("auth", TaskStatus::Running);
 
     let tasks = store.list();
     let output = serde_json::json!({
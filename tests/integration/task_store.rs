@@ -132,6 +132,7 @@ fn test_task_input_roundtrip() {
         name: "roundtrip".to_string(),
         depends: vec!["a".to_string(), "b".to_string()],
         description: "Test description".to_string(),
+        ..Default::default()
     };
 
     let markdown = input.to_markdown();